@@ -0,0 +1,63 @@
+//! Optional API key check for expensive endpoints. When `API_KEYS` (comma-separated) is
+//! set, requests to the screener and projection endpoints must carry one of the keys as
+//! `Authorization: Bearer <key>` or get 401. Every other path stays open.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use crate::error::ApiError;
+
+#[derive(Debug)]
+pub struct ApiKeys {
+    keys: HashSet<String>,
+}
+
+impl ApiKeys {
+    /// Keys from a comma-separated list; `None` when the list holds no keys
+    pub fn from_list(list: &str) -> Option<Self> {
+        let keys: HashSet<String> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(String::from)
+            .collect();
+        (!keys.is_empty()).then_some(ApiKeys { keys })
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+}
+
+/// Whether `path` needs an API key: everything under /api/screener/ plus player projections
+fn requires_key(path: &str) -> bool {
+    path.starts_with("/api/screener/")
+        || (path.starts_with("/api/players/") && path.ends_with("/projection"))
+}
+
+/// Middleware rejecting requests to protected paths without a valid bearer key
+pub async fn require_api_key(State(keys): State<Arc<ApiKeys>>, req: Request, next: Next) -> Response {
+    // CORS preflights never carry credentials
+    if req.method() == Method::OPTIONS || !requires_key(req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let key = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    match key {
+        Some(key) if keys.keys.contains(key) => next.run(req).await,
+        Some(_) => ApiError::Unauthorized("Invalid API key".to_string()).into_response(),
+        None => ApiError::Unauthorized("Missing API key; send Authorization: Bearer <key>".to_string())
+            .into_response(),
+    }
+}
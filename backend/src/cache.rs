@@ -0,0 +1,268 @@
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use crate::db;
+use crate::models::{ShootingZoneMatchupResponse, SlatePropsResponse, TopPick, UpcomingMatchupResponse, ZoneLeagueAverage};
+use crate::routes;
+use crate::util::today::Today;
+
+/// Computed result for one date, tagged with the data version it was built from
+struct CachedEntry<T> {
+    date: String,
+    data_version: String,
+    value: T,
+}
+
+/// Single-slot cache for an expensive per-date computation. An entry is only served
+/// while the data it was built from is still the latest (see `db::get_props_data_version`).
+pub struct DateCache<T> {
+    entry: RwLock<Option<CachedEntry<T>>>,
+}
+
+impl<T: Clone> DateCache<T> {
+    pub const fn new() -> Self {
+        Self { entry: RwLock::new(None) }
+    }
+
+    pub fn get(&self, date: &str, data_version: &str) -> Option<T> {
+        let entry = self.entry.read().ok()?;
+        entry
+            .as_ref()
+            .filter(|e| e.date == date && e.data_version == data_version)
+            .map(|e| e.value.clone())
+    }
+
+    pub fn put(&self, date: &str, data_version: &str, value: T) {
+        if let Ok(mut entry) = self.entry.write() {
+            *entry = Some(CachedEntry {
+                date: date.to_string(),
+                data_version: data_version.to_string(),
+                value,
+            });
+        }
+    }
+}
+
+/// Single-slot cache for a computation that depends only on the data it was built from,
+/// not the date (see `db::get_zone_data_version` and `db::get_player_stats_data_version`)
+pub struct VersionCache<T> {
+    entry: RwLock<Option<(String, T)>>,
+}
+
+impl<T: Clone> VersionCache<T> {
+    pub const fn new() -> Self {
+        Self { entry: RwLock::new(None) }
+    }
+
+    pub fn get(&self, data_version: &str) -> Option<T> {
+        let entry = self.entry.read().ok()?;
+        entry
+            .as_ref()
+            .filter(|(version, _)| version == data_version)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn put(&self, data_version: &str, value: T) {
+        if let Ok(mut entry) = self.entry.write() {
+            *entry = Some((data_version.to_string(), value));
+        }
+    }
+}
+
+/// Screener top picks (before the started-games filter is re-applied at request time)
+pub static TOP_PICKS: DateCache<Vec<TopPick>> = DateCache::new();
+
+/// Slate props grouped by game
+pub static SLATE_PROPS: DateCache<SlatePropsResponse> = DateCache::new();
+
+/// League-average zone defense, rebuilt when the nightly zone data load lands
+pub static ZONE_LEAGUE_AVERAGES: VersionCache<Vec<ZoneLeagueAverage>> = VersionCache::new();
+
+/// Sorted season averages per percentile stat among qualified players, rebuilt when the
+/// nightly player_stats load lands
+pub static STAT_DISTRIBUTIONS: VersionCache<HashMap<String, Vec<f32>>> = VersionCache::new();
+
+/// Recompute today's top picks and slate into the caches, snapshotting the picks through
+/// `writer` for CLV tracking
+async fn warm(pool: &SqlitePool, writer: &SqlitePool, today: &Today, data_version: &str) -> Result<(), sqlx::Error> {
+    let date = &today.ymd();
+    let picks = routes::line_shopping::compute_top_picks(pool, date, today).await?;
+    if let Err(e) = routes::line_shopping::snapshot_screener_picks(writer, &picks).await {
+        tracing::warn!("Failed to record top-pick snapshots: {}", e);
+    }
+    TOP_PICKS.put(date, data_version, picks);
+
+    let slate = routes::slate::compute_props_by_game(pool, date).await?;
+    SLATE_PROPS.put(date, data_version, slate);
+
+    Ok(())
+}
+
+/// Poll the props data version every `interval` and re-warm today's caches whenever
+/// fresh data lands (or the date rolls over), so the first request after an update is fast.
+pub async fn run_cache_warmer(pool: SqlitePool, writer: SqlitePool, interval: Duration) {
+    let mut warmed: Option<(String, String)> = None;
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+
+        let data_version = match db::get_props_data_version(&pool).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("Cache warmer failed to read data version: {}", e);
+                continue;
+            }
+        };
+        let today = Today::now();
+        let current = (today.ymd(), data_version);
+        if warmed.as_ref() == Some(&current) {
+            continue;
+        }
+
+        match warm(&pool, &writer, &today, &current.1).await {
+            Ok(()) => {
+                tracing::info!("Warmed top-picks and slate caches for {}", current.0);
+                warmed = Some(current);
+            }
+            Err(e) => tracing::warn!("Failed to warm caches: {}", e),
+        }
+    }
+}
+
+/// Response header reporting whether a cached endpoint was served from cache ("HIT"/"MISS")
+pub const X_CACHE: &str = "x-cache";
+
+/// In-memory cache whose entries expire `ttl` after insertion. Expired entries are
+/// dropped when read and swept on insert.
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: RwLock<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.read().ok()?;
+        let (inserted_at, value) = entries.get(key)?;
+        (inserted_at.elapsed() < self.ttl).then(|| value.clone())
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < self.ttl);
+            entries.insert(key, (Instant::now(), value));
+        }
+    }
+}
+
+/// Response caches for the matchup endpoints, whose underlying data refreshes daily
+pub struct MatchupCache {
+    /// Keyed by (player_id, opponent_id, stat_type)
+    pub upcoming: TtlCache<(i64, i64, String), UpcomingMatchupResponse>,
+    /// Keyed by (player_id, opponent_id)
+    pub shooting_zones: TtlCache<(i64, i64), ShootingZoneMatchupResponse>,
+}
+
+impl MatchupCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            upcoming: TtlCache::new(ttl),
+            shooting_zones: TtlCache::new(ttl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::*;
+
+    #[tokio::test]
+    async fn warm_fills_todays_top_picks_and_slate() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_team(&pool, 2, "AWY").await;
+        insert_game(&pool, "g1", "2026-03-05", 1, 2).await;
+        insert_prop(&pool, Prop {
+            player: "Alpha Guard",
+            team: "HOM Team",
+            opponent: "AWY Team",
+            stat: "points",
+            line: 20.5,
+            choice: "over",
+            price: -115,
+            scheduled_at: "2026-03-06T00:30:00Z",
+            updated_at: "2026-03-05 12:00:00",
+        })
+        .await;
+
+        let today = Today::fixed("2026-03-05");
+        warm(&pool, &pool, &today, "warm-test").await.unwrap();
+
+        assert!(TOP_PICKS.get("2026-03-05", "warm-test").is_some());
+        let slate = SLATE_PROPS.get("2026-03-05", "warm-test").expect("slate cached");
+        assert_eq!(slate.count, 1);
+        assert_eq!(slate.games[0].game_id, "g1");
+
+        // A newer data version misses until the next warm
+        assert!(SLATE_PROPS.get("2026-03-05", "warm-test-2").is_none());
+    }
+
+    #[test]
+    fn version_cache_serves_only_the_latest_data_version() {
+        let cache: VersionCache<i32> = VersionCache::new();
+        assert_eq!(cache.get("v1"), None);
+
+        cache.put("v1", 1);
+        assert_eq!(cache.get("v1"), Some(1));
+        assert_eq!(cache.get("v2"), None);
+
+        cache.put("v2", 2);
+        assert_eq!(cache.get("v2"), Some(2));
+        assert_eq!(cache.get("v1"), None);
+    }
+
+    #[tokio::test]
+    async fn debug_date_requests_skip_the_date_caches() {
+        use axum::{extract::{Query, State}, Json};
+        use routes::line_shopping::get_top_picks;
+        use routes::slate::{get_props_by_game, SlateQuery};
+
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_team(&pool, 2, "AWY").await;
+        insert_game(&pool, "g2", "2026-03-07", 1, 2).await;
+        insert_prop(&pool, Prop {
+            player: "Beta Wing",
+            team: "HOM Team",
+            opponent: "AWY Team",
+            stat: "points",
+            line: 18.5,
+            choice: "over",
+            price: -115,
+            scheduled_at: "2026-03-08T00:30:00Z",
+            updated_at: "2026-03-07 12:00:00",
+        })
+        .await;
+        let data_version = db::get_props_data_version(&pool).await.unwrap();
+
+        let today = Today::debug("2026-03-07");
+        let screener = Query::try_from_uri(&"/?limit=5".parse().unwrap()).unwrap();
+        let Json(_) = get_top_picks(State(pool.clone()), today, screener).await.unwrap();
+        let Json(slate) = get_props_by_game(State(pool.clone()), today, Query(SlateQuery { date: None }))
+            .await
+            .unwrap();
+
+        assert_eq!(slate.count, 1);
+        assert!(TOP_PICKS.get("2026-03-07", &data_version).is_none());
+        assert!(SLATE_PROPS.get("2026-03-07", &data_version).is_none());
+    }
+}
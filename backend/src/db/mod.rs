@@ -1,5 +1,43 @@
 use sqlx::sqlite::SqlitePool;
 use crate::models::*;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use crate::util::rank::rank_values;
+use crate::util::today::Today;
+
+#[cfg(test)]
+pub mod test_support;
+
+/// Conference and division for each team abbreviation (the teams table doesn't store them)
+pub const TEAM_DIVISIONS: [(&str, &str, &str); 30] = [
+    ("BOS", "East", "Atlantic"), ("BKN", "East", "Atlantic"), ("NYK", "East", "Atlantic"),
+    ("PHI", "East", "Atlantic"), ("TOR", "East", "Atlantic"),
+    ("CHI", "East", "Central"), ("CLE", "East", "Central"), ("DET", "East", "Central"),
+    ("IND", "East", "Central"), ("MIL", "East", "Central"),
+    ("ATL", "East", "Southeast"), ("CHA", "East", "Southeast"), ("MIA", "East", "Southeast"),
+    ("ORL", "East", "Southeast"), ("WAS", "East", "Southeast"),
+    ("DEN", "West", "Northwest"), ("MIN", "West", "Northwest"), ("OKC", "West", "Northwest"),
+    ("POR", "West", "Northwest"), ("UTA", "West", "Northwest"),
+    ("GSW", "West", "Pacific"), ("LAC", "West", "Pacific"), ("LAL", "West", "Pacific"),
+    ("PHX", "West", "Pacific"), ("SAC", "West", "Pacific"),
+    ("DAL", "West", "Southwest"), ("HOU", "West", "Southwest"), ("MEM", "West", "Southwest"),
+    ("NOP", "West", "Southwest"), ("SAS", "West", "Southwest"),
+];
+
+fn team_alignment(abbreviation: &str) -> Option<&'static (&'static str, &'static str, &'static str)> {
+    TEAM_DIVISIONS
+        .iter()
+        .find(|(abbr, _, _)| abbr.eq_ignore_ascii_case(abbreviation))
+}
+
+/// Conference ("East" or "West") of a team abbreviation
+pub fn team_conference(abbreviation: &str) -> Option<&'static str> {
+    team_alignment(abbreviation).map(|&(_, conference, _)| conference)
+}
+
+/// Division ("Atlantic", "Pacific", ...) of a team abbreviation
+pub fn team_division(abbreviation: &str) -> Option<&'static str> {
+    team_alignment(abbreviation).map(|&(_, _, division)| division)
+}
 
 // Team queries
 pub async fn get_all_teams(pool: &SqlitePool) -> Result<Vec<Team>, sqlx::Error> {
@@ -28,42 +66,462 @@ pub async fn get_team_by_abbreviation(pool: &SqlitePool, abbreviation: &str) ->
     .await
 }
 
+/// Season whose team_pace rows are served by default (env `CURRENT_SEASON`, e.g. "2025-26")
+pub static CURRENT_SEASON: std::sync::LazyLock<String> = std::sync::LazyLock::new(|| {
+    std::env::var("CURRENT_SEASON").unwrap_or_else(|_| "2025-26".to_string())
+});
+
+/// Attempts `with_retry` makes per query (env `DB_RETRY_ATTEMPTS`, 1 disables retrying)
+pub static DB_RETRY_ATTEMPTS: std::sync::LazyLock<u32> = std::sync::LazyLock::new(|| {
+    std::env::var("DB_RETRY_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3)
+});
+
+/// Delay before the first retry, doubled for each one after (env `DB_RETRY_BACKOFF_MS`)
+static DB_RETRY_BACKOFF_MS: std::sync::LazyLock<u64> = std::sync::LazyLock::new(|| {
+    std::env::var("DB_RETRY_BACKOFF_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(50)
+});
+
+/// SQLITE_BUSY / SQLITE_LOCKED, which go away once the writer holding the lock is done
+fn is_lock_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db_err) => {
+            let message = db_err.message();
+            message.contains("database is locked")
+                || message.contains("database table is locked")
+                || matches!(db_err.code().as_deref(), Some("5" | "6"))
+        }
+        _ => false,
+    }
+}
+
+/// Run the query built by `op`, retrying lock errors with exponential backoff until
+/// `max_attempts` attempts have been made. Other errors are returned straight away.
+pub async fn with_retry<T, F, Fut>(mut op: F, max_attempts: u32) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut backoff = std::time::Duration::from_millis(*DB_RETRY_BACKOFF_MS);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < max_attempts && is_lock_error(&e) => {
+                tracing::warn!("Database locked (attempt {}/{}), retrying in {:?}", attempt, max_attempts, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Get team pace and ratings from team_pace table
-pub async fn get_team_stats(pool: &SqlitePool, team_id: i64) -> Result<Option<crate::models::TeamStats>, sqlx::Error> {
+pub async fn get_team_stats(pool: &SqlitePool, team_id: i64, season: &str) -> Result<Option<crate::models::TeamStats>, sqlx::Error> {
     sqlx::query_as::<_, crate::models::TeamStats>(
         r#"SELECT team_id, season, pace, off_rating, def_rating, net_rating, games_played, wins, losses
            FROM team_pace
-           WHERE team_id = ? AND season = '2025-26'"#
+           WHERE team_id = ? AND season = ?"#
     )
     .bind(team_id)
+    .bind(season)
     .fetch_optional(pool)
     .await
 }
 
+/// League-average pace and offensive/defensive ratings across all teams in a season
+pub async fn get_league_team_averages(pool: &SqlitePool, season: &str) -> Result<crate::models::LeagueTeamAverages, sqlx::Error> {
+    sqlx::query_as::<_, crate::models::LeagueTeamAverages>(
+        r#"SELECT CAST(AVG(pace) AS REAL) as pace,
+                  CAST(AVG(off_rating) AS REAL) as off_rating,
+                  CAST(AVG(def_rating) AS REAL) as def_rating
+           FROM team_pace
+           WHERE season = ?"#
+    )
+    .bind(season)
+    .fetch_one(pool)
+    .await
+}
+
+/// Get pace/ratings for every team in a season
+pub async fn get_all_team_stats(pool: &SqlitePool, season: &str) -> Result<Vec<crate::models::TeamStats>, sqlx::Error> {
+    sqlx::query_as::<_, crate::models::TeamStats>(
+        r#"SELECT team_id, season, pace, off_rating, def_rating, net_rating, games_played, wins, losses
+           FROM team_pace
+           WHERE season = ?"#
+    )
+    .bind(season)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the (home_team_id, away_team_id) of each scheduled game in `game_ids`, keyed by game_id
+pub async fn get_game_teams(
+    pool: &SqlitePool,
+    game_ids: &[String],
+) -> Result<std::collections::HashMap<String, (i64, i64)>, sqlx::Error> {
+    if game_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let placeholders = vec!["?"; game_ids.len()].join(", ");
+    let query = format!(
+        "SELECT game_id, home_team_id, away_team_id FROM schedule WHERE game_id IN ({})",
+        placeholders
+    );
+
+    let mut q = sqlx::query_as::<_, (String, i64, i64)>(&query);
+    for game_id in game_ids {
+        q = q.bind(game_id);
+    }
+
+    Ok(q.fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(game_id, home, away)| (game_id, (home, away)))
+        .collect())
+}
+
 // Player queries
-pub async fn get_all_players(pool: &SqlitePool) -> Result<Vec<PlayerStats>, sqlx::Error> {
+/// Stat columns the player list can be sorted by (also guards the dynamic ORDER BY)
+pub const PLAYER_SORT_COLUMNS: [&str; 20] = [
+    "player_name", "points", "assists", "rebounds", "threes_made", "threes_attempted",
+    "fg_attempted", "steals", "blocks", "turnovers", "fouls", "ft_attempted",
+    "pts_plus_ast", "pts_plus_reb", "ast_plus_reb", "pts_plus_ast_plus_reb",
+    "steals_plus_blocks", "double_doubles", "triple_doubles", "games_played",
+];
+
+/// Sorting and filtering for the player list
+#[derive(Debug, Default, Clone)]
+pub struct PlayerListOptions {
+    /// Column from `PLAYER_SORT_COLUMNS` (default: player_name)
+    pub sort_by: Option<String>,
+    pub descending: bool,
+    pub team_id: Option<i64>,
+    pub min_games: Option<i64>,
+}
+
+/// Get one page of players after applying `opts`. A negative `limit` means no limit
+/// (SQLite semantics). Unknown sort columns fall back to player_name.
+pub async fn get_players_paginated(
+    pool: &SqlitePool,
+    limit: i64,
+    offset: i64,
+    opts: &PlayerListOptions,
+) -> Result<Vec<PlayerStats>, sqlx::Error> {
+    // Validate sort column to prevent SQL injection
+    let sort_column = opts
+        .sort_by
+        .as_deref()
+        .filter(|col| PLAYER_SORT_COLUMNS.contains(col))
+        .unwrap_or("player_name");
+    let direction = if opts.descending { "DESC" } else { "ASC" };
+
+    // Nulls always sort last; player_name breaks ties so pages are stable
+    let query = format!(
+        r#"SELECT * FROM player_stats
+           WHERE (? IS NULL OR team_id = ?)
+             AND (? IS NULL OR games_played >= ?)
+           ORDER BY {col} IS NULL, {col} {dir}, player_name
+           LIMIT ? OFFSET ?"#,
+        col = sort_column,
+        dir = direction
+    );
+
+    with_retry(|| {
+        sqlx::query_as::<_, PlayerStats>(&query)
+            .bind(opts.team_id)
+            .bind(opts.team_id)
+            .bind(opts.min_games)
+            .bind(opts.min_games)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
+
+/// Count players matching the filters in `opts` (sorting is ignored)
+pub async fn count_players(pool: &SqlitePool, opts: &PlayerListOptions) -> Result<i64, sqlx::Error> {
+    with_retry(|| {
+        sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM player_stats
+               WHERE (? IS NULL OR team_id = ?)
+                 AND (? IS NULL OR games_played >= ?)"#
+        )
+        .bind(opts.team_id)
+        .bind(opts.team_id)
+        .bind(opts.min_games)
+        .bind(opts.min_games)
+        .fetch_one(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
+
+pub async fn get_player_by_id(pool: &SqlitePool, player_id: i64) -> Result<Option<PlayerStats>, sqlx::Error> {
+    with_retry(|| {
+        sqlx::query_as::<_, PlayerStats>(
+            r#"SELECT * FROM player_stats WHERE player_id = ?"#
+        )
+        .bind(player_id)
+        .fetch_optional(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
+
+/// Season averages for every player with at least `min_games` games played
+pub async fn get_qualified_player_stats(pool: &SqlitePool, min_games: i64) -> Result<Vec<PlayerStats>, sqlx::Error> {
     sqlx::query_as::<_, PlayerStats>(
-        r#"SELECT * FROM player_stats ORDER BY player_name"#
+        r#"SELECT * FROM player_stats WHERE games_played >= ?"#
     )
+    .bind(min_games)
     .fetch_all(pool)
     .await
 }
 
-pub async fn get_player_by_id(pool: &SqlitePool, player_id: i64) -> Result<Option<PlayerStats>, sqlx::Error> {
+/// Changes whenever the nightly player_stats load lands, to key caches built from it
+pub async fn get_player_stats_data_version(pool: &SqlitePool) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"SELECT COALESCE(MAX(last_updated), '') || '|' || COUNT(*) FROM player_stats"#
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Get the player_stats rows for `player_ids` in one query, in no particular order
+pub async fn get_players_by_ids(pool: &SqlitePool, player_ids: &[i64]) -> Result<Vec<PlayerStats>, sqlx::Error> {
+    if player_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "SELECT * FROM player_stats WHERE player_id IN ({})",
+        vec!["?"; player_ids.len()].join(", ")
+    );
+    with_retry(|| {
+        let mut query = sqlx::query_as::<_, PlayerStats>(&sql);
+        for id in player_ids {
+            query = query.bind(id);
+        }
+        query.fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
+
+/// Average minutes over a player's played games in `season` (`player_stats` has no
+/// minutes column). None if they have no played games.
+pub async fn get_player_minutes_per_game(
+    pool: &SqlitePool,
+    player_id: i64,
+    season: &str,
+) -> Result<Option<f64>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"SELECT CAST(AVG(min) AS REAL)
+           FROM player_game_logs
+           WHERE player_id = ? AND season = ? AND min IS NOT NULL AND min > 0"#
+    )
+    .bind(player_id)
+    .bind(season)
+    .fetch_one(pool)
+    .await
+}
+
+/// Field goal totals over each player's `player_stats` season, keyed by player_id.
+/// Players without game logs for that season are left out.
+pub async fn get_season_shooting_totals(
+    pool: &SqlitePool,
+    player_ids: &[i64],
+) -> Result<std::collections::HashMap<i64, ShootingTotals>, sqlx::Error> {
+    if player_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let sql = format!(
+        r#"SELECT ps.player_id,
+                  CAST(COALESCE(SUM(pgl.fgm), 0) AS INTEGER) as fgm,
+                  CAST(COALESCE(SUM(pgl.fga), 0) AS INTEGER) as fga,
+                  CAST(COALESCE(SUM(pgl.fg3m), 0) AS INTEGER) as fg3m
+           FROM player_stats ps
+           JOIN player_game_logs pgl ON pgl.player_id = ps.player_id AND pgl.season = ps.season
+           WHERE ps.player_id IN ({})
+           GROUP BY ps.player_id"#,
+        vec!["?"; player_ids.len()].join(", ")
+    );
+    let mut query = sqlx::query_as::<_, ShootingTotals>(&sql);
+    for id in player_ids {
+        query = query.bind(id);
+    }
+    Ok(query
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|totals| (totals.player_id, totals))
+        .collect())
+}
+
+/// Per-game averages over a player's last `last_n` played games (DNPs skipped), computed
+/// from `player_game_logs` in the same shape as the season-long `player_stats` row.
+/// Quarter/half columns aren't tracked per game and come back NULL.
+/// Returns None if the player has no played games.
+pub async fn get_player_recent_averages(
+    pool: &SqlitePool,
+    player_id: i64,
+    last_n: i64,
+) -> Result<Option<PlayerStats>, sqlx::Error> {
     sqlx::query_as::<_, PlayerStats>(
-        r#"SELECT * FROM player_stats WHERE player_id = ?"#
+        r#"WITH recent AS (
+               SELECT *,
+                      (CASE WHEN pts >= 10 THEN 1 ELSE 0 END
+                       + CASE WHEN reb >= 10 THEN 1 ELSE 0 END
+                       + CASE WHEN ast >= 10 THEN 1 ELSE 0 END
+                       + CASE WHEN stl >= 10 THEN 1 ELSE 0 END
+                       + CASE WHEN blk >= 10 THEN 1 ELSE 0 END) as tens
+               FROM player_game_logs
+               WHERE player_id = ?
+                 AND min IS NOT NULL AND min > 0
+               ORDER BY game_date DESC
+               LIMIT ?
+           )
+           SELECT
+               CAST(? AS INTEGER) as player_id,
+               COALESCE(ps.player_name, MAX(r.player_name)) as player_name,
+               COALESCE(ps.season, MAX(r.season)) as season,
+               COALESCE(ps.team_id, MAX(r.team_id)) as team_id,
+               CAST(AVG(r.pts) AS REAL) as points,
+               CAST(AVG(r.ast) AS REAL) as assists,
+               CAST(AVG(r.reb) AS REAL) as rebounds,
+               CAST(AVG(r.fg3m) AS REAL) as threes_made,
+               CAST(AVG(r.fg3a) AS REAL) as threes_attempted,
+               CAST(AVG(r.fga) AS REAL) as fg_attempted,
+               CAST(AVG(r.stl) AS REAL) as steals,
+               CAST(AVG(r.blk) AS REAL) as blocks,
+               CAST(AVG(r.tov) AS REAL) as turnovers,
+               CAST(COALESCE(AVG(r.pf), 0) AS REAL) as fouls,
+               CAST(AVG(r.fta) AS REAL) as ft_attempted,
+               CAST(AVG(r.pts + r.ast) AS REAL) as pts_plus_ast,
+               CAST(AVG(r.pts + r.reb) AS REAL) as pts_plus_reb,
+               CAST(AVG(r.ast + r.reb) AS REAL) as ast_plus_reb,
+               CAST(AVG(r.pts + r.ast + r.reb) AS REAL) as pts_plus_ast_plus_reb,
+               CAST(AVG(r.stl + r.blk) AS REAL) as steals_plus_blocks,
+               SUM(CASE WHEN r.tens >= 2 THEN 1 ELSE 0 END) as double_doubles,
+               SUM(CASE WHEN r.tens >= 3 THEN 1 ELSE 0 END) as triple_doubles,
+               NULL as q1_points,
+               NULL as q1_assists,
+               NULL as q1_rebounds,
+               NULL as first_half_points,
+               COUNT(*) as games_played,
+               MAX(r.game_date) as last_updated
+           FROM recent r
+           LEFT JOIN player_stats ps ON ps.player_id = ?
+           HAVING COUNT(*) > 0"#
     )
     .bind(player_id)
+    .bind(last_n)
+    .bind(player_id)
+    .bind(player_id)
     .fetch_optional(pool)
     .await
 }
 
-pub async fn search_players(pool: &SqlitePool, player_name: &str) -> Result<Option<PlayerStats>, sqlx::Error> {
+/// Get players whose name exactly matches `player_name`, up to `limit`
+pub async fn search_players(pool: &SqlitePool, player_name: &str, limit: i64) -> Result<Vec<PlayerStats>, sqlx::Error> {
     sqlx::query_as::<_, PlayerStats>(
-        r#"SELECT * FROM player_stats WHERE player_name = ?"#
+        r#"SELECT * FROM player_stats WHERE player_name = ? ORDER BY player_id LIMIT ?"#
     )
     .bind(player_name)
-    .fetch_optional(pool)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Keep the `items` whose name partially matches `query`, ignoring case and accents
+/// ("doncic" finds "Dončić"), best matches first: exact name, then name prefix, then
+/// word prefix ("james" finds "LeBron James"), then any substring. The sort is stable,
+/// so ties keep their input order. Matching is done in Rust because SQLite's LIKE
+/// can't fold accents.
+fn rank_name_matches<T>(items: Vec<T>, name: impl Fn(&T) -> &str, query: &str, limit: usize) -> Vec<T> {
+    let needle = normalize_name(query.trim()).to_lowercase();
+    if needle.is_empty() {
+        return vec![];
+    }
+
+    let mut matches: Vec<(u8, T)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let name = normalize_name(name(&item)).to_lowercase();
+            let quality = if name == needle {
+                0
+            } else if name.starts_with(&needle) {
+                1
+            } else if name.split_whitespace().any(|word| word.starts_with(&needle)) {
+                2
+            } else if name.contains(&needle) {
+                3
+            } else {
+                return None;
+            };
+            Some((quality, item))
+        })
+        .collect();
+
+    matches.sort_by_key(|(quality, _)| *quality);
+    matches.into_iter().take(limit).map(|(_, item)| item).collect()
+}
+
+/// Search players by partial name (see `rank_name_matches`), up to `limit`
+pub async fn fuzzy_search_players(pool: &SqlitePool, query: &str, limit: usize) -> Result<Vec<PlayerStats>, sqlx::Error> {
+    let players = sqlx::query_as::<_, PlayerStats>(
+        r#"SELECT * FROM player_stats ORDER BY player_name"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rank_name_matches(players, |p| &p.player_name, query, limit))
+}
+
+/// Lightweight partial-name search for a search box (see `rank_name_matches`), up to `limit`
+pub async fn autocomplete_players(pool: &SqlitePool, query: &str, limit: usize) -> Result<Vec<PlayerSuggestion>, sqlx::Error> {
+    let players = sqlx::query_as::<_, PlayerSuggestion>(
+        r#"SELECT ps.player_id, ps.player_name, t.abbreviation as team_abbreviation
+           FROM player_stats ps
+           LEFT JOIN teams t ON ps.team_id = t.team_id
+           ORDER BY ps.player_name"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rank_name_matches(players, |p| &p.player_name, query, limit))
+}
+
+/// Count the rows each player dataset holds for `player_id` (props are filled in by the caller)
+pub async fn get_player_data_coverage(pool: &SqlitePool, player_id: i64) -> Result<DataCoverage, sqlx::Error> {
+    sqlx::query_as::<_, DataCoverage>(
+        r#"SELECT
+               ? as player_id,
+               (SELECT player_name FROM player_stats WHERE player_id = ?) as player_name,
+               EXISTS (SELECT 1 FROM player_stats WHERE player_id = ?) as season_stats,
+               (SELECT COUNT(*) FROM player_shooting_zones WHERE player_id = ?) as shooting_zones,
+               (SELECT COUNT(*) FROM player_assist_zones WHERE player_id = ?) as assist_zones,
+               (SELECT COUNT(*) FROM player_play_types WHERE player_id = ?) as play_types,
+               (SELECT COUNT(*) FROM player_game_logs WHERE player_id = ?) as game_logs,
+               (SELECT COUNT(*) FROM player_game_logs
+                WHERE player_id = ? AND min IS NOT NULL AND min > 0) as played_games"#
+    )
+    .bind(player_id)
+    .bind(player_id)
+    .bind(player_id)
+    .bind(player_id)
+    .bind(player_id)
+    .bind(player_id)
+    .bind(player_id)
+    .bind(player_id)
+    .fetch_one(pool)
     .await
 }
 
@@ -119,15 +577,7 @@ pub async fn get_assist_zones_with_team_defense(
     // Get opponent's defensive zones
     let opponent_def_zones = get_defensive_zones(pool, opponent_team_id).await?;
 
-    // Get all team defensive zones to calculate rankings
-    let all_team_zones: Vec<(i64, String, f32)> = sqlx::query_as(
-        r#"SELECT team_id, zone_name,
-                  CASE WHEN opp_fga > 0 THEN (opp_fgm / opp_fga) * 100.0 ELSE 0.0 END AS opp_fg_pct
-           FROM team_defensive_zones
-           ORDER BY zone_name, opp_fg_pct"#
-    )
-    .fetch_all(pool)
-    .await?;
+    let zone_ranks = compute_zone_ranks(pool).await?;
 
     // Build zone matchups
     let mut zones: Vec<AssistZoneMatchup> = Vec::new();
@@ -138,14 +588,14 @@ pub async fn get_assist_zones_with_team_defense(
             .find(|z| z.zone_name == player_zone.zone_name);
 
         let (opp_def_fg_pct, opp_def_rank, has_data) = if let Some(def_zone) = opp_def {
-            // Calculate ranking: count how many teams have lower FG% (better defense)
-            let rank = all_team_zones.iter()
-                .filter(|(_, zone, fg_pct)| zone == &player_zone.zone_name && fg_pct < &def_zone.opp_fg_pct)
-                .count() as i32 + 1;
+            // Rank among teams' FG% allowed in this zone (lower = better defense)
+            let rank = zone_ranks
+                .get(&(opponent_team_id, player_zone.zone_name.clone()))
+                .copied();
 
             (def_zone.opp_fg_pct, rank, true)
         } else {
-            (0.0, 0, false)
+            (0.0, None, false)
         };
 
         let player_ast_pct = if total_assists > 0 {
@@ -205,6 +655,353 @@ pub async fn get_defensive_play_types(pool: &SqlitePool, team_id: i64) -> Result
     .await
 }
 
+/// League-average PPP allowed on a play type (simple mean across teams)
+pub async fn get_league_avg_play_type_ppp(pool: &SqlitePool, play_type: &str) -> Result<Option<f32>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"SELECT CAST(AVG(ppp) AS REAL) FROM team_defensive_play_types WHERE play_type = ?"#
+    )
+    .bind(play_type)
+    .fetch_one(pool)
+    .await
+}
+
+/// Average rebounds (total/offensive/defensive) each team allows per game in `season`,
+/// summed from opposing players' game logs. Ordered by total rebounds allowed, fewest first.
+pub async fn get_team_rebounds_allowed(pool: &SqlitePool, season: &str) -> Result<Vec<TeamReboundsAllowed>, sqlx::Error> {
+    sqlx::query_as::<_, TeamReboundsAllowed>(
+        r#"WITH game_rebounds AS (
+            SELECT
+                s.game_id,
+                CASE WHEN pgl.team_id = s.home_team_id THEN s.away_team_id ELSE s.home_team_id END as defending_team_id,
+                SUM(pgl.reb) as total_reb,
+                SUM(pgl.oreb) as total_oreb,
+                SUM(pgl.dreb) as total_dreb
+            FROM player_game_logs pgl
+            JOIN schedule s ON pgl.game_id = s.game_id
+            WHERE pgl.reb IS NOT NULL
+              AND pgl.season = ?
+            GROUP BY s.game_id, defending_team_id
+        )
+        SELECT
+            defending_team_id as team_id,
+            CAST(AVG(total_reb) AS REAL) as reb_allowed,
+            CAST(AVG(total_oreb) AS REAL) as oreb_allowed,
+            CAST(AVG(total_dreb) AS REAL) as dreb_allowed
+        FROM game_rebounds
+        GROUP BY defending_team_id
+        ORDER BY reb_allowed ASC"#
+    )
+    .bind(season)
+    .fetch_all(pool)
+    .await
+}
+
+/// Average points each team allows per game in `season`, summed from opposing players'
+/// game logs like `get_team_rebounds_allowed`, with the team's `season` pace and defensive
+/// rating from team_pace (None without a row). Ordered by points allowed, fewest first.
+pub async fn get_team_points_allowed(pool: &SqlitePool, season: &str) -> Result<Vec<TeamPointsAllowed>, sqlx::Error> {
+    sqlx::query_as::<_, TeamPointsAllowed>(
+        r#"WITH game_points AS (
+            SELECT
+                s.game_id,
+                CASE WHEN pgl.team_id = s.home_team_id THEN s.away_team_id ELSE s.home_team_id END as defending_team_id,
+                SUM(pgl.pts) as total_pts
+            FROM player_game_logs pgl
+            JOIN schedule s ON pgl.game_id = s.game_id
+            WHERE pgl.pts IS NOT NULL
+              AND pgl.season = ?
+            GROUP BY s.game_id, defending_team_id
+        )
+        SELECT
+            gp.defending_team_id as team_id,
+            COUNT(*) as games,
+            CAST(AVG(gp.total_pts) AS REAL) as pts_allowed,
+            tp.pace,
+            tp.def_rating
+        FROM game_points gp
+        LEFT JOIN team_pace tp ON tp.team_id = gp.defending_team_id AND tp.season = ?
+        GROUP BY gp.defending_team_id
+        ORDER BY pts_allowed ASC"#
+    )
+    .bind(season)
+    .bind(season)
+    .fetch_all(pool)
+    .await
+}
+
+/// Points in each of a player's played games in `season` with the opponent's (found via
+/// the schedule) `season` defensive rating from team_pace, newest first. The rating is None
+/// when the opponent has no team_pace row.
+pub async fn get_player_games_vs_defense(
+    pool: &SqlitePool,
+    player_id: i64,
+    season: &str,
+) -> Result<Vec<GameVsDefense>, sqlx::Error> {
+    sqlx::query_as::<_, GameVsDefense>(
+        r#"WITH games AS (
+            SELECT
+                pgl.game_date,
+                pgl.pts,
+                CASE WHEN pgl.team_id = s.home_team_id THEN s.away_team_id ELSE s.home_team_id END as opponent_id
+            FROM player_game_logs pgl
+            JOIN schedule s ON pgl.game_id = s.game_id
+            WHERE pgl.player_id = ?
+              AND pgl.season = ?
+              AND pgl.pts IS NOT NULL
+              AND pgl.min IS NOT NULL AND pgl.min > 0
+        )
+        SELECT g.pts, tp.def_rating as opp_def_rating
+        FROM games g
+        LEFT JOIN team_pace tp ON tp.team_id = g.opponent_id AND tp.season = ?
+        ORDER BY g.game_date DESC"#
+    )
+    .bind(player_id)
+    .bind(season)
+    .bind(season)
+    .fetch_all(pool)
+    .await
+}
+
+/// Stats that can be measured as allowed by a defense, mapped to their game-log columns
+pub const ALLOWED_STAT_COLUMNS: [(&str, &str); 7] = [
+    ("points", "pts"),
+    ("rebounds", "reb"),
+    ("assists", "ast"),
+    ("steals", "stl"),
+    ("blocks", "blk"),
+    ("threes_made", "fg3m"),
+    ("turnovers", "tov"),
+];
+
+/// Average of a stat each team allows per game in `season` to opposing players at
+/// `position` ("G", "F" or "C"; hybrids like "G-F" count toward both), summed per game
+/// from the opponents' game logs. Only games where the position logged minutes are averaged.
+/// `stat` must be a name from `ALLOWED_STAT_COLUMNS`, otherwise nothing is returned.
+pub async fn get_stat_allowed_by_position(
+    pool: &SqlitePool,
+    stat: &str,
+    position: &str,
+    season: &str,
+) -> Result<Vec<TeamStatAllowed>, sqlx::Error> {
+    let Some(&(_, column)) = ALLOWED_STAT_COLUMNS.iter().find(|(name, _)| *name == stat) else {
+        return Ok(vec![]);
+    };
+
+    let query = format!(
+        r#"WITH game_totals AS (
+            SELECT
+                s.game_id,
+                CASE WHEN pgl.team_id = s.home_team_id THEN s.away_team_id ELSE s.home_team_id END as defending_team_id,
+                SUM(pgl.{column}) as total
+            FROM player_game_logs pgl
+            JOIN schedule s ON pgl.game_id = s.game_id
+            JOIN player_stats ps ON CAST(ps.player_id AS TEXT) = pgl.player_id
+            WHERE pgl.{column} IS NOT NULL
+              AND pgl.min IS NOT NULL AND pgl.min > 0
+              AND ('-' || ps.position || '-') LIKE '%-' || ? || '-%'
+              AND pgl.season = ?
+            GROUP BY s.game_id, defending_team_id
+        )
+        SELECT
+            defending_team_id as team_id,
+            CAST(AVG(total) AS REAL) as allowed,
+            COUNT(*) as games
+        FROM game_totals
+        GROUP BY defending_team_id
+        ORDER BY allowed ASC"#
+    );
+
+    sqlx::query_as::<_, TeamStatAllowed>(&query)
+        .bind(position)
+        .bind(season)
+        .fetch_all(pool)
+        .await
+}
+
+/// A player's per-game and per-36 rebounding over their played games in `season`
+pub async fn get_player_rebounding(pool: &SqlitePool, player_id: i64, season: &str) -> Result<Option<PlayerRebounding>, sqlx::Error> {
+    sqlx::query_as::<_, PlayerRebounding>(
+        r#"SELECT
+               COUNT(*) as games,
+               CAST(AVG(reb) AS REAL) as reb,
+               CAST(AVG(oreb) AS REAL) as oreb,
+               CAST(AVG(dreb) AS REAL) as dreb,
+               CAST(SUM(reb) * 36.0 / SUM(min) AS REAL) as reb_per_36
+           FROM player_game_logs
+           WHERE player_id = ? AND season = ? AND min IS NOT NULL AND min > 0
+           HAVING COUNT(*) > 0"#
+    )
+    .bind(player_id)
+    .bind(season)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Shooting zone names and whether they're 3-point zones
+pub const SHOOTING_ZONES: [(&str, bool); 6] = [
+    ("Above the Break 3", true),
+    ("In The Paint (Non-RA)", false),
+    ("Left Corner 3", true),
+    ("Mid-Range", false),
+    ("Restricted Area", false),
+    ("Right Corner 3", true),
+];
+
+/// Teams allowing fewer opponent FGA per game than this in a zone are left out of that
+/// zone's league average (env `ZONE_AVG_MIN_FGA`, default 1.0)
+static ZONE_AVG_MIN_FGA: std::sync::LazyLock<f32> = std::sync::LazyLock::new(|| {
+    std::env::var("ZONE_AVG_MIN_FGA")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1.0)
+});
+
+/// League-average opponent FG% for one zone's rows, weighted by opponent FGA so small
+/// samples carry little weight. Teams below `ZONE_AVG_MIN_FGA` are excluded unless no
+/// team meets it. 0.0 when there are no rows.
+pub fn zone_league_avg(zone_defenses: &[&ZoneDefenseRow]) -> f32 {
+    let qualified: Vec<&ZoneDefenseRow> = zone_defenses
+        .iter()
+        .copied()
+        .filter(|z| z.opp_fga >= *ZONE_AVG_MIN_FGA)
+        .collect();
+    let rows = if qualified.is_empty() { zone_defenses } else { &qualified[..] };
+
+    let fga: f32 = rows.iter().map(|z| z.opp_fga).sum();
+    if fga > 0.0 {
+        rows.iter().map(|z| z.opp_fgm).sum::<f32>() / fga * 100.0
+    } else if !rows.is_empty() {
+        rows.iter().map(|z| z.opp_fg_pct).sum::<f32>() / rows.len() as f32
+    } else {
+        0.0
+    }
+}
+
+/// Get every team's opponent FG% per zone, ordered by zone then best defense first
+pub async fn get_league_zone_defense(pool: &SqlitePool) -> Result<Vec<ZoneDefenseRow>, sqlx::Error> {
+    sqlx::query_as::<_, ZoneDefenseRow>(
+        r#"SELECT team_id, zone_name,
+                  CAST(COALESCE(opp_fgm, 0) AS REAL) AS opp_fgm,
+                  CAST(COALESCE(opp_fga, 0) AS REAL) AS opp_fga,
+                  CASE WHEN opp_fga > 0 THEN (opp_fgm / opp_fga) * 100.0 ELSE 0.0 END AS opp_fg_pct
+           FROM team_defensive_zones
+           ORDER BY zone_name, opp_fg_pct"#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Every team's rank defending each shooting zone (1 = lowest opp FG%), keyed by
+/// (team_id, zone_name). Ties use competition ranking: two teams tied for 3rd both rank 3
+/// and the next is 5th. Teams with no row for a zone have no entry for it.
+pub async fn compute_zone_ranks(pool: &SqlitePool) -> Result<std::collections::HashMap<(i64, String), i32>, sqlx::Error> {
+    let all_def_zones = get_league_zone_defense(pool).await?;
+
+    let mut by_zone: std::collections::HashMap<&str, Vec<&ZoneDefenseRow>> = std::collections::HashMap::new();
+    for row in &all_def_zones {
+        by_zone.entry(row.zone_name.as_str()).or_default().push(row);
+    }
+
+    let mut ranks: std::collections::HashMap<(i64, String), i32> = std::collections::HashMap::new();
+    for group in by_zone.values() {
+        let group_ranks = rank_values(group, |row| row.opp_fg_pct, true);
+        for (row, rank) in group.iter().zip(group_ranks) {
+            ranks.insert((row.team_id, row.zone_name.clone()), rank);
+        }
+    }
+
+    Ok(ranks)
+}
+
+/// Teams ranked on `name` in a ranks map from `compute_zone_ranks` or
+/// `get_team_defensive_play_type_ranks`; teams without data for that zone or play type
+/// aren't in the ranking
+pub fn teams_ranked(ranks: &std::collections::HashMap<(i64, String), i32>, name: &str) -> i32 {
+    ranks.keys().filter(|(_, ranked)| ranked == name).count() as i32
+}
+
+/// League average plus best and worst defending team for each shooting zone
+pub async fn get_zone_league_averages(pool: &SqlitePool) -> Result<Vec<ZoneLeagueAverage>, sqlx::Error> {
+    let all_def_zones = get_league_zone_defense(pool).await?;
+    let teams = get_all_teams(pool).await?;
+
+    let team_defense = |row: &ZoneDefenseRow| ZoneTeamDefense {
+        team_id: row.team_id,
+        abbreviation: teams
+            .iter()
+            .find(|t| t.team_id == row.team_id)
+            .map(|t| t.abbreviation.clone()),
+        opp_fg_pct: row.opp_fg_pct,
+    };
+
+    Ok(SHOOTING_ZONES
+        .iter()
+        .map(|(zone_name, is_three)| {
+            // Rows are ordered best defense (lowest opp FG%) first
+            let zone_defenses: Vec<&ZoneDefenseRow> = all_def_zones
+                .iter()
+                .filter(|z| z.zone_name == *zone_name)
+                .collect();
+
+            ZoneLeagueAverage {
+                zone_name: zone_name.to_string(),
+                is_three: *is_three,
+                league_avg_pct: zone_league_avg(&zone_defenses),
+                teams: zone_defenses.len() as i64,
+                best_defense: zone_defenses.first().map(|row| team_defense(row)),
+                worst_defense: zone_defenses.last().map(|row| team_defense(row)),
+            }
+        })
+        .collect())
+}
+
+/// Fingerprint of the team zone defense data, changing whenever the nightly load writes
+pub async fn get_zone_data_version(pool: &SqlitePool) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"SELECT COALESCE(MAX(last_updated), '') || '|' || COUNT(*) FROM team_defensive_zones"#
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Get a team's defended zones with league average and rank, best-defended zone first.
+/// Rank 1 = lowest opponent FG% in the league for that zone.
+pub async fn get_team_zone_strengths(pool: &SqlitePool, team_id: i64) -> Result<Vec<ZoneStrength>, sqlx::Error> {
+    let all_def_zones = get_league_zone_defense(pool).await?;
+
+    let mut strengths: Vec<ZoneStrength> = SHOOTING_ZONES
+        .iter()
+        .filter_map(|(zone_name, is_three)| {
+            let zone_defenses: Vec<&ZoneDefenseRow> = all_def_zones
+                .iter()
+                .filter(|z| z.zone_name == *zone_name)
+                .collect();
+
+            let pos = zone_defenses.iter().position(|z| z.team_id == team_id)?;
+            let league_avg_pct = zone_league_avg(&zone_defenses);
+            let rank = rank_values(&zone_defenses, |z| z.opp_fg_pct, true)[pos];
+
+            Some(ZoneStrength {
+                zone_name: zone_name.to_string(),
+                opp_fg_pct: zone_defenses[pos].opp_fg_pct,
+                league_avg_pct,
+                rank,
+                is_three: *is_three,
+            })
+        })
+        .collect();
+
+    // Equal ranks fall back to how far below the league average the team holds opponents
+    strengths.sort_by(|a, b| {
+        a.rank.cmp(&b.rank).then_with(|| {
+            (a.opp_fg_pct - a.league_avg_pct)
+                .partial_cmp(&(b.opp_fg_pct - b.league_avg_pct))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    });
+    Ok(strengths)
+}
+
 /// Get shooting zone matchup with league context (league averages, opponent ranks, volume)
 pub async fn get_shooting_zone_matchup(
     pool: &SqlitePool,
@@ -240,60 +1037,27 @@ pub async fn get_shooting_zone_matchup(
     // Get opponent's defensive zones
     let opponent_def_zones = get_defensive_zones(pool, opponent_team_id).await?;
 
-    // Get all team defensive zones to calculate league averages and rankings
-    #[derive(sqlx::FromRow)]
-    struct ZoneDefense {
-        team_id: i64,
-        zone_name: String,
-        opp_fg_pct: f32,
-    }
-    let all_def_zones: Vec<ZoneDefense> = sqlx::query_as(
-        r#"SELECT team_id, zone_name,
-                  CASE WHEN opp_fga > 0 THEN (opp_fgm / opp_fga) * 100.0 ELSE 0.0 END AS opp_fg_pct
-           FROM team_defensive_zones
-           ORDER BY zone_name, opp_fg_pct"#
-    )
-    .fetch_all(pool)
-    .await?;
-
-    // Zone names and whether they're 3-point zones
-    let zone_names = [
-        ("Above the Break 3", true),
-        ("In The Paint (Non-RA)", false),
-        ("Left Corner 3", true),
-        ("Mid-Range", false),
-        ("Restricted Area", false),
-        ("Right Corner 3", true),
-    ];
+    // Get all team defensive zones to calculate league averages
+    let all_def_zones = get_league_zone_defense(pool).await?;
+    let zone_ranks = compute_zone_ranks(pool).await?;
 
     let mut zones = Vec::new();
 
-    for (zone_name, is_three) in zone_names.iter() {
+    for (zone_name, is_three) in SHOOTING_ZONES.iter() {
         let player_zone = player_zones.iter().find(|z| z.zone_name == *zone_name);
         let opp_zone = opponent_def_zones.iter().find(|z| z.zone_name == *zone_name);
 
         // Calculate league average for this zone
-        let zone_defenses: Vec<&ZoneDefense> = all_def_zones
+        let zone_defenses: Vec<&ZoneDefenseRow> = all_def_zones
             .iter()
             .filter(|z| z.zone_name == *zone_name)
             .collect();
 
-        let league_avg: f32 = if !zone_defenses.is_empty() {
-            zone_defenses.iter().map(|z| z.opp_fg_pct).sum::<f32>() / zone_defenses.len() as f32
-        } else {
-            0.0
-        };
+        let league_avg = zone_league_avg(&zone_defenses);
 
-        // Calculate opponent rank (1 = best defense = lowest opp_fg_pct)
-        let opp_rank = if let Some(opp) = opp_zone {
-            zone_defenses
-                .iter()
-                .position(|z| z.team_id == opponent_team_id)
-                .map(|pos| (pos + 1) as i32)
-                .unwrap_or(15)
-        } else {
-            15 // Default to middle if no data
-        };
+        // Calculate opponent rank (1 = best defense = lowest opp_fg_pct); None if the
+        // opponent has no data in this zone rather than a made-up middle rank
+        let opp_rank = opp_zone.and_then(|_| zone_ranks.get(&(opponent_team_id, zone_name.to_string())).copied());
 
         let has_data = player_zone.is_some() && opp_zone.is_some();
 
@@ -346,82 +1110,140 @@ pub async fn get_shooting_zone_matchup(
     })
 }
 
-// Schedule queries - read from cached SQLite data
-pub async fn get_schedule_by_date(pool: &SqlitePool, date: &str) -> Result<Vec<ScheduleRow>, sqlx::Error> {
-    sqlx::query_as::<_, ScheduleRow>(
-        r#"SELECT * FROM schedule WHERE game_date = ? ORDER BY game_time"#
+/// Players on the most recent injury report who aren't "Available", optionally limited
+/// to one team and/or one status (case-insensitive). Ordered by team, then player.
+pub async fn get_injured_players(
+    pool: &SqlitePool,
+    team_id: Option<i64>,
+    status: Option<&str>,
+) -> Result<Vec<InjuredPlayer>, sqlx::Error> {
+    sqlx::query_as::<_, InjuredPlayer>(
+        r#"SELECT pi.player_id, pi.player_name, pi.team_id,
+                  t.abbreviation AS team_abbreviation, t.full_name AS team_name,
+                  pi.injury_status, pi.injury_description, pi.collection_date
+           FROM player_injuries pi
+           LEFT JOIN teams t ON t.team_id = pi.team_id
+           WHERE pi.collection_date = (SELECT MAX(collection_date) FROM player_injuries)
+             AND pi.injury_status != 'Available'
+             AND (? IS NULL OR pi.team_id = ?)
+             AND (? IS NULL OR LOWER(pi.injury_status) = LOWER(?))
+           ORDER BY t.abbreviation, pi.player_name"#
     )
-    .bind(date)
+    .bind(team_id)
+    .bind(team_id)
+    .bind(status)
+    .bind(status)
     .fetch_all(pool)
     .await
 }
 
-pub async fn get_todays_schedule(pool: &SqlitePool) -> Result<Vec<ScheduleRow>, sqlx::Error> {
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    get_schedule_by_date(pool, &today).await
-}
-
+// Schedule queries - read from cached SQLite data
+pub async fn get_schedule_by_date(pool: &SqlitePool, date: &str) -> Result<Vec<ScheduleRow>, sqlx::Error> {
+    with_retry(|| {
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"SELECT * FROM schedule WHERE game_date = ? ORDER BY game_time"#
+        )
+        .bind(date)
+        .fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
+
+pub async fn get_todays_schedule(pool: &SqlitePool, today: &Today) -> Result<Vec<ScheduleRow>, sqlx::Error> {
+    get_schedule_by_date(pool, &today.ymd()).await
+}
+
 pub async fn get_schedule_by_team(pool: &SqlitePool, team_abbreviation: &str) -> Result<Vec<ScheduleRow>, sqlx::Error> {
-    sqlx::query_as::<_, ScheduleRow>(
-        r#"SELECT * FROM schedule
-           WHERE home_team_abbreviation = ? OR away_team_abbreviation = ?
-           ORDER BY game_date, game_time"#
-    )
-    .bind(team_abbreviation)
-    .bind(team_abbreviation)
-    .fetch_all(pool)
+    with_retry(|| {
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"SELECT * FROM schedule
+               WHERE home_team_abbreviation = ? OR away_team_abbreviation = ?
+               ORDER BY game_date, game_time"#
+        )
+        .bind(team_abbreviation)
+        .bind(team_abbreviation)
+        .fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
     .await
 }
 
-pub async fn get_upcoming_schedule(pool: &SqlitePool, days: i32) -> Result<Vec<ScheduleRow>, sqlx::Error> {
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let end_date = (chrono::Local::now() + chrono::Duration::days(days as i64))
-        .format("%Y-%m-%d")
-        .to_string();
+/// One game from the schedule table by id
+pub async fn get_schedule_game(pool: &SqlitePool, game_id: &str) -> Result<Option<ScheduleRow>, sqlx::Error> {
+    with_retry(|| {
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"SELECT * FROM schedule WHERE game_id = ?"#
+        )
+        .bind(game_id)
+        .fetch_optional(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
 
+/// Every game in the schedule table, oldest first
+pub async fn get_full_schedule(pool: &SqlitePool) -> Result<Vec<ScheduleRow>, sqlx::Error> {
     sqlx::query_as::<_, ScheduleRow>(
-        r#"SELECT * FROM schedule
-           WHERE game_date >= ? AND game_date <= ?
-           ORDER BY game_date, game_time"#
+        r#"SELECT * FROM schedule ORDER BY game_date, game_time"#
     )
-    .bind(&today)
-    .bind(&end_date)
     .fetch_all(pool)
     .await
 }
 
+/// Games from `start_date` through `end_date` (YYYY-MM-DD, both inclusive)
+pub async fn get_schedule_by_range(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<ScheduleRow>, sqlx::Error> {
+    with_retry(|| {
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"SELECT * FROM schedule
+               WHERE game_date BETWEEN ? AND ?
+               ORDER BY game_date, game_time"#
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
+
+pub async fn get_upcoming_schedule(pool: &SqlitePool, days: i32, today: &Today) -> Result<Vec<ScheduleRow>, sqlx::Error> {
+    get_schedule_by_range(pool, &today.ymd(), &today.plus_days(days as i64)).await
+}
+
 /// Get today + tomorrow schedule combined (for upcoming rosters endpoint)
-pub async fn get_upcoming_schedule_for_roster(pool: &SqlitePool) -> Result<Vec<ScheduleRow>, sqlx::Error> {
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let tomorrow = (chrono::Local::now() + chrono::Duration::days(1))
-        .format("%Y-%m-%d")
-        .to_string();
+pub async fn get_upcoming_schedule_for_roster(pool: &SqlitePool, today: &Today) -> Result<Vec<ScheduleRow>, sqlx::Error> {
+    let tomorrow = today.plus_days(1);
     sqlx::query_as::<_, ScheduleRow>(
         r#"SELECT * FROM schedule
            WHERE game_date IN (?, ?)
            ORDER BY game_date, game_time"#
     )
-    .bind(&today)
+    .bind(today.ymd())
     .bind(&tomorrow)
     .fetch_all(pool)
     .await
 }
 
-/// Get players for a specific team (with injury status and props availability)
-pub async fn get_team_roster(pool: &SqlitePool, team_id: i64) -> Result<Vec<RosterPlayerRow>, sqlx::Error> {
-    sqlx::query_as::<_, RosterPlayerRow>(
+/// Dates (YYYY-MM-DD) whose props count as "upcoming": today and tomorrow, plus the day
+/// after because late-night ET games on "tomorrow" have UTC dates that spill into it.
+/// Every props query should use this window so views agree on which props exist.
+pub fn props_date_window(today: &Today) -> [String; 3] {
+    [0, 1, 2].map(|offset| today.plus_days(offset))
+}
+
+/// Get players for a specific team (with injury status and props availability).
+/// `has_props` matches names the same way as `get_player_props`.
+pub async fn get_team_roster(pool: &SqlitePool, team_id: i64, today: &Today) -> Result<Vec<RosterPlayerRow>, sqlx::Error> {
+    let mut roster = sqlx::query_as::<_, RosterPlayerRow>(
         r#"SELECT
                ps.player_id,
                ps.player_name,
                ps.position,
                pi.injury_status,
                pi.injury_description,
-               (SELECT 1 FROM underdog_props
-                WHERE (full_name = ps.player_name
-                       OR full_name = REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(
-                           ps.player_name, 'ć', 'c'), 'č', 'c'), 'š', 's'), 'ž', 'z'), 'đ', 'd'))
-                AND DATE(scheduled_at) >= DATE('now')
-                LIMIT 1) IS NOT NULL as has_props
+               FALSE as has_props,
+               ps.last_updated
            FROM player_stats ps
            LEFT JOIN player_injuries pi ON ps.player_id = pi.player_id
            WHERE ps.team_id = ?
@@ -440,139 +1262,383 @@ pub async fn get_team_roster(pool: &SqlitePool, team_id: i64) -> Result<Vec<Rost
     )
     .bind(team_id)
     .fetch_all(pool)
-    .await
+    .await?;
+
+    let prop_names = get_prop_names_by_key(pool, &props_date_window(today)).await?;
+    for player in &mut roster {
+        player.has_props = !matching_prop_names(&player.player_name, &prop_names).is_empty();
+    }
+    Ok(roster)
 }
 
 /// Get game logs for a specific player
 pub async fn get_player_game_logs(pool: &SqlitePool, player_id: i64, limit: i64) -> Result<Vec<PlayerGameLog>, sqlx::Error> {
+    get_player_trailing_logs(pool, player_id, limit, &TrailingLogOptions::default()).await
+}
+
+/// Filters for a player's trailing game-log window
+#[derive(Debug, Default, Clone)]
+pub struct TrailingLogOptions {
+    /// Skip games where the player logged no minutes (DNPs)
+    pub exclude_dnp: bool,
+    /// Only games against this opponent team (derived from the schedule, not `matchup`)
+    pub opponent_id: Option<i64>,
+    /// Only games with this result ("W" or "L")
+    pub result: Option<String>,
+    /// Only games from this season (e.g. "2025-26")
+    pub season: Option<String>,
+}
+
+/// Get a player's most recent `games` game logs (newest first) after applying `opts`.
+/// Filters are applied before the limit, so asking for 10 games excluding DNPs
+/// returns the last 10 games the player actually played.
+pub async fn get_player_trailing_logs(
+    pool: &SqlitePool,
+    player_id: i64,
+    games: i64,
+    opts: &TrailingLogOptions,
+) -> Result<Vec<PlayerGameLog>, sqlx::Error> {
+    let result = opts.result.as_ref().map(|r| r.to_uppercase());
+
     sqlx::query_as::<_, PlayerGameLog>(
+        r#"SELECT * FROM (
+               SELECT
+                   pgl.game_id,
+                   pgl.player_id,
+                   pgl.team_id,
+                   pgl.season,
+                   pgl.game_date,
+                   pgl.matchup,
+                   CASE
+                       WHEN s.home_score IS NOT NULL AND s.away_score IS NOT NULL THEN
+                           CASE
+                               WHEN pgl.team_id = s.home_team_id THEN
+                                   CASE WHEN s.home_score > s.away_score THEN 'W' ELSE 'L' END
+                               ELSE
+                                   CASE WHEN s.away_score > s.home_score THEN 'W' ELSE 'L' END
+                           END
+                       ELSE NULL
+                   END as wl,
+                   pgl.min,
+                   pgl.pts,
+                   pgl.reb,
+                   pgl.ast,
+                   pgl.stl,
+                   pgl.blk,
+                   pgl.fgm,
+                   pgl.fga,
+                   pgl.fg3m,
+                   pgl.fg3a,
+                   pgl.ftm,
+                   pgl.fta,
+                   pgl.tov,
+                   CASE
+                       WHEN s.home_score IS NOT NULL AND s.away_score IS NOT NULL THEN
+                           CASE
+                               WHEN pgl.team_id = s.home_team_id THEN s.home_score - s.away_score
+                               ELSE s.away_score - s.home_score
+                           END
+                       ELSE NULL
+                   END as game_margin,
+                   pgl.oreb,
+                   pgl.dreb
+               FROM player_game_logs pgl
+               LEFT JOIN schedule s ON pgl.game_id = s.game_id
+               WHERE pgl.player_id = ?
+                 AND (? = 0 OR (pgl.min IS NOT NULL AND pgl.min > 0))
+                 AND (? IS NULL OR
+                      CASE WHEN pgl.team_id = s.home_team_id THEN s.away_team_id ELSE s.home_team_id END = ?)
+                 AND (? IS NULL OR pgl.season = ?)
+           )
+           WHERE (? IS NULL OR wl = ?)
+           ORDER BY game_date DESC
+           LIMIT ?"#
+    )
+    .bind(player_id)
+    .bind(opts.exclude_dnp)
+    .bind(opts.opponent_id)
+    .bind(opts.opponent_id)
+    .bind(&opts.season)
+    .bind(&opts.season)
+    .bind(&result)
+    .bind(&result)
+    .bind(games)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a player's per-game averages split by home and away games.
+/// Home/away is derived from the schedule; games with no minutes logged are excluded.
+pub async fn get_home_away_splits(pool: &SqlitePool, player_id: i64) -> Result<HomeAwaySplits, sqlx::Error> {
+    let rows = sqlx::query_as::<_, SplitRow>(
         r#"SELECT
-               pgl.game_id,
-               pgl.player_id,
-               pgl.team_id,
-               pgl.season,
-               pgl.game_date,
-               pgl.matchup,
-               CASE
-                   WHEN s.home_score IS NOT NULL AND s.away_score IS NOT NULL THEN
-                       CASE
-                           WHEN pgl.team_id = s.home_team_id THEN
-                               CASE WHEN s.home_score > s.away_score THEN 'W' ELSE 'L' END
-                           ELSE
-                               CASE WHEN s.away_score > s.home_score THEN 'W' ELSE 'L' END
-                       END
-                   ELSE NULL
-               END as wl,
-               pgl.min,
-               pgl.pts,
-               pgl.reb,
-               pgl.ast,
-               pgl.stl,
-               pgl.blk,
-               pgl.fgm,
-               pgl.fga,
-               pgl.fg3m,
-               pgl.fg3a,
-               pgl.ftm,
-               pgl.fta,
-               pgl.tov,
-               CASE
-                   WHEN s.home_score IS NOT NULL AND s.away_score IS NOT NULL THEN
-                       CASE
-                           WHEN pgl.team_id = s.home_team_id THEN s.home_score - s.away_score
-                           ELSE s.away_score - s.home_score
-                       END
-                   ELSE NULL
-               END as game_margin,
-               pgl.oreb,
-               pgl.dreb
+               pgl.team_id = s.home_team_id AS is_home,
+               COUNT(*) AS games,
+               CAST(COALESCE(AVG(pgl.min), 0) AS REAL) AS min,
+               CAST(COALESCE(AVG(pgl.pts), 0) AS REAL) AS pts,
+               CAST(COALESCE(AVG(pgl.reb), 0) AS REAL) AS reb,
+               CAST(COALESCE(AVG(pgl.ast), 0) AS REAL) AS ast,
+               CAST(COALESCE(AVG(pgl.stl), 0) AS REAL) AS stl,
+               CAST(COALESCE(AVG(pgl.blk), 0) AS REAL) AS blk,
+               CAST(COALESCE(AVG(pgl.tov), 0) AS REAL) AS tov,
+               CAST(COALESCE(AVG(pgl.fg3m), 0) AS REAL) AS fg3m,
+               CAST(COALESCE(AVG(pgl.fga), 0) AS REAL) AS fga,
+               CAST(COALESCE(AVG(pgl.fta), 0) AS REAL) AS fta
            FROM player_game_logs pgl
-           LEFT JOIN schedule s ON pgl.game_id = s.game_id
-           WHERE pgl.player_id = ?
-           ORDER BY pgl.game_date DESC
-           LIMIT ?"#
+           JOIN schedule s ON pgl.game_id = s.game_id
+           WHERE pgl.player_id = ? AND pgl.min IS NOT NULL AND pgl.min > 0
+           GROUP BY is_home"#
     )
     .bind(player_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut splits = HomeAwaySplits {
+        home: SplitLine::default(),
+        away: SplitLine::default(),
+        games_home: 0,
+        games_away: 0,
+    };
+
+    for row in rows {
+        if row.is_home {
+            splits.home = row.line;
+            splits.games_home = row.games;
+        } else {
+            splits.away = row.line;
+            splits.games_away = row.games;
+        }
+    }
+
+    Ok(splits)
+}
+
+/// Count how often a player went over `line` for an Underdog stat in their last `last_n`
+/// played games. DNPs are excluded from the window. Returns None if the stat can't be
+/// mapped to game-log columns or the player has no qualifying games.
+pub async fn get_prop_hit_rate(
+    pool: &SqlitePool,
+    player_id: i64,
+    stat_name: &str,
+    line: f64,
+    last_n: i64,
+) -> Result<Option<HitRate>, sqlx::Error> {
+    let opts = TrailingLogOptions {
+        exclude_dnp: true,
+        ..Default::default()
+    };
+    let logs = get_player_trailing_logs(pool, player_id, last_n, &opts).await?;
+
+    let values: Vec<i32> = logs.iter().filter_map(|log| log.underdog_stat(stat_name)).collect();
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let hits = values.iter().filter(|&&v| v as f64 > line).count() as i64;
+    let games = values.len() as i64;
+
+    Ok(Some(HitRate {
+        hits,
+        games,
+        pct: (hits as f32 / games as f32) * 100.0,
+    }))
+}
+
+/// Get players whose last-3-game minutes most exceed their average over the rest of
+/// `season`, as of `as_of_date`. The baseline leaves out those 3 games, so it's what the
+/// recent stretch is compared against. Only players who have played within the previous
+/// 10 days are considered active.
+pub async fn get_usage_spikes(
+    pool: &SqlitePool,
+    as_of_date: &str,
+    season: &str,
+    limit: i64,
+) -> Result<Vec<UsageSpike>, sqlx::Error> {
+    sqlx::query_as::<_, UsageSpike>(
+        r#"WITH played AS (
+               SELECT player_id, game_date, min,
+                      ROW_NUMBER() OVER (PARTITION BY player_id ORDER BY game_date DESC) AS rn
+               FROM player_game_logs
+               WHERE game_date <= ? AND season = ? AND min IS NOT NULL AND min > 0
+           ),
+           minutes AS (
+               SELECT player_id,
+                      CAST(AVG(CASE WHEN rn <= 3 THEN min END) AS REAL) AS recent_minutes,
+                      CAST(AVG(CASE WHEN rn > 3 THEN min END) AS REAL) AS season_minutes,
+                      COUNT(*) AS games_played,
+                      MAX(game_date) AS last_game_date
+               FROM played
+               GROUP BY player_id
+               HAVING COUNT(*) > 3 AND MAX(game_date) >= DATE(?, '-10 days')
+           )
+           SELECT ps.player_id, ps.player_name, ps.team_id,
+                  m.recent_minutes, m.season_minutes,
+                  CAST(m.recent_minutes - m.season_minutes AS REAL) AS minutes_delta,
+                  m.games_played, m.last_game_date
+           FROM minutes m
+           JOIN player_stats ps ON CAST(ps.player_id AS TEXT) = CAST(m.player_id AS TEXT)
+           WHERE m.recent_minutes > m.season_minutes
+           ORDER BY minutes_delta DESC
+           LIMIT ?"#
+    )
+    .bind(as_of_date)
+    .bind(season)
+    .bind(as_of_date)
     .bind(limit)
     .fetch_all(pool)
     .await
 }
 
 /// Normalize a name by removing accents and special characters
-/// Helps match "Luka Dončić" with "Luka Doncic"
-fn normalize_name(name: &str) -> String {
-    name.chars()
-        .map(|c| match c {
-            'á' | 'à' | 'ä' | 'â' | 'ã' => 'a',
-            'é' | 'è' | 'ë' | 'ê' => 'e',
-            'í' | 'ì' | 'ï' | 'î' => 'i',
-            'ó' | 'ò' | 'ö' | 'ô' | 'õ' => 'o',
-            'ú' | 'ù' | 'ü' | 'û' => 'u',
-            'ć' | 'č' | 'ç' => 'c',
-            'ñ' => 'n',
-            'š' => 's',
-            'ž' => 'z',
-            'ý' | 'ÿ' => 'y',
-            'đ' => 'd',
-            'Á' | 'À' | 'Ä' | 'Â' | 'Ã' => 'A',
-            'É' | 'È' | 'Ë' | 'Ê' => 'E',
-            'Í' | 'Ì' | 'Ï' | 'Î' => 'I',
-            'Ó' | 'Ò' | 'Ö' | 'Ô' | 'Õ' => 'O',
-            'Ú' | 'Ù' | 'Ü' | 'Û' => 'U',
-            'Ć' | 'Č' | 'Ç' => 'C',
-            'Ñ' => 'N',
-            'Š' => 'S',
-            'Ž' => 'Z',
-            'Ý' | 'Ÿ' => 'Y',
-            'Đ' => 'D',
-            _ => c,
-        })
-        .collect()
+/// Helps match "Luka Dončić" with "Luka Doncic". Names are NFD-decomposed and combining
+/// marks dropped, so any accent works whether scraped precomposed or as combining marks.
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.nfd().filter(|c| !is_combining_mark(*c)) {
+        match c {
+            // Letters that have no decomposition into base letter + mark
+            'đ' => normalized.push('d'),
+            'Đ' => normalized.push('D'),
+            'ø' => normalized.push('o'),
+            'Ø' => normalized.push('O'),
+            'ł' => normalized.push('l'),
+            'Ł' => normalized.push('L'),
+            'ß' => normalized.push_str("ss"),
+            // Unicode hyphens and dashes (e.g. in hyphenated surnames)
+            '\u{2010}'..='\u{2015}' => normalized.push('-'),
+            _ => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// Key two names match on: normalized and lowercased, so an accent (or capitalization)
+/// on either side - our stored name or Underdog's - still matches
+pub fn name_key(name: &str) -> String {
+    normalize_name(name).to_lowercase()
+}
+
+/// Distinct `underdog_props.full_name`s scheduled on `dates`, grouped by `name_key`
+async fn get_prop_names_by_key(
+    pool: &SqlitePool,
+    dates: &[String],
+) -> Result<std::collections::HashMap<String, Vec<String>>, sqlx::Error> {
+    let sql = format!(
+        "SELECT DISTINCT full_name FROM underdog_props WHERE DATE(scheduled_at) IN ({}) ORDER BY full_name",
+        vec!["?"; dates.len()].join(", ")
+    );
+    let full_names = with_retry(|| {
+        let mut query = sqlx::query_scalar::<_, String>(&sql);
+        for date in dates {
+            query = query.bind(date);
+        }
+        query.fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await?;
+
+    let mut by_key: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    for full_name in full_names {
+        by_key.entry(name_key(&full_name)).or_default().push(full_name);
+    }
+    Ok(by_key)
+}
+
+/// Stored prop names matching `player_name`. An exact match wins; otherwise every prop
+/// name with the same `name_key`.
+fn matching_prop_names(player_name: &str, by_key: &std::collections::HashMap<String, Vec<String>>) -> Vec<String> {
+    let Some(candidates) = by_key.get(&name_key(player_name)) else {
+        return Vec::new();
+    };
+    if candidates.iter().any(|name| name == player_name) {
+        vec![player_name.to_string()]
+    } else {
+        candidates.clone()
+    }
 }
 
 /// Get underdog props for a player by name (for today's or tomorrow's games)
-/// Only returns the latest version of each line (by updated_at timestamp)
-/// Tries exact match first, then normalized name match for accented characters
-pub async fn get_player_props(pool: &SqlitePool, player_name: &str) -> Result<Vec<UnderdogProp>, sqlx::Error> {
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let tomorrow = (chrono::Local::now() + chrono::Duration::days(1))
-        .format("%Y-%m-%d")
-        .to_string();
-    // Late-night ET games on "tomorrow" have UTC dates that spill into day_after_tomorrow
-    let day_after_tomorrow = (chrono::Local::now() + chrono::Duration::days(2))
-        .format("%Y-%m-%d")
-        .to_string();
-
-    // Try exact match first
-    let results = sqlx::query_as::<_, UnderdogProp>(
+/// Only returns the latest version of each line (by updated_at timestamp); alternate
+/// lines for the same stat are kept as separate rows
+/// Tries exact match first, then matches normalized names on both sides (see `name_key`)
+pub async fn get_player_props(pool: &SqlitePool, player_name: &str, today: &Today) -> Result<Vec<UnderdogProp>, sqlx::Error> {
+    let dates = props_date_window(today);
+    let names = matching_prop_names(player_name, &get_prop_names_by_key(pool, &dates).await?);
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let sql = format!(
         r#"SELECT id, full_name, team_name, opponent_name, stat_name, stat_value,
                   choice, american_price, decimal_price, scheduled_at
            FROM (
                SELECT id, full_name, team_name, opponent_name, stat_name, stat_value,
                       choice, american_price, decimal_price, scheduled_at,
                       ROW_NUMBER() OVER (
-                          PARTITION BY stat_name, choice
+                          PARTITION BY stat_name, stat_value, choice
                           ORDER BY updated_at DESC
                       ) as rn
                FROM underdog_props
-               WHERE full_name = ? AND DATE(scheduled_at) IN (?, ?, ?)
+               WHERE full_name IN ({}) AND DATE(scheduled_at) IN ({})
            )
            WHERE rn = 1
-           ORDER BY stat_name, choice"#
-    )
-    .bind(player_name)
-    .bind(&today)
-    .bind(&tomorrow)
-    .bind(&day_after_tomorrow)
-    .fetch_all(pool)
-    .await?;
+           ORDER BY stat_name, stat_value, choice"#,
+        vec!["?"; names.len()].join(", "),
+        vec!["?"; dates.len()].join(", ")
+    );
+    with_retry(|| {
+        let mut query = sqlx::query_as::<_, UnderdogProp>(&sql);
+        for name in &names {
+            query = query.bind(name);
+        }
+        for date in &dates {
+            query = query.bind(date);
+        }
+        query.fetch_all(pool)
+    }, *DB_RETRY_ATTEMPTS)
+    .await
+}
 
-    if !results.is_empty() {
-        return Ok(results);
+/// Get every recorded version of a player's underdog line for one stat (for today's or
+/// tomorrow's games), oldest first, so line movement through the day can be traced.
+/// Names are matched like `get_player_props`
+pub async fn get_player_prop_history(
+    pool: &SqlitePool,
+    player_name: &str,
+    stat_name: &str,
+    today: &Today,
+) -> Result<Vec<PropLineSnapshot>, sqlx::Error> {
+    let dates = props_date_window(today);
+    let names = matching_prop_names(player_name, &get_prop_names_by_key(pool, &dates).await?);
+    if names.is_empty() {
+        return Ok(vec![]);
     }
 
-    // Try normalized name (strips accents: Dončić -> Doncic)
-    let normalized = normalize_name(player_name);
+    let sql = format!(
+        r#"SELECT updated_at, stat_value, american_price, choice
+           FROM underdog_props
+           WHERE full_name IN ({}) AND stat_name = ? AND DATE(scheduled_at) IN ({})
+           ORDER BY updated_at, choice"#,
+        vec!["?"; names.len()].join(", "),
+        vec!["?"; dates.len()].join(", ")
+    );
+    let mut query = sqlx::query_as::<_, PropLineSnapshot>(&sql);
+    for name in &names {
+        query = query.bind(name);
+    }
+    query = query.bind(stat_name);
+    for date in &dates {
+        query = query.bind(date);
+    }
+    query.fetch_all(pool).await
+}
+
+/// Get the latest version of every underdog prop line scheduled on `date` or the day after
+/// (late ET games spill into the next UTC date). Callers match props to games by team names.
+pub async fn get_slate_props(pool: &SqlitePool, date: &str) -> Result<Vec<UnderdogProp>, sqlx::Error> {
+    let next_day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| (d + chrono::Duration::days(1)).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| date.to_string());
+
     sqlx::query_as::<_, UnderdogProp>(
         r#"SELECT id, full_name, team_name, opponent_name, stat_name, stat_value,
                   choice, american_price, decimal_price, scheduled_at
@@ -580,60 +1646,315 @@ pub async fn get_player_props(pool: &SqlitePool, player_name: &str) -> Result<Ve
                SELECT id, full_name, team_name, opponent_name, stat_name, stat_value,
                       choice, american_price, decimal_price, scheduled_at,
                       ROW_NUMBER() OVER (
-                          PARTITION BY stat_name, choice
+                          PARTITION BY full_name, stat_name, stat_value, choice
                           ORDER BY updated_at DESC
                       ) as rn
                FROM underdog_props
-               WHERE full_name = ? AND DATE(scheduled_at) IN (?, ?, ?)
+               WHERE DATE(scheduled_at) IN (?, ?)
            )
            WHERE rn = 1
-           ORDER BY stat_name, choice"#
+           ORDER BY full_name, stat_name, stat_value, choice"#
     )
-    .bind(&normalized)
-    .bind(&today)
-    .bind(&tomorrow)
-    .bind(&day_after_tomorrow)
+    .bind(date)
+    .bind(&next_day)
     .fetch_all(pool)
     .await
 }
 
+/// Players with any upcoming prop (see `props_date_window`) and their count of distinct
+/// (stat, line) props. Prop names are matched to `player_stats` by `name_key`, an exact
+/// name winning; names with no player_stats row are left out. Most props first.
+pub async fn get_players_with_props(pool: &SqlitePool, today: &Today) -> Result<Vec<PropPlayer>, sqlx::Error> {
+    let dates = props_date_window(today);
+    let sql = format!(
+        r#"SELECT full_name, COUNT(DISTINCT stat_name || ':' || stat_value)
+           FROM underdog_props
+           WHERE DATE(scheduled_at) IN ({})
+           GROUP BY full_name"#,
+        vec!["?"; dates.len()].join(", ")
+    );
+    let mut query = sqlx::query_as::<_, (String, i64)>(&sql);
+    for date in &dates {
+        query = query.bind(date);
+    }
+    let prop_counts = query.fetch_all(pool).await?;
+    if prop_counts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let players = sqlx::query_as::<_, PlayerSuggestion>(
+        r#"SELECT ps.player_id, ps.player_name, t.abbreviation as team_abbreviation
+           FROM player_stats ps
+           LEFT JOIN teams t ON ps.team_id = t.team_id"#
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut by_key: std::collections::HashMap<String, Vec<&PlayerSuggestion>> = std::collections::HashMap::new();
+    for player in &players {
+        by_key.entry(name_key(&player.player_name)).or_default().push(player);
+    }
+
+    // Several prop names (accented and not) can land on one player
+    let mut by_player: std::collections::HashMap<i64, PropPlayer> = std::collections::HashMap::new();
+    for (full_name, count) in prop_counts {
+        let Some(candidates) = by_key.get(&name_key(&full_name)) else {
+            continue;
+        };
+        let player = candidates
+            .iter()
+            .find(|p| p.player_name == full_name)
+            .unwrap_or(&candidates[0]);
+        by_player
+            .entry(player.player_id)
+            .or_insert_with(|| PropPlayer {
+                player_id: player.player_id,
+                player_name: player.player_name.clone(),
+                team: player.team_abbreviation.clone(),
+                prop_count: 0,
+            })
+            .prop_count += count;
+    }
+
+    let mut prop_players: Vec<PropPlayer> = by_player.into_values().collect();
+    prop_players.sort_by(|a, b| b.prop_count.cmp(&a.prop_count).then_with(|| a.player_name.cmp(&b.player_name)));
+    Ok(prop_players)
+}
+
+/// Get the distinct Underdog stat names offered for games on `date` (or the next day,
+/// since late ET games spill into the next UTC date)
+pub async fn get_slate_stat_names(pool: &SqlitePool, date: &str) -> Result<Vec<String>, sqlx::Error> {
+    let next_day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| (d + chrono::Duration::days(1)).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| date.to_string());
+
+    sqlx::query_scalar(
+        r#"SELECT DISTINCT stat_name
+           FROM underdog_props
+           WHERE DATE(scheduled_at) IN (?, ?)"#
+    )
+    .bind(date)
+    .bind(&next_day)
+    .fetch_all(pool)
+    .await
+}
+
+/// (player_id, player_name) for each id that has a player_stats row
+async fn get_player_names(pool: &SqlitePool, player_ids: &[i64]) -> Result<Vec<(i64, String)>, sqlx::Error> {
+    if player_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let players_sql = format!(
+        "SELECT player_id, player_name FROM player_stats WHERE player_id IN ({})",
+        vec!["?"; player_ids.len()].join(", ")
+    );
+    let mut players_query = sqlx::query_as::<_, (i64, String)>(&players_sql);
+    for id in player_ids {
+        players_query = players_query.bind(id);
+    }
+    players_query.fetch_all(pool).await
+}
+
+/// Latest `updated_at` among each player's upcoming props (see `props_date_window`),
+/// keyed by player_id. Matches names like `get_batch_player_props`; players without
+/// upcoming props are left out.
+pub async fn get_latest_prop_updates(
+    pool: &SqlitePool,
+    player_ids: &[i64],
+    today: &Today,
+) -> Result<std::collections::HashMap<i64, String>, sqlx::Error> {
+    let mut updates = std::collections::HashMap::new();
+    let players = get_player_names(pool, player_ids).await?;
+    if players.is_empty() {
+        return Ok(updates);
+    }
+
+    let dates = props_date_window(today);
+    let prop_names = get_prop_names_by_key(pool, &dates).await?;
+    let names_by_player: Vec<(i64, Vec<String>)> = players
+        .into_iter()
+        .map(|(player_id, name)| (player_id, matching_prop_names(&name, &prop_names)))
+        .filter(|(_, names)| !names.is_empty())
+        .collect();
+    let names: Vec<&String> = names_by_player.iter().flat_map(|(_, names)| names).collect();
+    if names.is_empty() {
+        return Ok(updates);
+    }
+
+    let sql = format!(
+        r#"SELECT full_name, MAX(updated_at)
+           FROM underdog_props
+           WHERE full_name IN ({}) AND DATE(scheduled_at) IN ({})
+           GROUP BY full_name"#,
+        vec!["?"; names.len()].join(", "),
+        vec!["?"; dates.len()].join(", ")
+    );
+    let mut query = sqlx::query_as::<_, (String, String)>(&sql);
+    for name in &names {
+        query = query.bind(name);
+    }
+    for date in &dates {
+        query = query.bind(date);
+    }
+    let latest_by_name: std::collections::HashMap<String, String> =
+        query.fetch_all(pool).await?.into_iter().collect();
+
+    for (player_id, names) in &names_by_player {
+        let latest = names.iter().filter_map(|name| latest_by_name.get(name)).max();
+        if let Some(latest) = latest {
+            updates.insert(*player_id, latest.clone());
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Get underdog props for many players at once, keyed by player_id. Names are looked up
+/// from `player_stats` and matched against prop names as in `get_player_props`, then
+/// fetched in a single `full_name IN (...)` query. With `date`, props scheduled that day or
+/// the next are returned; otherwise the usual upcoming window from `props_date_window`.
+/// Players without props (or unknown ids) are left out of the map.
+pub async fn get_batch_player_props(
+    pool: &SqlitePool,
+    player_ids: &[i64],
+    date: Option<&str>,
+    today: &Today,
+) -> Result<std::collections::HashMap<i64, Vec<UnderdogProp>>, sqlx::Error> {
+    let mut props_by_player = std::collections::HashMap::new();
+    let players = get_player_names(pool, player_ids).await?;
+    if players.is_empty() {
+        return Ok(props_by_player);
+    }
+
+    let dates: Vec<String> = match date {
+        Some(date) => {
+            let next_day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(|d| (d + chrono::Duration::days(1)).format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|_| date.to_string());
+            vec![date.to_string(), next_day]
+        }
+        None => props_date_window(today).to_vec(),
+    };
+
+    let prop_names = get_prop_names_by_key(pool, &dates).await?;
+    let names_by_player: Vec<(i64, Vec<String>)> = players
+        .into_iter()
+        .map(|(player_id, name)| (player_id, matching_prop_names(&name, &prop_names)))
+        .filter(|(_, names)| !names.is_empty())
+        .collect();
+    let names: Vec<&String> = names_by_player.iter().flat_map(|(_, names)| names).collect();
+    if names.is_empty() {
+        return Ok(props_by_player);
+    }
+
+    let props_sql = format!(
+        r#"SELECT id, full_name, team_name, opponent_name, stat_name, stat_value,
+                  choice, american_price, decimal_price, scheduled_at
+           FROM (
+               SELECT id, full_name, team_name, opponent_name, stat_name, stat_value,
+                      choice, american_price, decimal_price, scheduled_at,
+                      ROW_NUMBER() OVER (
+                          PARTITION BY full_name, stat_name, stat_value, choice
+                          ORDER BY updated_at DESC
+                      ) as rn
+               FROM underdog_props
+               WHERE full_name IN ({}) AND DATE(scheduled_at) IN ({})
+           )
+           WHERE rn = 1
+           ORDER BY full_name, stat_name, stat_value, choice"#,
+        vec!["?"; names.len()].join(", "),
+        vec!["?"; dates.len()].join(", ")
+    );
+    let mut props_query = sqlx::query_as::<_, UnderdogProp>(&props_sql);
+    for name in &names {
+        props_query = props_query.bind(name);
+    }
+    for date in &dates {
+        props_query = props_query.bind(date);
+    }
+    let props = props_query.fetch_all(pool).await?;
+
+    // With several matching prop names (e.g. "Jokic" and "JOKIC"), the first with props wins
+    let mut by_name: std::collections::HashMap<String, Vec<UnderdogProp>> = std::collections::HashMap::new();
+    for prop in props {
+        by_name.entry(prop.full_name.clone()).or_default().push(prop);
+    }
+    for (player_id, names) in names_by_player {
+        if let Some(player_props) = names.iter().find_map(|name| by_name.remove(name)) {
+            props_by_player.insert(player_id, player_props);
+        }
+    }
+
+    Ok(props_by_player)
+}
+
 /// Get underdog props for a player by ID (looks up name first)
-pub async fn get_player_props_by_id(pool: &SqlitePool, player_id: i64) -> Result<Vec<UnderdogProp>, sqlx::Error> {
+pub async fn get_player_props_by_id(pool: &SqlitePool, player_id: i64, today: &Today) -> Result<Vec<UnderdogProp>, sqlx::Error> {
     // First get the player name
     let player = get_player_by_id(pool, player_id).await?;
 
     match player {
-        Some(p) => get_player_props(pool, &p.player_name).await,
+        Some(p) => get_player_props(pool, &p.player_name, today).await,
         None => Ok(vec![]),
     }
 }
 
-/// Get team defensive play type rankings (1 = best defense, 30 = worst)
+/// A player's (injury_status, injury_description) on the latest injury report, matched
+/// by player_id so accent differences between name sources don't matter
+pub async fn get_player_injury(
+    pool: &SqlitePool,
+    player_id: i64,
+) -> Result<Option<(String, Option<String>)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, Option<String>)>(
+        r#"SELECT injury_status, injury_description
+           FROM player_injuries
+           WHERE player_id = ?
+             AND collection_date = (SELECT MAX(collection_date) FROM player_injuries)"#
+    )
+    .bind(player_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get team defensive play type rankings (1 = best defense, league size = worst)
 pub async fn get_team_defensive_play_type_ranks(pool: &SqlitePool) -> Result<std::collections::HashMap<(i64, String), i32>, sqlx::Error> {
-    // Get all team defensive play types ordered by PPP (lower = better defense)
+    // Get all team defensive play types (lower PPP = better defense)
     let rows = sqlx::query_as::<_, (i64, String, f32)>(
-        r#"SELECT team_id, play_type, ppp FROM team_defensive_play_types ORDER BY play_type, ppp ASC"#
+        r#"SELECT team_id, play_type, ppp FROM team_defensive_play_types ORDER BY play_type"#
     )
     .fetch_all(pool)
     .await?;
 
-    // Group by play_type and assign ranks
-    let mut ranks: std::collections::HashMap<(i64, String), i32> = std::collections::HashMap::new();
-    let mut current_play_type = String::new();
-    let mut rank = 0;
+    // Group by play_type and rank within each group
+    let mut by_play_type: std::collections::HashMap<&str, Vec<&(i64, String, f32)>> = std::collections::HashMap::new();
+    for row in &rows {
+        by_play_type.entry(row.1.as_str()).or_default().push(row);
+    }
 
-    for (team_id, play_type, _ppp) in rows {
-        if play_type != current_play_type {
-            current_play_type = play_type.clone();
-            rank = 0;
+    let mut ranks: std::collections::HashMap<(i64, String), i32> = std::collections::HashMap::new();
+    for group in by_play_type.values() {
+        let group_ranks = rank_values(group, |row| row.2, true);
+        for (row, rank) in group.iter().zip(group_ranks) {
+            ranks.insert((row.0, row.1.clone()), rank);
         }
-        rank += 1;
-        ranks.insert((team_id, play_type), rank);
     }
 
     Ok(ranks)
 }
 
+/// Fingerprint of the latest props/odds/injury data, changing whenever a scraper writes.
+/// Used to tell whether cached screener and slate results are still current.
+pub async fn get_props_data_version(pool: &SqlitePool) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"SELECT COALESCE((SELECT MAX(scraped_at) FROM underdog_props), '')
+                  || '|' || COALESCE((SELECT MAX(scraped_at) FROM all_props), '')
+                  || '|' || COALESCE((SELECT MAX(scraped_at) FROM odds_api_props), '')
+                  || '|' || COALESCE((SELECT MAX(collection_date) FROM player_injuries), '')"#
+    )
+    .fetch_one(pool)
+    .await
+}
+
 /// Ensure indexes exist for fast top-picks joins
 pub async fn ensure_top_picks_indexes(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     sqlx::query(
@@ -651,6 +1972,114 @@ pub async fn ensure_top_picks_indexes(pool: &SqlitePool) -> Result<(), sqlx::Err
     Ok(())
 }
 
+/// Ensure the table tracking flagged screener picks for closing line value exists
+pub async fn ensure_clv_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS screener_pick_snapshots (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               player_name TEXT NOT NULL,
+               stat_type TEXT NOT NULL,
+               direction TEXT NOT NULL,
+               game_date TEXT NOT NULL,
+               game_time TEXT,
+               best_book TEXT NOT NULL,
+               open_line REAL NOT NULL,
+               open_ud_odds INTEGER,
+               open_implied_prob REAL NOT NULL,
+               open_fair_prob REAL NOT NULL,
+               flagged_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+               close_line REAL,
+               close_ud_odds INTEGER,
+               close_fair_prob REAL,
+               closed_at TEXT,
+               UNIQUE(player_name, stat_type, direction, game_date)
+           )"#
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Record top picks at flag time. The first time a pick is flagged is its opening
+/// snapshot, so repeat flags for the same pick are ignored.
+pub async fn record_pick_snapshots(pool: &SqlitePool, picks: &[TopPick]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for pick in picks {
+        sqlx::query(
+            r#"INSERT OR IGNORE INTO screener_pick_snapshots
+                   (player_name, stat_type, direction, game_date, game_time, best_book,
+                    open_line, open_ud_odds, open_implied_prob, open_fair_prob)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+        )
+        .bind(&pick.player_name)
+        .bind(&pick.stat_type)
+        .bind(&pick.direction)
+        .bind(&pick.game_date)
+        .bind(&pick.game_time)
+        .bind(&pick.best_book)
+        .bind(pick.ud_line)
+        .bind(pick.ud_odds)
+        .bind(pick.ud_implied_prob)
+        .bind(pick.best_book_devigged_prob)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Get flagged picks on or after `from_date` (their games may or may not have started)
+pub async fn get_open_clv_snapshots(pool: &SqlitePool, from_date: &str) -> Result<Vec<ClvSnapshotRow>, sqlx::Error> {
+    sqlx::query_as::<_, ClvSnapshotRow>(
+        r#"SELECT * FROM screener_pick_snapshots WHERE game_date >= ? ORDER BY game_date"#
+    )
+    .bind(from_date)
+    .fetch_all(pool)
+    .await
+}
+
+/// Overwrite the closing quote for a flagged pick with the latest pre-tip values.
+/// `close_fair_prob` is None once the book stops quoting the flagged line.
+pub async fn update_clv_close(
+    pool: &SqlitePool,
+    id: i64,
+    close_line: f64,
+    close_ud_odds: Option<i32>,
+    close_fair_prob: Option<f64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"UPDATE screener_pick_snapshots
+           SET close_line = ?, close_ud_odds = ?, close_fair_prob = ?, closed_at = CURRENT_TIMESTAMP
+           WHERE id = ?"#
+    )
+    .bind(close_line)
+    .bind(close_ud_odds)
+    .bind(close_fair_prob)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Get flagged picks within an optional game date range
+pub async fn get_clv_snapshots(
+    pool: &SqlitePool,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<Vec<ClvSnapshotRow>, sqlx::Error> {
+    sqlx::query_as::<_, ClvSnapshotRow>(
+        r#"SELECT * FROM screener_pick_snapshots
+           WHERE (? IS NULL OR game_date >= ?)
+             AND (? IS NULL OR game_date <= ?)
+           ORDER BY game_date DESC, player_name, stat_type"#
+    )
+    .bind(start_date)
+    .bind(start_date)
+    .bind(end_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+}
+
 /// Get Underdog even-odds lines joined against individual sharp book rows.
 /// Filters odds_api_props to only include matchups on today's actual schedule
 /// (avoids UTC vs ET date mismatch for late-night games).
@@ -728,57 +2157,587 @@ pub async fn get_top_pick_candidates(
     .await
 }
 
-/// Get DNP (Did Not Play) players for a specific game and team
-/// Returns top 2 players who were on the roster but didn't play, sorted by season average
-pub async fn get_dnp_players_for_game(
+/// Stat categories the DNP lookup can rank by (also guards the dynamic column name)
+pub const DNP_STAT_COLUMNS: [&str; 14] = [
+    "points", "assists", "rebounds", "threes_made", "threes_attempted", "fg_attempted",
+    "pts_plus_ast", "pts_plus_reb", "ast_plus_reb", "pts_plus_ast_plus_reb",
+    "steals", "blocks", "steals_plus_blocks", "turnovers",
+];
+
+/// Get DNP (Did Not Play) players for several games of one team, keyed by game_id
+/// Each game gets the top 2 players who were on the roster but didn't play, sorted by
+/// season average. Uses one roster query and one `game_id IN (...)` query for all games.
+pub async fn get_dnp_players_for_games(
     pool: &SqlitePool,
-    game_id: &str,
+    game_ids: &[String],
     team_id: i64,
     stat_column: &str,
-) -> Result<Vec<crate::models::DnpPlayer>, sqlx::Error> {
-    // Validate stat_column to prevent SQL injection
-    let valid_stats = ["points", "assists", "rebounds", "threes_made", "threes_attempted", "fg_attempted",
-                       "pts_plus_ast", "pts_plus_reb", "ast_plus_reb", "pts_plus_ast_plus_reb",
-                       "steals", "blocks", "steals_plus_blocks", "turnovers"];
+) -> Result<std::collections::HashMap<String, Vec<crate::models::DnpPlayer>>, sqlx::Error> {
+    use std::collections::{HashMap, HashSet};
 
-    if !valid_stats.contains(&stat_column) {
-        // Return empty vec for invalid stat
-        return Ok(vec![]);
+    // Validate stat_column to prevent SQL injection
+    if !DNP_STAT_COLUMNS.contains(&stat_column) || game_ids.is_empty() {
+        // Return empty map for invalid stat
+        return Ok(HashMap::new());
     }
 
-    // Build the query dynamically with the stat column
-    let query = format!(
+    // Build the roster query dynamically with the stat column
+    let roster_query = format!(
         r#"
         SELECT ps.player_id, ps.player_name, ps.position,
                COALESCE(ps.{}, 0.0) as season_avg
         FROM player_stats ps
         WHERE ps.team_id = ?
-          AND NOT EXISTS (
-              SELECT 1
-              FROM player_game_logs pgl
-              WHERE pgl.game_id = ?
-                AND CAST(pgl.player_id AS TEXT) = CAST(ps.player_id AS TEXT)
-          )
         ORDER BY season_avg DESC
-        LIMIT 2
         "#,
         stat_column
     );
 
-    let rows = sqlx::query_as::<_, (i64, String, Option<String>, f32)>(&query)
+    let roster = sqlx::query_as::<_, (i64, String, Option<String>, f32)>(&roster_query)
         .bind(team_id)
-        .bind(game_id)
         .fetch_all(pool)
         .await?;
 
-    Ok(rows
-        .into_iter()
-        .map(|(player_id, player_name, position, season_avg)| crate::models::DnpPlayer {
-            player_id,
-            player_name,
-            position,
-            season_avg,
+    // Everyone who logged a game in any of the requested games
+    let placeholders = vec!["?"; game_ids.len()].join(", ");
+    let played_query = format!(
+        "SELECT game_id, CAST(player_id AS TEXT) FROM player_game_logs WHERE game_id IN ({})",
+        placeholders
+    );
+
+    let mut query = sqlx::query_as::<_, (String, String)>(&played_query);
+    for game_id in game_ids {
+        query = query.bind(game_id);
+    }
+    let played: HashSet<(String, String)> = query.fetch_all(pool).await?.into_iter().collect();
+
+    Ok(game_ids
+        .iter()
+        .map(|game_id| {
+            let dnp_players = roster
+                .iter()
+                .filter(|(player_id, ..)| !played.contains(&(game_id.clone(), player_id.to_string())))
+                .take(2)
+                .map(|(player_id, player_name, position, season_avg)| crate::models::DnpPlayer {
+                    player_id: *player_id,
+                    player_name: player_name.clone(),
+                    position: position.clone(),
+                    season_avg: *season_avg,
+                })
+                .collect();
+            (game_id.clone(), dnp_players)
         })
         .collect())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::test_support::*;
+
+    const HOME: i64 = 1;
+    const AWAY: i64 = 2;
+
+    /// Seed `minutes` (oldest first) as consecutive daily games for `player_id` on HOME
+    async fn seed_minutes(pool: &SqlitePool, player_id: i64, season: &str, first_day: u32, minutes: &[f64]) {
+        for (i, min) in minutes.iter().enumerate() {
+            let date = format!("2026-01-{:02}", first_day + i as u32);
+            let game_id = format!("{}-{}", season, date);
+            insert_game(pool, &game_id, &date, HOME, AWAY).await;
+            insert_log(pool, player_id, HOME, &game_id, &date, season, Line::new(*min, 10, 4, 2)).await;
+        }
+    }
+
+    #[test]
+    fn normalize_name_strips_precomposed_and_combining_accents() {
+        // "Dončić" precomposed (U+010D, U+0107) vs decomposed (c + U+030C, c + U+0301)
+        assert_eq!(normalize_name("Luka Don\u{10D}i\u{107}"), "Luka Doncic");
+        assert_eq!(normalize_name("Luka Donc\u{30C}ic\u{301}"), "Luka Doncic");
+        assert_eq!(normalize_name("Luka Doncic"), "Luka Doncic");
+    }
+
+    #[test]
+    fn normalize_name_maps_letters_without_a_decomposition() {
+        assert_eq!(normalize_name("Đorđe Đ"), "Dorde D");
+        assert_eq!(normalize_name("Søren Ø"), "Soren O");
+        assert_eq!(normalize_name("Łukasz Kał"), "Lukasz Kal");
+        assert_eq!(normalize_name("Strauß"), "Strauss");
+        assert_eq!(normalize_name("Gilgeous\u{2010}Alexander"), "Gilgeous-Alexander");
+    }
+
+    #[test]
+    fn name_key_matches_accents_on_either_side() {
+        for (stored, scraped) in [
+            ("Nikola Jokić", "Nikola Jokic"),
+            ("Luka Doncic", "Luka Dončić"),
+            ("Alperen Şengün", "alperen sengun"),
+            ("ALPEREN SENGUN", "Alperen Şengün"),
+        ] {
+            assert_eq!(name_key(stored), name_key(scraped), "{} vs {}", stored, scraped);
+        }
+        assert_eq!(name_key("Nikola Jokić"), "nikola jokic");
+        assert_ne!(name_key("Nikola Jokić"), name_key("Nikola Jovic"));
+    }
+
+    #[tokio::test]
+    async fn usage_spikes_rank_recent_minutes_jump_first() {
+        let pool = test_pool().await;
+        insert_team(&pool, HOME, "HOM").await;
+        insert_team(&pool, AWAY, "AWY").await;
+        insert_player(&pool, 10, "Steady Starter", HOME, "G").await;
+        insert_player(&pool, 11, "New Starter", HOME, "F").await;
+        insert_player(&pool, 12, "Small Bump", HOME, "C").await;
+
+        seed_minutes(&pool, 10, SEASON, 1, &[30.0, 30.0, 30.0, 30.0, 31.0, 31.0, 31.0]).await;
+        seed_minutes(&pool, 11, SEASON, 1, &[18.0, 20.0, 22.0, 20.0, 36.0, 35.0, 37.0]).await;
+        seed_minutes(&pool, 12, SEASON, 1, &[24.0, 24.0, 24.0, 24.0, 27.0, 27.0, 27.0]).await;
+        // Heavy minutes last season must not move this season's baseline
+        seed_minutes(&pool, 11, "2024-25", 20, &[40.0, 40.0, 40.0]).await;
+
+        let spikes = get_usage_spikes(&pool, "2026-01-10", SEASON, 10).await.unwrap();
+
+        let ids: Vec<i64> = spikes.iter().map(|s| s.player_id).collect();
+        assert_eq!(ids, vec![11, 12, 10]);
+        let top = &spikes[0];
+        assert_eq!(top.recent_minutes, 36.0);
+        // Baseline is the 4 earlier games only, not the recent 3
+        assert_eq!(top.season_minutes, 20.0);
+        assert_eq!(top.games_played, 7);
+    }
+
+    #[tokio::test]
+    async fn rebounding_counts_only_the_requested_season() {
+        let pool = test_pool().await;
+        insert_team(&pool, HOME, "HOM").await;
+        insert_team(&pool, AWAY, "AWY").await;
+        insert_player(&pool, 30, "Home Big", HOME, "C").await;
+        insert_player(&pool, 31, "Away Big", AWAY, "C").await;
+        let games = [
+            ("r1", "2026-01-01", SEASON, 12, 6),
+            ("r2", "2026-01-03", SEASON, 9, 3),
+            ("r3", "2025-03-01", "2024-25", 30, 20),
+        ];
+        for (game_id, date, season, home_reb, away_reb) in games {
+            insert_game(&pool, game_id, date, HOME, AWAY).await;
+            insert_log(&pool, 30, HOME, game_id, date, season, Line::new(30.0, 10, home_reb, 1)).await;
+            insert_log(&pool, 31, AWAY, game_id, date, season, Line::new(30.0, 10, away_reb, 1)).await;
+        }
+
+        let teams = get_team_rebounds_allowed(&pool, SEASON).await.unwrap();
+        let allowed: Vec<(i64, f32, f32)> = teams.iter().map(|t| (t.team_id, t.reb_allowed, t.oreb_allowed)).collect();
+        assert_eq!(allowed, vec![(HOME, 4.5, 1.5), (AWAY, 10.5, 3.5)]);
+
+        let rebounding = get_player_rebounding(&pool, 30, SEASON).await.unwrap().unwrap();
+        assert_eq!(rebounding.games, 2);
+        assert_eq!(rebounding.reb, 10.5);
+        assert_eq!(rebounding.reb_per_36, Some(12.6));
+
+        assert!(get_player_rebounding(&pool, 30, "2023-24").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn stat_allowed_by_position_is_per_season_and_counts_hybrids() {
+        let pool = test_pool().await;
+        insert_team(&pool, HOME, "HOM").await;
+        insert_team(&pool, AWAY, "AWY").await;
+        insert_player(&pool, 40, "Home Guard", HOME, "G").await;
+        insert_player(&pool, 41, "Home Forward", HOME, "F").await;
+        insert_player(&pool, 42, "Away Wing", AWAY, "G-F").await;
+        insert_player(&pool, 43, "Away Guard", AWAY, "G").await;
+        // (game, date, season, [(player, team, minutes, points)])
+        let games = [
+            ("p1", "2026-01-01", SEASON, [(40, HOME, 30.0, 20), (41, HOME, 30.0, 15), (42, AWAY, 30.0, 10), (43, AWAY, 30.0, 12)]),
+            ("p2", "2026-01-03", SEASON, [(40, HOME, 30.0, 30), (41, HOME, 30.0, 15), (42, AWAY, 0.0, 8), (43, AWAY, 30.0, 14)]),
+            ("p3", "2025-03-01", "2024-25", [(40, HOME, 30.0, 50), (41, HOME, 30.0, 50), (42, AWAY, 30.0, 50), (43, AWAY, 30.0, 50)]),
+        ];
+        for (game_id, date, season, lines) in games {
+            insert_game(&pool, game_id, date, HOME, AWAY).await;
+            for (player_id, team_id, min, pts) in lines {
+                insert_log(&pool, player_id, team_id, game_id, date, season, Line::new(min, pts, 4, 2)).await;
+            }
+        }
+        let allowed = |rows: Vec<TeamStatAllowed>| -> Vec<(i64, f32, i64)> {
+            rows.into_iter().map(|t| (t.team_id, t.allowed, t.games)).collect()
+        };
+
+        // HOME faces the "G" and "G-F" wings; the wing's DNP game doesn't count
+        let guards = get_stat_allowed_by_position(&pool, "points", "G", SEASON).await.unwrap();
+        assert_eq!(allowed(guards), vec![(HOME, 18.0, 2), (AWAY, 25.0, 2)]);
+        let forwards = get_stat_allowed_by_position(&pool, "points", "F", SEASON).await.unwrap();
+        assert_eq!(allowed(forwards), vec![(HOME, 10.0, 1), (AWAY, 15.0, 2)]);
+
+        let last_season = get_stat_allowed_by_position(&pool, "points", "G", "2024-25").await.unwrap();
+        assert_eq!(allowed(last_season), vec![(AWAY, 50.0, 1), (HOME, 100.0, 1)]);
+
+        assert!(get_stat_allowed_by_position(&pool, "bogus", "G", SEASON).await.unwrap().is_empty());
+    }
+
+    fn zone_row(team_id: i64, opp_fgm: f32, opp_fga: f32) -> ZoneDefenseRow {
+        ZoneDefenseRow {
+            team_id,
+            zone_name: "Mid-Range".to_string(),
+            opp_fgm,
+            opp_fga,
+            opp_fg_pct: if opp_fga > 0.0 { opp_fgm / opp_fga * 100.0 } else { 0.0 },
+        }
+    }
+
+    #[test]
+    fn zone_league_avg_discounts_low_sample_outliers() {
+        let (a, b) = (zone_row(1, 4.0, 10.0), zone_row(2, 6.0, 10.0));
+        // 100% on half an attempt a game is below ZONE_AVG_MIN_FGA and left out
+        let tiny = zone_row(3, 0.5, 0.5);
+        assert_eq!(zone_league_avg(&[&a, &b, &tiny]), 50.0);
+
+        // At the minimum it counts, but only by its attempts: 11/21, not the 66.7% plain mean
+        let small = zone_row(3, 1.0, 1.0);
+        let avg = zone_league_avg(&[&a, &b, &small]);
+        assert!((avg - 1100.0 / 21.0).abs() < 1e-4, "{}", avg);
+    }
+
+    #[test]
+    fn zone_league_avg_falls_back_when_no_team_qualifies() {
+        let (a, b) = (zone_row(1, 0.2, 0.5), zone_row(2, 0.3, 0.5));
+        assert_eq!(zone_league_avg(&[&a, &b]), 50.0);
+        assert_eq!(zone_league_avg(&[]), 0.0);
+    }
+
+    /// Player 20 (on HOME): two played games and two DNPs this season, one played game last season
+    async fn seed_trailing_logs(pool: &SqlitePool) {
+        insert_team(pool, HOME, "HOM").await;
+        insert_team(pool, AWAY, "AWY").await;
+        insert_team(pool, 3, "THR").await;
+        insert_player(pool, 20, "Trailing Player", HOME, "G").await;
+        let games = [
+            ("g1", "2026-01-01", AWAY, SEASON, 30.0),
+            ("g2", "2026-01-02", 3, SEASON, 0.0),
+            ("g3", "2026-01-03", AWAY, SEASON, 0.0),
+            ("g4", "2026-01-04", 3, SEASON, 25.0),
+            ("g5", "2025-03-01", AWAY, "2024-25", 28.0),
+        ];
+        for (game_id, date, opponent, season, min) in games {
+            insert_game(pool, game_id, date, HOME, opponent).await;
+            insert_log(pool, 20, HOME, game_id, date, season, Line::new(min, 10, 4, 2)).await;
+        }
+    }
+
+    async fn trailing_ids(pool: &SqlitePool, games: i64, opts: TrailingLogOptions) -> Vec<String> {
+        get_player_trailing_logs(pool, 20, games, &opts)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|log| log.game_id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn props_date_window_covers_todays_props() {
+        let pool = test_pool().await;
+        let today = Today::fixed("2026-01-10");
+        assert_eq!(props_date_window(&today), ["2026-01-10", "2026-01-11", "2026-01-12"]);
+
+        let prop = |line: f64, scheduled_at: &'static str| Prop {
+            player: "Window Player",
+            team: "HOM Team",
+            opponent: "AWY Team",
+            stat: "points",
+            line,
+            choice: "over",
+            price: -110,
+            scheduled_at,
+            updated_at: scheduled_at,
+        };
+        insert_prop(&pool, prop(18.5, "2026-01-09T23:30:00Z")).await;
+        insert_prop(&pool, prop(19.5, "2026-01-10T23:30:00Z")).await;
+        insert_prop(&pool, prop(20.5, "2026-01-12T02:30:00Z")).await;
+
+        let lines: Vec<f64> = get_player_props(&pool, "Window Player", &today)
+            .await
+            .unwrap()
+            .iter()
+            .map(|prop| prop.stat_value)
+            .collect();
+        assert_eq!(lines, [19.5, 20.5]);
+    }
+
+    #[tokio::test]
+    async fn batch_props_resolve_several_players_including_accented_names() {
+        let pool = test_pool().await;
+        insert_team(&pool, HOME, "HOM").await;
+        insert_player(&pool, 30, "Nikola Jokić", HOME, "C").await;
+        insert_player(&pool, 31, "Jayson Tatum", HOME, "F").await;
+        insert_player(&pool, 32, "Propless Player", HOME, "G").await;
+
+        let prop = |player: &'static str, stat: &'static str| Prop {
+            player,
+            team: "HOM Team",
+            opponent: "AWY Team",
+            stat,
+            line: 10.5,
+            choice: "over",
+            price: -110,
+            scheduled_at: "2026-01-10T23:30:00Z",
+            updated_at: "2026-01-10 12:00:00",
+        };
+        // Underdog drops the accent
+        insert_prop(&pool, prop("Nikola Jokic", "rebounds")).await;
+        insert_prop(&pool, prop("Nikola Jokic", "assists")).await;
+        insert_prop(&pool, prop("Jayson Tatum", "points")).await;
+
+        let today = Today::fixed("2026-01-10");
+        let props = get_batch_player_props(&pool, &[30, 31, 32, 999], Some("2026-01-10"), &today)
+            .await
+            .unwrap();
+
+        let mut ids: Vec<i64> = props.keys().copied().collect();
+        ids.sort();
+        assert_eq!(ids, [30, 31]);
+        assert_eq!(props[&30].len(), 2);
+        assert!(props[&30].iter().all(|prop| prop.full_name == "Nikola Jokic"));
+        assert_eq!(props[&31][0].stat_name, "points");
+    }
+
+    #[tokio::test]
+    async fn trailing_logs_filter_combinations() {
+        let pool = test_pool().await;
+        seed_trailing_logs(&pool).await;
+        let season = Some(SEASON.to_string());
+
+        assert_eq!(trailing_ids(&pool, 10, TrailingLogOptions::default()).await, ["g4", "g3", "g2", "g1", "g5"]);
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { exclude_dnp: true, ..Default::default() }).await,
+            ["g4", "g1", "g5"]
+        );
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { opponent_id: Some(AWAY), ..Default::default() }).await,
+            ["g3", "g1", "g5"]
+        );
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { season: season.clone(), ..Default::default() }).await,
+            ["g4", "g3", "g2", "g1"]
+        );
+        assert_eq!(
+            trailing_ids(
+                &pool,
+                10,
+                TrailingLogOptions { exclude_dnp: true, opponent_id: Some(AWAY), ..Default::default() }
+            )
+            .await,
+            ["g1", "g5"]
+        );
+        assert_eq!(
+            trailing_ids(
+                &pool,
+                10,
+                TrailingLogOptions { exclude_dnp: true, opponent_id: Some(AWAY), season, ..Default::default() }
+            )
+            .await,
+            ["g1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn trailing_logs_filter_by_result() {
+        let pool = test_pool().await;
+        seed_trailing_logs(&pool).await;
+        for (game_id, home_score, away_score) in [("g1", 110, 100), ("g2", 95, 101), ("g3", 120, 118), ("g4", 88, 90), ("g5", 104, 99)] {
+            set_score(&pool, game_id, home_score, away_score).await;
+        }
+        // A road win: the result is read from the player's side
+        insert_game(&pool, "g6", "2026-01-05", 3, HOME).await;
+        insert_log(&pool, 20, HOME, "g6", "2026-01-05", SEASON, Line::new(32.0, 10, 4, 2)).await;
+        set_score(&pool, "g6", 99, 105).await;
+        let result = |r: &str| Some(r.to_string());
+
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { result: result("W"), ..Default::default() }).await,
+            ["g6", "g3", "g1", "g5"]
+        );
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { result: result("l"), ..Default::default() }).await,
+            ["g4", "g2"]
+        );
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { result: result("W"), exclude_dnp: true, ..Default::default() }).await,
+            ["g6", "g1", "g5"]
+        );
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { result: result("L"), exclude_dnp: true, ..Default::default() }).await,
+            ["g4"]
+        );
+        assert_eq!(
+            trailing_ids(&pool, 10, TrailingLogOptions { result: result("W"), opponent_id: Some(AWAY), ..Default::default() }).await,
+            ["g3", "g1", "g5"]
+        );
+        assert!(
+            trailing_ids(&pool, 10, TrailingLogOptions { result: result("L"), opponent_id: Some(AWAY), ..Default::default() })
+                .await
+                .is_empty()
+        );
+        assert_eq!(
+            trailing_ids(
+                &pool,
+                10,
+                TrailingLogOptions { result: result("W"), exclude_dnp: true, opponent_id: Some(AWAY), season: result(SEASON) }
+            )
+            .await,
+            ["g1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn zone_ranks_share_a_rank_on_tied_fg_pct() {
+        let pool = test_pool().await;
+        for (team_id, makes) in [(1, 3.0), (2, 4.0), (3, 4.0), (4, 5.0)] {
+            insert_zone_defense(&pool, team_id, "Mid-Range", makes, 10.0).await;
+        }
+        insert_zone_defense(&pool, 1, "Restricted Area", 6.0, 10.0).await;
+        insert_zone_defense(&pool, 2, "Restricted Area", 5.0, 10.0).await;
+
+        let ranks = compute_zone_ranks(&pool).await.unwrap();
+        let rank = |team_id: i64, zone: &str| ranks.get(&(team_id, zone.to_string())).copied();
+
+        assert_eq!(rank(1, "Mid-Range"), Some(1));
+        assert_eq!(rank(2, "Mid-Range"), Some(2));
+        assert_eq!(rank(3, "Mid-Range"), Some(2));
+        assert_eq!(rank(4, "Mid-Range"), Some(4));
+        // Each zone ranks on its own; teams without a row for it get no rank
+        assert_eq!(rank(2, "Restricted Area"), Some(1));
+        assert_eq!(rank(1, "Restricted Area"), Some(2));
+        assert_eq!(rank(3, "Restricted Area"), None);
+    }
+
+    #[tokio::test]
+    async fn zone_strengths_put_best_defended_zone_first() {
+        let pool = test_pool().await;
+        // Team 1 allows 30% at the rim (best in the league there) and 50% from mid-range
+        // (worst); every other zone is in the middle of the pack
+        for (zone_name, _) in SHOOTING_ZONES {
+            let team_1_makes = match zone_name {
+                "Restricted Area" => 3.0,
+                "Mid-Range" => 5.0,
+                _ => 4.0,
+            };
+            insert_zone_defense(&pool, 1, zone_name, team_1_makes, 10.0).await;
+            insert_zone_defense(&pool, 2, zone_name, 3.5, 10.0).await;
+            insert_zone_defense(&pool, 3, zone_name, 4.5, 10.0).await;
+        }
+
+        let strengths = get_team_zone_strengths(&pool, 1).await.unwrap();
+
+        assert_eq!(strengths.len(), SHOOTING_ZONES.len());
+        assert_eq!(strengths[0].zone_name, "Restricted Area");
+        assert_eq!(strengths[0].rank, 1);
+        assert_eq!(strengths.last().unwrap().zone_name, "Mid-Range");
+        assert_eq!(strengths.last().unwrap().rank, 3);
+        assert!(strengths.windows(2).all(|pair| pair[0].rank <= pair[1].rank));
+    }
+
+    #[tokio::test]
+    async fn trailing_logs_filter_before_limit() {
+        let pool = test_pool().await;
+        seed_trailing_logs(&pool).await;
+
+        // The last 2 games played, not the last 2 games with the DNPs dropped afterwards
+        assert_eq!(
+            trailing_ids(&pool, 2, TrailingLogOptions { exclude_dnp: true, ..Default::default() }).await,
+            ["g4", "g1"]
+        );
+    }
+
+    /// A database error carrying an SQLite result code, like the driver reports
+    #[derive(Debug)]
+    struct CodedError(&'static str, &'static str);
+
+    impl std::fmt::Display for CodedError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.1)
+        }
+    }
+
+    impl std::error::Error for CodedError {}
+
+    impl sqlx::error::DatabaseError for CodedError {
+        fn message(&self) -> &str {
+            self.1
+        }
+
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(self.0.into())
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn locked() -> sqlx::Error {
+        sqlx::Error::Database(Box::new(CodedError("5", "database is locked")))
+    }
+
+    /// Run `with_retry` over an op failing with `errors` in turn before succeeding, and
+    /// return its result with the number of attempts made
+    async fn run_with_retry(errors: Vec<fn() -> sqlx::Error>, max_attempts: u32) -> (Result<i32, sqlx::Error>, u32) {
+        let attempts = std::cell::Cell::new(0);
+        let result = with_retry(
+            || {
+                let attempt = attempts.get();
+                attempts.set(attempt + 1);
+                let outcome = errors.get(attempt as usize).map_or(Ok(42), |error| Err(error()));
+                async move { outcome }
+            },
+            max_attempts,
+        )
+        .await;
+        (result, attempts.get())
+    }
+
+    #[test]
+    fn lock_errors_are_recognised_by_code_or_message() {
+        assert!(is_lock_error(&locked()));
+        assert!(is_lock_error(&sqlx::Error::Database(Box::new(CodedError("6", "database table is locked")))));
+        assert!(!is_lock_error(&sqlx::Error::Database(Box::new(CodedError("1", "no such table: teams")))));
+        assert!(!is_lock_error(&sqlx::Error::RowNotFound));
+    }
+
+    #[tokio::test]
+    async fn with_retry_retries_lock_errors_until_success() {
+        let (result, attempts) = run_with_retry(vec![locked, locked], 3).await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn with_retry_gives_up_after_max_attempts() {
+        let (result, attempts) = run_with_retry(vec![locked, locked, locked], 3).await;
+        assert!(result.is_err_and(|e| is_lock_error(&e)));
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn with_retry_returns_other_errors_at_once() {
+        let (result, attempts) = run_with_retry(vec![|| sqlx::Error::RowNotFound], 3).await;
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn with_retry_single_attempt_never_retries() {
+        let (result, attempts) = run_with_retry(vec![locked], 1).await;
+        assert!(result.is_err_and(|e| is_lock_error(&e)));
+        assert_eq!(attempts, 1);
+    }
+}
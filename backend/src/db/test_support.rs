@@ -0,0 +1,251 @@
+//! In-memory database for the DB-backed unit tests: the production schema from
+//! `test_schema.sql` plus helpers that seed the rows most tests need.
+
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+
+/// Season the seeding helpers write unless told otherwise
+pub const SEASON: &str = "2025-26";
+
+/// A fresh in-memory database with every table the API reads. One connection that never
+/// idles out, since each in-memory connection is its own database.
+pub async fn test_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("open in-memory database");
+    sqlx::raw_sql(include_str!("test_schema.sql"))
+        .execute(&pool)
+        .await
+        .expect("create schema");
+    super::ensure_clv_tables(&pool).await.expect("create CLV tables");
+    pool
+}
+
+pub async fn insert_team(pool: &SqlitePool, team_id: i64, abbreviation: &str) {
+    sqlx::query(
+        r#"INSERT INTO teams (team_id, name, full_name, abbreviation, city)
+           VALUES (?, ?, ?, ?, ?)"#
+    )
+    .bind(team_id)
+    .bind(abbreviation)
+    .bind(format!("{} Team", abbreviation))
+    .bind(abbreviation)
+    .bind(abbreviation)
+    .execute(pool)
+    .await
+    .expect("insert team");
+}
+
+pub async fn insert_player(pool: &SqlitePool, player_id: i64, player_name: &str, team_id: i64, position: &str) {
+    sqlx::query(
+        r#"INSERT INTO player_stats (player_id, player_name, season, team_id, position, points, assists, rebounds, games_played)
+           VALUES (?, ?, ?, ?, ?, 20.0, 5.0, 5.0, 10)"#
+    )
+    .bind(player_id)
+    .bind(player_name)
+    .bind(SEASON)
+    .bind(team_id)
+    .bind(position)
+    .execute(pool)
+    .await
+    .expect("insert player");
+}
+
+/// A team's `SEASON` pace and defensive rating; the other team_pace columns are filler
+pub async fn insert_team_pace(pool: &SqlitePool, team_id: i64, pace: f64, def_rating: f64) {
+    sqlx::query(
+        r#"INSERT INTO team_pace (team_id, season, pace, off_rating, def_rating, net_rating, games_played, wins, losses)
+           VALUES (?, ?, ?, 110.0, ?, 110.0 - ?, 10, 5, 5)"#
+    )
+    .bind(team_id)
+    .bind(SEASON)
+    .bind(pace)
+    .bind(def_rating)
+    .bind(def_rating)
+    .execute(pool)
+    .await
+    .expect("insert team pace");
+}
+
+/// A game between two seeded teams, with their names copied from `teams` like the schedule
+/// load does; re-inserting the same game_id is a no-op
+pub async fn insert_game(pool: &SqlitePool, game_id: &str, game_date: &str, home_team_id: i64, away_team_id: i64) {
+    sqlx::query(
+        r#"INSERT OR IGNORE INTO schedule (game_id, game_date, game_time, game_status,
+                                          home_team_id, home_team_name, home_team_abbreviation,
+                                          away_team_id, away_team_name, away_team_abbreviation)
+           SELECT ?, ?, '7:30 PM', 'Final', h.team_id, h.full_name, h.abbreviation, a.team_id, a.full_name, a.abbreviation
+           FROM teams h, teams a
+           WHERE h.team_id = ? AND a.team_id = ?"#
+    )
+    .bind(game_id)
+    .bind(game_date)
+    .bind(home_team_id)
+    .bind(away_team_id)
+    .execute(pool)
+    .await
+    .expect("insert game");
+}
+
+/// Final score for a seeded game, which is what game logs derive W/L and margin from
+pub async fn set_score(pool: &SqlitePool, game_id: &str, home_score: i64, away_score: i64) {
+    sqlx::query(r#"UPDATE schedule SET home_score = ?, away_score = ? WHERE game_id = ?"#)
+        .bind(home_score)
+        .bind(away_score)
+        .bind(game_id)
+        .execute(pool)
+        .await
+        .expect("set score");
+}
+
+/// The box-score numbers a seeded game log carries; everything else is left NULL
+#[derive(Clone, Copy, Default)]
+pub struct Line {
+    pub min: f64,
+    pub pts: i64,
+    pub reb: i64,
+    pub ast: i64,
+}
+
+impl Line {
+    pub fn new(min: f64, pts: i64, reb: i64, ast: i64) -> Self {
+        Line { min, pts, reb, ast }
+    }
+}
+
+/// A player's game log for a game already in `schedule`
+pub async fn insert_log(
+    pool: &SqlitePool,
+    player_id: i64,
+    team_id: i64,
+    game_id: &str,
+    game_date: &str,
+    season: &str,
+    line: Line,
+) {
+    sqlx::query(
+        r#"INSERT INTO player_game_logs (game_id, player_id, team_id, season, game_date, min, pts, reb, ast, oreb, dreb)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+    )
+    .bind(game_id)
+    .bind(player_id.to_string())
+    .bind(team_id)
+    .bind(season)
+    .bind(game_date)
+    .bind(line.min)
+    .bind(line.pts)
+    .bind(line.reb)
+    .bind(line.ast)
+    .bind(line.reb / 3)
+    .bind(line.reb - line.reb / 3)
+    .execute(pool)
+    .await
+    .expect("insert game log");
+}
+
+/// A player's makes/attempts (per game) in one shooting zone
+pub async fn insert_shooting_zone(pool: &SqlitePool, player_id: i64, zone_name: &str, fgm: f64, fga: f64) {
+    sqlx::query(
+        r#"INSERT INTO player_shooting_zones (player_id, season, zone_name, fgm, fga, fg_pct)
+           VALUES (?, ?, ?, ?, ?, ?)"#
+    )
+    .bind(player_id)
+    .bind(SEASON)
+    .bind(zone_name)
+    .bind(fgm)
+    .bind(fga)
+    .bind(fgm / fga * 100.0)
+    .execute(pool)
+    .await
+    .expect("insert shooting zone");
+}
+
+/// A team's opponent makes/attempts (per game) in one shooting zone
+pub async fn insert_zone_defense(pool: &SqlitePool, team_id: i64, zone_name: &str, opp_fgm: f64, opp_fga: f64) {
+    sqlx::query(
+        r#"INSERT INTO team_defensive_zones (team_id, season, zone_name, opp_fgm, opp_fga, opp_fg_pct, last_updated)
+           VALUES (?, ?, ?, ?, ?, ?, '2026-01-01')"#
+    )
+    .bind(team_id)
+    .bind(SEASON)
+    .bind(zone_name)
+    .bind(opp_fgm)
+    .bind(opp_fga)
+    .bind(opp_fgm / opp_fga)
+    .execute(pool)
+    .await
+    .expect("insert zone defense");
+}
+
+/// A player's play-type row; `pct_of_total_points` is a 0-100 share as the scraper writes it,
+/// and the possession and shooting columns are filler
+pub async fn insert_player_play_type(pool: &SqlitePool, player_id: i64, play_type: &str, points_per_game: f64, pct_of_total_points: f64) {
+    sqlx::query(
+        r#"INSERT INTO player_play_types (player_id, season, play_type, points, points_per_game, possessions,
+                                          poss_per_game, ppp, fg_pct, pct_of_total_points, games_played, last_updated)
+           VALUES (?, ?, ?, ? * 10, ?, 40.0, 4.0, 1.0, 0.45, ?, 10, '2026-01-01')"#
+    )
+    .bind(player_id)
+    .bind(SEASON)
+    .bind(play_type)
+    .bind(points_per_game)
+    .bind(points_per_game)
+    .bind(pct_of_total_points)
+    .execute(pool)
+    .await
+    .expect("insert player play type");
+}
+
+/// A team's defense of one play type; only `ppp` matters to the ranks
+pub async fn insert_defensive_play_type(pool: &SqlitePool, team_id: i64, play_type: &str, ppp: f64) {
+    sqlx::query(
+        r#"INSERT INTO team_defensive_play_types (team_id, season, play_type, poss_pct, possessions, poss_per_game,
+                                                  ppp, fg_pct, efg_pct, points, points_per_game, games_played, last_updated)
+           VALUES (?, ?, ?, 0.1, 100.0, 10.0, ?, 0.45, 0.5, 100.0, 10.0, 10, '2026-01-01')"#
+    )
+    .bind(team_id)
+    .bind(SEASON)
+    .bind(play_type)
+    .bind(ppp)
+    .execute(pool)
+    .await
+    .expect("insert defensive play type");
+}
+
+/// One side of an Underdog prop line. `team`/`opponent` are full team names, as scraped.
+pub struct Prop<'a> {
+    pub player: &'a str,
+    pub team: &'a str,
+    pub opponent: &'a str,
+    pub stat: &'a str,
+    pub line: f64,
+    pub choice: &'a str,
+    pub price: i64,
+    pub scheduled_at: &'a str,
+    /// Scrape time; the latest version of a line is the one with the newest
+    pub updated_at: &'a str,
+}
+
+pub async fn insert_prop(pool: &SqlitePool, prop: Prop<'_>) {
+    sqlx::query(
+        r#"INSERT INTO underdog_props (full_name, team_name, opponent_name, stat_name, stat_value, choice,
+                                       american_price, scheduled_at, updated_at, scraped_at)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+    )
+    .bind(prop.player)
+    .bind(prop.team)
+    .bind(prop.opponent)
+    .bind(prop.stat)
+    .bind(prop.line)
+    .bind(prop.choice)
+    .bind(prop.price)
+    .bind(prop.scheduled_at)
+    .bind(prop.updated_at)
+    .bind(prop.updated_at)
+    .execute(pool)
+    .await
+    .expect("insert prop");
+}
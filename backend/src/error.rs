@@ -1,37 +1,48 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::Serialize;
+use utoipa::ToSchema;
 
 #[derive(Debug)]
 pub enum ApiError {
-    NotFound,
+    NotFound(String),
+    BadRequest(String),
     DatabaseError(sqlx::Error),
-    InternalError,
+    /// Missing or invalid API key
+    Unauthorized(String),
+    /// Too many requests; retry after this many seconds
+    RateLimited(u64),
 }
 
-#[derive(Serialize)]
-struct ErrorResponse {
+/// JSON body of every error response
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
     error: String,
     message: String,
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let extra_header = match &self {
+            ApiError::Unauthorized(_) => Some((header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"))),
+            ApiError::RateLimited(retry_after_secs) => Some((header::RETRY_AFTER, HeaderValue::from(*retry_after_secs))),
+            _ => None,
+        };
+
         let (status, error_message) = match self {
-            ApiError::NotFound => (
-                StatusCode::NOT_FOUND,
-                "Resource not found".to_string(),
-            ),
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
             ApiError::DatabaseError(err) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Database error: {}", err),
             ),
-            ApiError::InternalError => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
+            ApiError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message),
+            ApiError::RateLimited(retry_after_secs) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Rate limit exceeded, retry in {}s", retry_after_secs),
             ),
         };
 
@@ -40,7 +51,11 @@ impl IntoResponse for ApiError {
             message: error_message,
         });
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let Some((name, value)) = extra_header {
+            response.headers_mut().insert(name, value);
+        }
+        response
     }
 }
 
@@ -49,3 +64,24 @@ impl From<sqlx::Error> for ApiError {
         ApiError::DatabaseError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_rate_limited_carries_retry_after() {
+        let response = ApiError::RateLimited(7).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "7");
+
+        let response = ApiError::Unauthorized("missing key".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+
+        let response = ApiError::NotFound("player 1 not found".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    }
+}
@@ -0,0 +1,179 @@
+//! ETags and conditional GETs for the nightly-refreshed player, team and zone endpoints.
+//! A successful response is tagged with a hash of its body, so the tag changes whenever
+//! the data behind it does (including each row's `last_updated`); a request whose
+//! `If-None-Match` already holds that tag gets an empty 304 instead of the payload.
+//!
+//! The hash covers the uncompressed body, so gzip and identity responses share a tag,
+//! which is why it's a weak ETag. It's stable for a given build, not across Rust versions.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::hash::{DefaultHasher, Hasher};
+
+/// Path prefixes whose GET responses get ETags
+const ETAG_PREFIXES: [&str; 3] = ["/api/players", "/api/teams", "/api/zones"];
+
+fn is_cacheable(method: &Method, path: &str) -> bool {
+    (method == Method::GET || method == Method::HEAD)
+        && ETAG_PREFIXES
+            .iter()
+            .any(|prefix| path == *prefix || path.starts_with(&format!("{}/", prefix)))
+}
+
+/// Weak ETag for a response body
+fn etag_for(body: &[u8]) -> HeaderValue {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(body);
+    HeaderValue::from_str(&format!("W/\"{:016x}\"", hasher.finish())).expect("hex is a valid header value")
+}
+
+/// Whether `If-None-Match` lists `etag` (or is `*`), comparing weakly as RFC 9110 asks
+fn none_match(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let strip_weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let Ok(etag) = etag.to_str().map(strip_weak) else {
+        return false;
+    };
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|tag| tag.trim() == "*" || strip_weak(tag) == etag)
+}
+
+/// Middleware adding an ETag to successful responses on cacheable paths and answering
+/// 304 Not Modified when the client's copy is current
+pub async fn etag(req: Request, next: Next) -> Response {
+    if !is_cacheable(req.method(), req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let request_headers = req.headers().clone();
+    let response = next.run(req).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Failed to buffer response body for ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = etag_for(&bytes);
+    if none_match(&request_headers, &etag) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        not_modified.headers_mut().insert(header::ETAG, etag);
+        return not_modified;
+    }
+
+    parts.headers.insert(header::ETAG, etag);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        extract::State,
+        routing::get,
+        Router,
+    };
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+    use tower::ServiceExt;
+
+    /// Cacheable and non-cacheable routes whose body is the shared `version`
+    fn app(version: Arc<AtomicU32>) -> Router {
+        let body = |State(version): State<Arc<AtomicU32>>| async move {
+            format!("{{\"version\":{}}}", version.load(Ordering::SeqCst))
+        };
+        Router::new()
+            .route("/api/players/1", get(body).post(body))
+            .route("/api/teams/99", get(|| async { (StatusCode::NOT_FOUND, "no such team") }))
+            .route("/api/screener/top-picks", get(body))
+            .with_state(version)
+            .layer(axum::middleware::from_fn(etag))
+    }
+
+    async fn send(app: &Router, method: Method, uri: &str, if_none_match: Option<&str>) -> Response {
+        let mut req = Request::builder().method(method).uri(uri);
+        if let Some(tags) = if_none_match {
+            req = req.header(header::IF_NONE_MATCH, tags);
+        }
+        app.clone().oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    fn etag_of(response: &Response) -> String {
+        response.headers()[header::ETAG].to_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn matching_if_none_match_gets_304() {
+        let app = app(Arc::new(AtomicU32::new(1)));
+        let first = send(&app, Method::GET, "/api/players/1", None).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        let tag = etag_of(&first);
+        assert!(tag.starts_with("W/\""));
+
+        let strong = tag.trim_start_matches("W/").to_string();
+        let listed = format!("\"other\", {}", tag);
+        for header in [tag.as_str(), strong.as_str(), listed.as_str(), "*"] {
+            let response = send(&app, Method::GET, "/api/players/1", Some(header)).await;
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "If-None-Match: {}", header);
+            assert_eq!(etag_of(&response), tag);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert!(body.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn changed_body_gets_200_with_a_new_etag() {
+        let version = Arc::new(AtomicU32::new(1));
+        let app = app(version.clone());
+        let old_tag = etag_of(&send(&app, Method::GET, "/api/players/1", None).await);
+
+        version.store(2, Ordering::SeqCst);
+        let response = send(&app, Method::GET, "/api/players/1", Some(&old_tag)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(etag_of(&response), old_tag);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"{\"version\":2}");
+    }
+
+    #[tokio::test]
+    async fn other_requests_pass_through_untouched() {
+        let app = app(Arc::new(AtomicU32::new(1)));
+
+        let post = send(&app, Method::POST, "/api/players/1", Some("*")).await;
+        assert_eq!(post.status(), StatusCode::OK);
+        assert!(!post.headers().contains_key(header::ETAG));
+
+        let missing = send(&app, Method::GET, "/api/teams/99", Some("*")).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        assert!(!missing.headers().contains_key(header::ETAG));
+
+        let screener = send(&app, Method::GET, "/api/screener/top-picks", Some("*")).await;
+        assert_eq!(screener.status(), StatusCode::OK);
+        assert!(!screener.headers().contains_key(header::ETAG));
+    }
+
+    #[test]
+    fn cacheable_paths_match_whole_segments() {
+        assert!(is_cacheable(&Method::GET, "/api/players"));
+        assert!(is_cacheable(&Method::HEAD, "/api/zones/league-averages"));
+        assert!(!is_cacheable(&Method::GET, "/api/playersearch"));
+        assert!(!is_cacheable(&Method::DELETE, "/api/teams/1"));
+    }
+}
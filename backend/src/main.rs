@@ -1,45 +1,160 @@
-use sqlx::sqlite::SqlitePool;
-use axum::{routing::get, Router};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use axum::{http::{HeaderName, HeaderValue}, routing::{get, post}, Router};
 use std::net::{Ipv4Addr, SocketAddr};
-use tower_http::cors::{CorsLayer, Any};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::compression::{predicate::{DefaultPredicate, Predicate, SizeAbove}, CompressionLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::trace::TraceLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
+mod auth;
+mod cache;
 mod routes;
 mod models;
 mod db;
 mod error;
+mod etag;
+mod metrics;
+mod openapi;
+mod query_stats;
+mod rate_limit;
+mod request_id;
+mod state;
+mod util;
 
 #[tokio::main]
 async fn main() {
-    // Initialize tracing/logging
+    dotenvy::dotenv().ok();
+
+    // DEV_MODE reports per-request query counts/timings as response headers
+    let dev_mode = std::env::var("DEV_MODE").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+
+    // Initialize tracing/logging. The log filter applies to the fmt layer only, so the
+    // query stats layer still sees sqlx statement events whatever RUST_LOG is.
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info".into()),
+            tracing_subscriber::fmt::layer().with_filter(
+                tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| "info".into()),
+            ),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(dev_mode.then_some(query_stats::QueryStatsLayer))
+        .with(metrics::DbMetricsLayer)
         .init();
 
     tracing::info!("Starting api server...");
     
-    dotenvy::dotenv().ok();
-    
     // Create database connection pool
     let db_url = std::env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set in .env");
     
-    let pool = SqlitePool::connect(&db_url)
+    // Pool sizing and timeouts; each is overridable via env (see .env.example)
+    let env_u64 = |name: &str, default: u64| -> u64 {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    let max_connections = env_u64("DB_MAX_CONNECTIONS", 10) as u32;
+    let acquire_timeout = Duration::from_secs(env_u64("DB_ACQUIRE_TIMEOUT_SECS", 10));
+    let idle_timeout = Duration::from_secs(env_u64("DB_IDLE_TIMEOUT_SECS", 600));
+    let busy_timeout = Duration::from_millis(env_u64("DB_BUSY_TIMEOUT_MS", 5000));
+
+    // WAL lets reads proceed while the nightly loader writes; the busy timeout makes
+    // writers wait for a lock instead of failing with "database is locked"
+    let use_wal = std::env::var("DB_WAL").map_or(true, |v| v != "0" && !v.eq_ignore_ascii_case("false"));
+    let connect_options: SqliteConnectOptions = db_url
+        .parse::<SqliteConnectOptions>()
+        .expect("DATABASE_URL is not a valid SQLite URL")
+        .busy_timeout(busy_timeout);
+    let write_options = if use_wal {
+        connect_options.clone().journal_mode(SqliteJournalMode::Wal)
+    } else {
+        connect_options.clone()
+    };
+
+    // Serve queries from a read-only pool so handlers can't mutate the scraper's data.
+    // The few writes the API does make (indexes, CLV snapshots) go through a separate
+    // single-connection writer. DB_READ_ONLY=0 puts everything on one read-write pool.
+    let read_only = std::env::var("DB_READ_ONLY").map_or(true, |v| v != "0" && !v.eq_ignore_ascii_case("false"));
+
+    let writer = SqlitePoolOptions::new()
+        .max_connections(if read_only { 1 } else { max_connections })
+        .acquire_timeout(acquire_timeout)
+        .idle_timeout(idle_timeout)
+        .connect_with(write_options)
         .await
         .expect("Failed to connect to database");
+
+    let pool = if read_only {
+        // The writer persists the journal mode in the file, so readers leave it alone
+        let read_options = connect_options.read_only(true);
+        SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .acquire_timeout(acquire_timeout)
+            .idle_timeout(idle_timeout)
+            .connect_with(read_options)
+            .await
+            .expect("Failed to open read-only database pool")
+    } else {
+        writer.clone()
+    };
     
-    tracing::info!("Database connection established.");
+    tracing::info!(
+        "Database connection established (max {} connections, WAL {}).",
+        max_connections,
+        if use_wal { "on" } else { "off" }
+    );
+    if read_only {
+        tracing::info!("Database pool opened read-only; writes use a single-connection writer pool.");
+    } else {
+        tracing::warn!("Database pool opened read-write (DB_READ_ONLY is off).");
+    }
 
     // Ensure indexes for fast top-picks queries
-    db::ensure_top_picks_indexes(&pool)
+    db::ensure_top_picks_indexes(&writer)
         .await
         .expect("Failed to create top-picks indexes");
 
+    db::ensure_clv_tables(&writer)
+        .await
+        .expect("Failed to create CLV tracking table");
+
+    // Periodically capture closing lines for flagged screener picks (0 disables)
+    let clv_interval_secs: u64 = std::env::var("CLV_CAPTURE_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(300);
+    if clv_interval_secs > 0 {
+        let clv_pool = writer.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(clv_interval_secs));
+            loop {
+                ticker.tick().await;
+                match routes::line_shopping::capture_closing_lines(&clv_pool).await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!("Captured closing lines for {} flagged picks", n),
+                    Err(e) => tracing::warn!("Failed to capture closing lines: {}", e),
+                }
+            }
+        });
+    } else {
+        tracing::warn!("CLV_CAPTURE_INTERVAL_SECS=0 disables closing-line capture, so CLV reports won't fill in");
+    }
+
+    // Re-warm the screener and slate caches whenever fresh props data lands (0 disables).
+    // Each rebuilt pick set is also snapshotted through the writer for CLV tracking.
+    let warm_interval_secs: u64 = std::env::var("CACHE_WARM_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+    if warm_interval_secs > 0 {
+        tokio::spawn(cache::run_cache_warmer(pool.clone(), writer.clone(), Duration::from_secs(warm_interval_secs)));
+    } else {
+        tracing::warn!("CACHE_WARM_INTERVAL_SECS=0 disables the cache warmer, so no screener picks are snapshotted for CLV tracking");
+    }
+
     let host: Ipv4Addr = std::env::var("HOST")
         .expect("HOST is set in .env")
         .parse()
@@ -52,51 +167,193 @@ async fn main() {
 
     let addr = SocketAddr::from((host, port));
 
-    // CORS configuration for NextJS frontend
+    // CORS configuration for NextJS frontend. ALLOWED_ORIGINS is a comma-separated list
+    // of origins, or "*" for any; unset means any so local development keeps working.
+    let allowed_origins = std::env::var("ALLOWED_ORIGINS").unwrap_or_else(|_| "*".to_string());
+    let allow_origin = if allowed_origins.trim() == "*" {
+        tracing::warn!("CORS allows any origin; set ALLOWED_ORIGINS to restrict it in production");
+        AllowOrigin::any()
+    } else {
+        let origins: Vec<HeaderValue> = allowed_origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(|origin| origin.parse().expect("ALLOWED_ORIGINS contains an invalid origin"))
+            .collect();
+        tracing::info!("CORS allowed origins: {}", allowed_origins);
+        AllowOrigin::list(origins)
+    };
+
     let cors = CorsLayer::new()
-        .allow_origin(Any)  // In production, use specific origins
+        .allow_origin(allow_origin)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([
+            request_id::X_REQUEST_ID,
+            axum::http::header::ETAG,
+            HeaderName::from_static(cache::X_CACHE),
+            axum::http::header::RETRY_AFTER,
+        ]);
+
+    // Compress responses (gzip, br or deflate per Accept-Encoding) once they're big enough
+    // to be worth it. Responses that already carry a Content-Encoding are left alone.
+    let compression_min_bytes: u16 = std::env::var("COMPRESSION_MIN_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1024);
+    let compression = CompressionLayer::new()
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(compression_min_bytes)));
+
+    // Per-IP request budget (0 disables). Behind a proxy (Cloud Run) set
+    // RATE_LIMIT_TRUST_FORWARDED so the client IP is the last X-Forwarded-For entry.
+    let rate_limit_per_minute: u32 = std::env::var("RATE_LIMIT_PER_MINUTE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(300);
+    let trust_forwarded = std::env::var("RATE_LIMIT_TRUST_FORWARDED")
+        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+
+    // Matchup responses are cached in memory; the underlying data refreshes daily
+    let matchup_cache_ttl_secs: u64 = std::env::var("MATCHUP_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(15 * 60);
+    let state = state::AppState {
+        pool,
+        matchup_cache: Arc::new(cache::MatchupCache::new(Duration::from_secs(matchup_cache_ttl_secs))),
+    };
 
     let app = Router::new()
         // Root and health
         .route("/", get(|| async { "NBA Stats API - v1.0" }))
         .route("/health", get(routes::health::health_check))
+        .route("/health/live", get(routes::health::liveness))
+        .route("/health/ready", get(routes::health::readiness))
+
+        // API documentation
+        .route("/api/openapi.json", get(openapi::openapi_json))
+        .route("/docs", get(openapi::swagger_ui))
 
         // Player endpoints
         .route("/api/players", get(routes::players::get_players))
         .route("/api/players/{id}", get(routes::players::get_player_by_id))
+        .route("/api/players/batch", get(routes::players::get_players_batch))
         .route("/api/players/search", get(routes::players::search_players))
+        .route("/api/players/autocomplete", get(routes::players::autocomplete_players))
+        .route("/api/players/usage-spikes", get(routes::players::get_usage_spikes))
         .route("/api/players/{id}/shooting-zones", get(routes::players::get_player_shooting_zones))
         .route("/api/players/{id}/assist-zones", get(routes::players::get_player_assist_zones))
         .route("/api/players/{id}/play-types", get(routes::players::get_player_play_types))
         .route("/api/players/{id}/game-logs", get(routes::players::get_player_game_logs))
+        .route("/api/players/{id}/game-logs.csv", get(routes::players::get_player_game_logs_csv))
+        .route("/api/players/{id}/averages", get(routes::players::get_player_recent_averages))
+        .route("/api/players/{id}/boom-bust", get(routes::players::get_player_boom_bust))
+        .route("/api/players/{id}/fantasy-consistency", get(routes::players::get_player_fantasy_consistency))
+        .route("/api/players/{id}/consistency", get(routes::players::get_player_consistency))
+        .route("/api/players/{id}/adjusted", get(routes::players::get_player_adjusted_stats))
+        .route("/api/players/{id}/percentiles", get(routes::players::get_player_percentiles))
+        .route("/api/players/{id}/data-coverage", get(routes::players::get_player_data_coverage))
+        .route("/api/players/{id}/splits/home-away", get(routes::players::get_player_home_away_splits))
+        .route("/api/players/{id}/advanced", get(routes::players::get_player_advanced))
         .route("/api/players/{id}/props", get(routes::props::get_player_props))
+        .route("/api/players/{id}/props/history", get(routes::props::get_player_prop_history))
+        .route("/api/players/{id}/best-odds", get(routes::line_shopping::get_best_odds))
         .route("/api/players/{id}/play-type-matchup", get(routes::players::get_player_play_type_matchup))
+        .route("/api/players/{id}/attack-point", get(routes::players::get_player_attack_point))
+        .route("/api/players/{id}/matchup-grade", get(routes::players::get_player_matchup_grade))
+        .route("/api/players/{id}/rebound-matchup", get(routes::players::get_player_rebound_matchup))
         .route("/api/players/{id}/assist-zone-matchup", get(routes::players::get_player_assist_zone_matchup))
         .route("/api/players/{player_id}/shooting-zones/vs/{opponent_id}", get(routes::players::get_player_shooting_zone_matchup))
+        .route("/api/players/{id}/vs/{opponent_id}/history", get(routes::players::get_player_opponent_history))
         .route("/api/players/{id}/upcoming-matchup", get(routes::players::get_upcoming_matchup_context))
+        .route("/api/players/{id}/upcoming-matchup/all", get(routes::players::get_upcoming_matchup_context_all))
+        .route("/api/players/{id}/projection", get(routes::players::get_player_projection))
 
         // Team endpoints
+        .route("/api/injuries", get(routes::injuries::get_injuries))
         .route("/api/teams", get(routes::teams::get_teams))
         .route("/api/teams/search", get(routes::teams::search_team))
+        .route("/api/teams/rankings", get(routes::teams::get_team_rankings))
         .route("/api/teams/{id}", get(routes::teams::get_team_by_id))
         .route("/api/teams/{id}/stats", get(routes::teams::get_team_stats))
+        .route("/api/teams/{id}/allowed", get(routes::teams::get_team_allowed_by_position))
+        .route("/api/teams/{id}/scoring-defense", get(routes::teams::get_team_scoring_defense))
+        .route("/api/teams/{id}/projected-points", get(routes::teams::get_team_projected_points))
         .route("/api/teams/{id}/defensive-zones", get(routes::zones::get_team_defensive_zones))
+        .route("/api/teams/{id}/defensive-zones/ranks", get(routes::zones::get_team_defensive_zone_ranks))
+        .route("/api/teams/{id}/zone-strengths", get(routes::zones::get_team_zone_strengths))
+        .route("/api/zones/league-averages", get(routes::zones::get_zone_league_averages))
         .route("/api/teams/{id}/defensive-play-types", get(routes::play_types::get_team_defensive_play_types))
+        .route("/api/teams/{id}/defensive-play-types/ranks", get(routes::play_types::get_team_defensive_play_type_ranks))
+        .route("/api/standings", get(routes::teams::get_standings))
 
         // Screener endpoints
         .route("/api/screener/top-picks", get(routes::line_shopping::get_top_picks))
+        .route("/api/screener/clv-report", get(routes::line_shopping::get_clv_report))
+        .route("/api/screener/parlay", post(routes::line_shopping::compute_parlay_ev))
+        .route("/api/screener/arbs", get(routes::line_shopping::get_arbs))
+
+        // Props endpoints
+        .route("/api/props/batch", post(routes::props::get_batch_props))
+        .route("/api/props/stat-types", get(routes::props::get_stat_types))
+        .route("/api/props/players", get(routes::props::get_prop_players))
+
+        // Live updates
+        .route("/api/ws/props", get(routes::ws::props_socket))
+
+        // Slate endpoints
+        .route("/api/slate/props-by-game", get(routes::slate::get_props_by_game))
 
         // Schedule endpoints
         .route("/api/schedule", get(routes::schedule::get_schedule))
         .route("/api/schedule/today", get(routes::schedule::get_todays_games))
         .route("/api/schedule/upcoming", get(routes::schedule::get_upcoming_games))
         .route("/api/schedule/upcoming/rosters", get(routes::schedule::get_upcoming_rosters))
+        .route("/api/schedule/{game_id}/context", get(routes::schedule::get_game_context))
+        .route("/api/teams/{abbr}/schedule/context", get(routes::schedule::get_team_schedule_context))
 
+        // Per-route request metrics; /metrics itself is added after so it isn't counted
+        .route_layer(axum::middleware::from_fn(metrics::track_metrics))
+        .route("/metrics", get(metrics::metrics_handler))
+
+        // Inside compression, so the ETag hashes the uncompressed body
+        .layer(axum::middleware::from_fn(etag::etag))
+        .layer(compression)
+        .with_state(state);
+
+    // API keys for the screener and projection endpoints; unset leaves them open
+    let app = match std::env::var("API_KEYS").ok().as_deref().and_then(auth::ApiKeys::from_list) {
+        Some(keys) => {
+            tracing::info!("Requiring one of {} API keys for screener and projection endpoints", keys.len());
+            app.layer(axum::middleware::from_fn_with_state(Arc::new(keys), auth::require_api_key))
+        }
+        None => app,
+    };
+
+    let app = if rate_limit_per_minute > 0 {
+        tracing::info!("Rate limiting to {} requests/minute per IP", rate_limit_per_minute);
+        let limiter = Arc::new(rate_limit::RateLimiter::new(rate_limit_per_minute, trust_forwarded));
+        app.layer(axum::middleware::from_fn_with_state(limiter, rate_limit::rate_limit))
+    } else {
+        app
+    };
+
+    // CORS wraps the API key check and rate limiter, so 401s and 429s carry the CORS
+    // headers (and browsers can read Retry-After), and preflights never need a key
+    let app = app
         .layer(cors)
-        .layer(TraceLayer::new_for_http())
-        .with_state(pool);
+        .layer(TraceLayer::new_for_http().make_span_with(request_id::make_span));
+
+    let app = if dev_mode {
+        tracing::info!("DEV_MODE: reporting X-Query-Count and X-DB-Time-Ms headers, honoring X-Debug-Date");
+        app.layer(axum::middleware::from_fn(query_stats::query_stats_headers))
+            .layer(axum::middleware::from_fn(util::today::debug_date))
+    } else {
+        app
+    };
+
+    // Outermost, so the trace layer sees the ID and rate-limited responses carry it too
+    let app = app.layer(axum::middleware::from_fn(request_id::request_id));
 
     let listener= tokio::net::TcpListener::bind(addr)
         .await
@@ -104,7 +361,7 @@ async fn main() {
 
     tracing::info!("Server listening on {}", addr);
 
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
     .await
     .expect("Failed to start server.");
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+//! Prometheus metrics served at `/metrics` in the text exposition format.
+//!
+//! `track_metrics` runs as a route layer, so every request is labelled with its matched
+//! route pattern (`/api/players/{id}`, not the raw path) and counted by status and
+//! latency. `DbMetricsLayer` times every SQL statement from the `sqlx::query` tracing
+//! events, the same events `query_stats` uses for the DEV_MODE headers.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use crate::query_stats::ElapsedVisitor;
+
+/// Request latency buckets in seconds
+const REQUEST_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// SQL statement latency buckets in seconds
+const QUERY_BUCKETS: [f64; 11] = [0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+/// Cumulative histogram over fixed bucket bounds
+#[derive(Debug)]
+struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Histogram { bounds, counts: vec![0; bounds.len()], sum: 0.0, count: 0 }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Append `name_bucket`/`name_sum`/`name_count` lines, with `labels` ("" or `k="v",...`)
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, sep, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{{}{}le=\"+Inf\"}} {}", name, labels, sep, self.count);
+        let braced = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, braced, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, braced, self.count);
+    }
+}
+
+/// Per (method, route) request stats
+#[derive(Debug)]
+struct RouteMetrics {
+    by_status: BTreeMap<u16, u64>,
+    latency: Histogram,
+}
+
+#[derive(Debug)]
+struct Registry {
+    routes: BTreeMap<(String, String), RouteMetrics>,
+    queries: Histogram,
+}
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(|| {
+    Mutex::new(Registry { routes: BTreeMap::new(), queries: Histogram::new(&QUERY_BUCKETS) })
+});
+
+fn record_request(method: &str, route: &str, status: u16, elapsed_secs: f64) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let route_metrics = registry
+        .routes
+        .entry((method.to_string(), route.to_string()))
+        .or_insert_with(|| RouteMetrics {
+            by_status: BTreeMap::new(),
+            latency: Histogram::new(&REQUEST_BUCKETS),
+        });
+    *route_metrics.by_status.entry(status).or_default() += 1;
+    route_metrics.latency.observe(elapsed_secs);
+}
+
+fn record_query(elapsed_secs: f64) {
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner()).queries.observe(elapsed_secs);
+}
+
+/// Escape a label value per the exposition format
+fn label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Render every metric in the Prometheus text format
+fn render() -> String {
+    let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = String::new();
+
+    out.push_str("# HELP http_requests_total HTTP requests by route and status code.\n");
+    out.push_str("# TYPE http_requests_total counter\n");
+    for ((method, route), metrics) in &registry.routes {
+        for (status, count) in &metrics.by_status {
+            let _ = writeln!(
+                out,
+                "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                method, label_value(route), status, count
+            );
+        }
+    }
+
+    out.push_str("# HELP http_request_errors_total HTTP responses with a 4xx or 5xx status by route.\n");
+    out.push_str("# TYPE http_request_errors_total counter\n");
+    for ((method, route), metrics) in &registry.routes {
+        for (class, range) in [("4xx", 400..500), ("5xx", 500..600)] {
+            let count: u64 = metrics
+                .by_status
+                .iter()
+                .filter(|(status, _)| range.contains(*status))
+                .map(|(_, count)| count)
+                .sum();
+            if count > 0 {
+                let _ = writeln!(
+                    out,
+                    "http_request_errors_total{{method=\"{}\",route=\"{}\",class=\"{}\"}} {}",
+                    method, label_value(route), class, count
+                );
+            }
+        }
+    }
+
+    out.push_str("# HELP http_request_duration_seconds HTTP request latency by route.\n");
+    out.push_str("# TYPE http_request_duration_seconds histogram\n");
+    for ((method, route), metrics) in &registry.routes {
+        let labels = format!("method=\"{}\",route=\"{}\"", method, label_value(route));
+        metrics.latency.render(&mut out, "http_request_duration_seconds", &labels);
+    }
+
+    out.push_str("# HELP db_query_duration_seconds SQL statement execution time.\n");
+    out.push_str("# TYPE db_query_duration_seconds histogram\n");
+    registry.queries.render(&mut out, "db_query_duration_seconds", "");
+
+    out
+}
+
+/// Route-layer middleware recording each request's status and latency under its
+/// matched route pattern
+pub async fn track_metrics(req: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+
+    record_request(&method, &route, response.status().as_u16(), started.elapsed().as_secs_f64());
+    response
+}
+
+// GET /metrics - Prometheus scrape endpoint
+pub async fn metrics_handler() -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        render(),
+    )
+        .into_response()
+}
+
+/// Tracing layer feeding every sqlx statement's execution time into
+/// `db_query_duration_seconds`
+pub struct DbMetricsLayer;
+
+impl<S: Subscriber> Layer<S> for DbMetricsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != "sqlx::query" {
+            return;
+        }
+        let mut elapsed = ElapsedVisitor(None);
+        event.record(&mut elapsed);
+        if let Some(secs) = elapsed.0 {
+            record_query(secs);
+        }
+    }
+}
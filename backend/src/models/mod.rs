@@ -1,7 +1,9 @@
 use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::util::game_status::GameStatus;
 
 /// Player roster info for sidebar display
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RosterPlayer {
     pub player_id: i64,
@@ -21,6 +23,7 @@ pub struct RosterPlayerRow {
     pub injury_status: Option<String>,
     pub injury_description: Option<String>,
     pub has_props: bool,
+    pub last_updated: Option<String>,
 }
 
 impl RosterPlayerRow {
@@ -37,7 +40,7 @@ impl RosterPlayerRow {
 }
 
 /// Game with player rosters for sidebar
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GameWithRosters {
     pub game_id: String,
@@ -51,15 +54,17 @@ pub struct GameWithRosters {
 }
 
 /// Response wrapper for roster endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RosterResponse {
     pub games: Vec<GameWithRosters>,
     pub count: usize,
+    /// Latest `last_updated` among the games and rostered players (ISO 8601 UTC)
+    pub data_as_of: Option<String>,
 }
 
 /// Team info from teams table
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct Team {
     pub team_id: i64,
     pub name: String,
@@ -71,20 +76,32 @@ pub struct Team {
     pub last_updated: Option<String>,
 }
 
+/// Team plus its conference and division, filled in from a static map, and its logo
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TeamWithDivision {
+    #[serde(flatten)]
+    pub team: Team,
+    pub conference: Option<String>,
+    pub division: Option<String>,
+    pub logo_url: String,
+}
+
 
 /// Game info for API responses
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleGame {
     pub game_id: String,
     pub game_date: String,
     pub game_time: String,
     pub game_status: String,
+    /// `game_status` normalized to scheduled, live or final
+    pub status_category: GameStatus,
     pub home_team: TeamInfo,
     pub away_team: TeamInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TeamInfo {
     pub id: i64,
@@ -93,12 +110,52 @@ pub struct TeamInfo {
     pub city: String,
 }
 
+/// A player on the latest injury report, with their team
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct InjuredPlayer {
+    pub player_id: i64,
+    pub player_name: String,
+    pub team_id: Option<i64>,
+    pub team_abbreviation: Option<String>,
+    pub team_name: Option<String>,
+    pub injury_status: String,
+    pub injury_description: Option<String>,
+    pub collection_date: String,
+}
+
 /// Response wrapper for schedule endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleResponse {
     pub games: Vec<ScheduleGame>,
     pub count: usize,
+    /// Latest `last_updated` among the games (ISO 8601 UTC)
+    pub data_as_of: Option<String>,
+}
+
+/// A team's game with rest context. Rest days are full days off since the side's
+/// previous game (0 on a back-to-back, None for its first game on the schedule).
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleGameContext {
+    #[serde(flatten)]
+    pub game: ScheduleGame,
+    pub is_home: bool,
+    pub opponent: TeamInfo,
+    pub rest_days: Option<i64>,
+    pub back_to_back: bool,
+    pub opponent_rest_days: Option<i64>,
+    pub opponent_back_to_back: bool,
+}
+
+/// Response for /api/teams/:abbr/schedule/context
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamScheduleContext {
+    pub team_abbreviation: String,
+    pub games: Vec<ScheduleGameContext>,
+    pub count: usize,
 }
 
 /// Schedule row from SQLite database
@@ -116,7 +173,7 @@ pub struct ScheduleRow {
     pub away_team_name: Option<String>,
     pub away_team_abbreviation: Option<String>,
     pub away_team_city: Option<String>,
-    // pub last_updated: Option<String>,
+    pub last_updated: Option<String>,
 }
 
 impl ScheduleRow {
@@ -127,6 +184,7 @@ impl ScheduleRow {
             game_date: self.game_date.clone(),
             game_time: self.game_time.clone().unwrap_or_else(|| "TBD".to_string()),
             game_status: self.game_status.clone().unwrap_or_default(),
+            status_category: GameStatus::from_raw(self.game_status.as_deref().unwrap_or_default()),
             home_team: TeamInfo {
                 id: self.home_team_id,
                 name: self.home_team_name.clone().unwrap_or_default(),
@@ -143,7 +201,7 @@ impl ScheduleRow {
     }
 }
 
-#[derive(Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, ToSchema, Clone, sqlx::FromRow)]
 pub struct PlayerStats {
     pub player_id: i64,
     pub player_name: String,
@@ -175,7 +233,105 @@ pub struct PlayerStats {
     pub last_updated: String
 }
 
-#[derive(Serialize, Deserialize, sqlx::FromRow)]
+impl PlayerStats {
+    /// API shape with shooting efficiency. TS% comes from the season averages; FG% and
+    /// eFG% need made field goals, which `player_stats` doesn't store, so they come from
+    /// the season's game-log totals in `shooting`. Zero attempts give None, not NaN.
+    pub fn to_response(&self, shooting: Option<&ShootingTotals>) -> PlayerResponse {
+        let round = |pct: f64| (pct * 1000.0).round() / 1000.0;
+        let fga = self.fg_attempted.unwrap_or(0.0) as f64;
+        let ts_attempts = 2.0 * (fga + 0.44 * self.ft_attempted as f64);
+        let ts_pct = (ts_attempts > 0.0).then(|| round(self.points as f64 / ts_attempts));
+
+        let (fg_pct, efg_pct) = match shooting.filter(|totals| totals.fga > 0) {
+            Some(totals) => {
+                let fga = totals.fga as f64;
+                (
+                    Some(round(totals.fgm as f64 / fga)),
+                    Some(round((totals.fgm as f64 + 0.5 * totals.fg3m as f64) / fga)),
+                )
+            }
+            None => (None, None),
+        };
+
+        PlayerResponse {
+            stats: self.clone(),
+            fg_pct,
+            ts_pct,
+            efg_pct,
+            headshot_url: crate::util::image_urls::headshot_url(self.player_id),
+        }
+    }
+
+    /// Season average for an internal stat column (e.g. `pts_plus_ast`), or None for
+    /// names that aren't per-game averages
+    pub fn season_avg(&self, column: &str) -> Option<f32> {
+        match column {
+            "points" => Some(self.points),
+            "assists" => Some(self.assists),
+            "rebounds" => Some(self.rebounds),
+            "threes_made" => Some(self.threes_made),
+            "steals" => Some(self.steals),
+            "blocks" => Some(self.blocks),
+            "turnovers" => Some(self.turnovers),
+            "pts_plus_ast" => Some(self.pts_plus_ast),
+            "pts_plus_reb" => Some(self.pts_plus_reb),
+            "ast_plus_reb" => Some(self.ast_plus_reb),
+            "pts_plus_ast_plus_reb" => Some(self.pts_plus_ast_plus_reb),
+            "steals_plus_blocks" => Some(self.steals_plus_blocks),
+            "q1_points" => self.q1_points,
+            "q1_assists" => self.q1_assists,
+            "q1_rebounds" => self.q1_rebounds,
+            "first_half_points" => self.first_half_points,
+            _ => None,
+        }
+    }
+}
+
+/// Season made/attempted field goal totals from the game logs
+#[derive(Debug, sqlx::FromRow)]
+pub struct ShootingTotals {
+    pub player_id: i64,
+    pub fgm: i64,
+    pub fga: i64,
+    pub fg3m: i64,
+}
+
+/// Player season stats plus shooting efficiency, as fractions rounded to 3 decimals
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PlayerResponse {
+    #[serde(flatten)]
+    pub stats: PlayerStats,
+    /// FGM / FGA
+    pub fg_pct: Option<f64>,
+    /// PTS / (2 * (FGA + 0.44 * FTA))
+    pub ts_pct: Option<f64>,
+    /// (FGM + 0.5 * 3PM) / FGA
+    pub efg_pct: Option<f64>,
+    pub headshot_url: String,
+}
+
+/// One page of the player list plus the total count for pagination controls
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerPage {
+    pub players: Vec<PlayerResponse>,
+    pub total: i64,
+    pub limit: Option<i64>,
+    pub offset: i64,
+    /// Latest `last_updated` among the page's players (ISO 8601 UTC)
+    pub data_as_of: Option<String>,
+}
+
+/// Players for a list of ids, in the requested order, plus the ids with no stats row
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerBatch {
+    pub players: Vec<PlayerResponse>,
+    pub missing: Vec<i64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct PlayerShootingZones {
     pub player_id: i64,
     pub season: String,
@@ -187,7 +343,7 @@ pub struct PlayerShootingZones {
     pub last_updated: String
 }
 
-#[derive(Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct PlayerAssistZones {
     pub player_id: i64,
     pub season: String,
@@ -201,18 +357,18 @@ pub struct PlayerAssistZones {
     pub last_updated: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AssistZoneMatchup {
     pub zone_name: String,
     pub player_assists: i64,
     pub player_ast_pct: f32,
-    pub opp_def_rank: i32,
+    pub opp_def_rank: Option<i32>,  // None when the opponent has no data for the zone
     pub opp_def_fg_pct: f32,
     pub has_data: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AssistZoneMatchupResponse {
     pub player_name: String,
@@ -221,7 +377,7 @@ pub struct AssistZoneMatchupResponse {
     pub zones: Vec<AssistZoneMatchup>,
 }
 
-#[derive(Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct PlayerPlayTypes {
     pub player_id: i64,
     pub season: String,
@@ -237,7 +393,7 @@ pub struct PlayerPlayTypes {
     pub last_updated: String
 }
 
-#[derive(Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct TeamDefensiveZones {
     pub team_id: i64,
     pub season: String,
@@ -249,8 +405,62 @@ pub struct TeamDefensiveZones {
     pub last_updated: String
 }
 
+// One team's opponent FG% in one zone, used for league averages and ranks
+#[derive(Debug, sqlx::FromRow)]
+pub struct ZoneDefenseRow {
+    pub team_id: i64,
+    pub zone_name: String,
+    pub opp_fgm: f32,              // Per game
+    pub opp_fga: f32,              // Per game
+    pub opp_fg_pct: f32,
+}
+
+// One team's opponent FG% in a zone, for league-average extremes
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneTeamDefense {
+    pub team_id: i64,
+    pub abbreviation: Option<String>,
+    pub opp_fg_pct: f32,
+}
+
+// League baseline for one shooting zone
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneLeagueAverage {
+    pub zone_name: String,
+    pub is_three: bool,
+    pub league_avg_pct: f32,       // FGA-weighted, see db::zone_league_avg
+    pub teams: i64,
+    pub best_defense: Option<ZoneTeamDefense>,
+    pub worst_defense: Option<ZoneTeamDefense>,
+}
+
+// A team's defense in one zone relative to the league
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneStrength {
+    pub zone_name: String,
+    pub opp_fg_pct: f32,
+    pub league_avg_pct: f32,
+    pub rank: i32,                // 1 = best defense (lowest opp FG%)
+    pub is_three: bool,
+}
+
+// A team's league rank defending one shooting zone; None without data for the zone
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneRank {
+    pub zone_name: String,
+    pub is_three: bool,
+    pub opp_fg_pct: Option<f32>,
+    pub rank: Option<i32>,         // 1 = best defense (lowest opp FG%)
+    pub teams_ranked: i64,
+    pub league_avg_pct: f32,       // FGA-weighted, see db::zone_league_avg
+}
+
 // Shooting zone matchup with league context
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ShootingZoneMatchup {
     pub zone_name: String,
@@ -259,14 +469,14 @@ pub struct ShootingZoneMatchup {
     pub player_fg_pct: f32,       // Player's FG% (already as percentage, e.g., 38.5)
     pub player_volume_pct: f32,   // % of player's total FGA from this zone
     pub opp_fg_pct: f32,          // Opponent allows (as percentage)
-    pub opp_rank: i32,            // Opponent rank 1-30 (1 = best defense)
+    pub opp_rank: Option<i32>,    // Opponent rank 1-30 (1 = best defense), None without data
     pub league_avg_pct: f32,      // League average FG% for this zone
     pub advantage: f32,           // League-adjusted advantage
     pub is_three: bool,           // Is this a 3-point zone
     pub has_data: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ShootingZoneMatchupResponse {
     pub player_name: String,
@@ -278,7 +488,7 @@ pub struct ShootingZoneMatchupResponse {
 }
 
 //
-#[derive(Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct TeamDefensivePlayTypes {
     pub team_id: i64,
     pub season: String,
@@ -295,8 +505,18 @@ pub struct TeamDefensivePlayTypes {
     pub last_updated: String,
 }
 
+// A team's league rank defending one play type (1 = lowest PPP allowed)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayTypeRank {
+    pub play_type: String,
+    pub ppp: f32,
+    pub rank: i32,
+    pub teams_ranked: i32,         // Teams with data for the play type
+}
+
 // DNP (Did Not Play) player info
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DnpPlayer {
     pub player_id: i64,
@@ -306,7 +526,7 @@ pub struct DnpPlayer {
 }
 
 // Player game log for individual game stats
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerGameLog {
     pub game_id: String,
@@ -334,17 +554,438 @@ pub struct PlayerGameLog {
     pub dreb: Option<i32>,
 }
 
+/// Column order of the game log CSV export. Only ever append columns so scripts
+/// reading by position keep working.
+pub const GAME_LOG_CSV_COLUMNS: [&str; 23] = [
+    "game_id", "player_id", "team_id", "season", "game_date", "matchup", "wl", "min",
+    "pts", "reb", "ast", "stl", "blk", "fgm", "fga", "fg3m", "fg3a", "ftm", "fta",
+    "tov", "game_margin", "oreb", "dreb",
+];
+
+/// One CSV cell: empty for None, quoted when the text holds a comma, quote or newline
+fn csv_cell<T: std::fmt::Display>(value: Option<T>) -> String {
+    let Some(value) = value else {
+        return String::new();
+    };
+    let text = value.to_string();
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+impl PlayerGameLog {
+    /// This game as a CSV line in `GAME_LOG_CSV_COLUMNS` order, without the newline
+    pub fn csv_row(&self) -> String {
+        [
+            csv_cell(Some(&self.game_id)),
+            csv_cell(Some(&self.player_id)),
+            csv_cell(self.team_id),
+            csv_cell(self.season.as_ref()),
+            csv_cell(self.game_date.as_ref()),
+            csv_cell(self.matchup.as_ref()),
+            csv_cell(self.wl.as_ref()),
+            csv_cell(self.min),
+            csv_cell(self.pts),
+            csv_cell(self.reb),
+            csv_cell(self.ast),
+            csv_cell(self.stl),
+            csv_cell(self.blk),
+            csv_cell(self.fgm),
+            csv_cell(self.fga),
+            csv_cell(self.fg3m),
+            csv_cell(self.fg3a),
+            csv_cell(self.ftm),
+            csv_cell(self.fta),
+            csv_cell(self.tov),
+            csv_cell(self.game_margin),
+            csv_cell(self.oreb),
+            csv_cell(self.dreb),
+        ]
+        .join(",")
+    }
+
+    /// Value of an Underdog stat (e.g. `pts_rebs_asts`) for this game, summing the
+    /// component columns for combo stats. Returns None for unmapped stats or missing columns.
+    pub fn underdog_stat(&self, stat_name: &str) -> Option<i32> {
+        match stat_name {
+            "points" => self.pts,
+            "rebounds" => self.reb,
+            "assists" => self.ast,
+            "steals" => self.stl,
+            "blocks" => self.blk,
+            "turnovers" => self.tov,
+            "three_points_made" => self.fg3m,
+            "free_throws_made" => self.ftm,
+            "pts_rebs_asts" => Some(self.pts? + self.reb? + self.ast?),
+            "pts_asts" => Some(self.pts? + self.ast?),
+            "pts_rebs" => Some(self.pts? + self.reb?),
+            "rebs_asts" => Some(self.reb? + self.ast?),
+            "blks_stls" => Some(self.blk? + self.stl?),
+            _ => None,
+        }
+    }
+
+    /// Fantasy points for this game under `scoring`. Returns None if any counting stat
+    /// is missing.
+    pub fn fantasy_points(&self, scoring: FantasyScoring) -> Option<f32> {
+        let (pts, reb, ast, stl, blk, tov, fg3m) = (
+            self.pts? as f32, self.reb? as f32, self.ast? as f32, self.stl? as f32,
+            self.blk? as f32, self.tov? as f32, self.fg3m? as f32,
+        );
+
+        match scoring {
+            FantasyScoring::DraftKings => {
+                // Double-double / triple-double bonuses count pts, reb, ast, stl and blk
+                let doubles = [pts, reb, ast, stl, blk].iter().filter(|&&v| v >= 10.0).count();
+                let bonus = match doubles {
+                    0 | 1 => 0.0,
+                    2 => 1.5,
+                    _ => 1.5 + 3.0,
+                };
+                Some(pts + 0.5 * fg3m + 1.25 * reb + 1.5 * ast + 2.0 * stl + 2.0 * blk - 0.5 * tov + bonus)
+            }
+            FantasyScoring::FanDuel => {
+                Some(pts + 1.2 * reb + 1.5 * ast + 3.0 * stl + 3.0 * blk - tov)
+            }
+        }
+    }
+}
+
+/// DFS fantasy scoring rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FantasyScoring {
+    DraftKings,
+    FanDuel,
+}
+
+impl FantasyScoring {
+    /// Parse a scoring query value ("dk" or "fd")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "dk" | "draftkings" => Some(Self::DraftKings),
+            "fd" | "fanduel" => Some(Self::FanDuel),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DraftKings => "dk",
+            Self::FanDuel => "fd",
+        }
+    }
+}
+
+/// Fantasy points for one game, scaled by the opponent's defense
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FantasyGame {
+    pub game_id: String,
+    pub game_date: Option<String>,
+    pub opponent_id: Option<i64>,
+    pub fantasy_points: f32,
+    pub opp_factor: f32,           // Opponent DefRtg / league average (>1 = weak defense)
+    pub adjusted_points: f32,      // fantasy_points / opp_factor
+}
+
+/// How steady a player's fantasy output is over a trailing window of this season's played games.
+/// `consistency` is 100 * (1 - coefficient of variation) of the opponent-adjusted points,
+/// floored at 0, so higher means more reliable.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FantasyConsistency {
+    pub player_name: String,
+    pub scoring: String,
+    pub games: i64,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub adjusted_mean: f32,
+    pub adjusted_std_dev: f32,
+    pub consistency: f32,
+    pub game_log: Vec<FantasyGame>,
+}
+
+/// Where a player's season average for one stat falls among qualified players (0-100,
+/// ties count half). Higher is always "more", so a high turnovers percentile is bad.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatPercentile {
+    pub stat: String,
+    pub value: f32,
+    pub percentile: Option<f32>,   // None when no player qualifies
+}
+
+/// League percentile ranks of a player's season averages
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerPercentiles {
+    pub player_id: i64,
+    pub player_name: String,
+    pub games_played: i64,
+    pub min_games: i64,
+    pub qualified: bool,           // Player meets the games floor themselves
+    pub pool_size: i64,            // Qualified players compared against
+    pub percentiles: Vec<StatPercentile>,
+}
+
+/// Spread of one stat over a trailing window of played games. `consistency_score` is
+/// 100 * (1 - coefficient of variation), floored at 0, so higher means a steadier floor.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatConsistency {
+    pub mean: f32,
+    pub std_dev: f32,
+    pub coefficient_of_variation: Option<f32>, // None when the mean is 0
+    pub min: i32,
+    pub max: i32,
+    pub consistency_score: f32,
+}
+
+/// Points, rebounds and assists volatility over a player's recent played games
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerConsistency {
+    pub player_name: String,
+    pub games: i64,
+    pub points: StatConsistency,
+    pub rebounds: StatConsistency,
+    pub assists: StatConsistency,
+}
+
+/// One played game with the opponent's defensive rating, for schedule-strength adjustment
+#[derive(Debug, sqlx::FromRow)]
+pub struct GameVsDefense {
+    pub pts: i32,
+    pub opp_def_rating: Option<f32>,
+}
+
+/// A player's scoring average raw and adjusted for the defenses faced. Each game's points
+/// are scaled by league-average DefRtg / opponent DefRtg, so points against a stingy
+/// defense count for more; games against an unrated opponent are left unadjusted.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerAdjustedStats {
+    pub player_id: i64,
+    pub player_name: String,
+    pub season: String,
+    pub games: i64,
+    pub games_with_rating: i64,    // Games whose opponent has a DefRtg
+    pub raw_points: f32,
+    pub adjusted_points: f32,
+    pub avg_opp_def_rating: Option<f32>,
+    pub league_avg_def_rating: Option<f32>,
+}
+
+// Over/under record against a line across a trailing window of played games
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct HitRate {
+    pub hits: i64,
+    pub games: i64,
+    pub pct: f32,
+}
+
+// Boom/bust split against a line. A boom finishes at or above `line + margin`, a bust at or
+// below `line - margin`, and everything in between is neutral
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BoomBust {
+    pub stat: String,
+    pub line: f64,
+    pub margin: f64,
+    pub games: i64,
+    pub booms: i64,
+    pub busts: i64,
+    pub boom_pct: f32,
+    pub bust_pct: f32,
+    pub neutral_pct: f32,
+}
+
+// Minimal player entry for search-box autocomplete
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct PlayerSuggestion {
+    pub player_id: i64,
+    pub player_name: String,
+    pub team_abbreviation: Option<String>,
+}
+
+// Which datasets exist for a player, to explain sections missing from the player page
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct DataCoverage {
+    pub player_id: i64,
+    pub player_name: Option<String>,
+    pub season_stats: bool,
+    pub shooting_zones: i64,       // Zone rows
+    pub assist_zones: i64,
+    pub play_types: i64,
+    pub game_logs: i64,
+    pub played_games: i64,         // Game logs with minutes (excludes DNPs)
+    #[sqlx(skip)]
+    pub has_props: bool,           // Upcoming Underdog props
+}
+
+// Per-game averages over a subset of a player's games
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitLine {
+    pub min: f32,
+    pub pts: f32,
+    pub reb: f32,
+    pub ast: f32,
+    pub stl: f32,
+    pub blk: f32,
+    pub tov: f32,
+    pub fg3m: f32,
+    pub fga: f32,
+    pub fta: f32,
+}
+
+impl SplitLine {
+    /// Average the given games; games with no minutes logged are skipped
+    pub fn from_logs(logs: &[PlayerGameLog]) -> (SplitLine, i64) {
+        let played: Vec<&PlayerGameLog> = logs.iter().filter(|g| g.min.is_some_and(|m| m > 0.0)).collect();
+        if played.is_empty() {
+            return (SplitLine::default(), 0);
+        }
+
+        let n = played.len() as f32;
+        let avg = |f: fn(&PlayerGameLog) -> Option<i32>| {
+            played.iter().filter_map(|g| f(g)).sum::<i32>() as f32 / n
+        };
+
+        let line = SplitLine {
+            min: played.iter().filter_map(|g| g.min).sum::<f32>() / n,
+            pts: avg(|g| g.pts),
+            reb: avg(|g| g.reb),
+            ast: avg(|g| g.ast),
+            stl: avg(|g| g.stl),
+            blk: avg(|g| g.blk),
+            tov: avg(|g| g.tov),
+            fg3m: avg(|g| g.fg3m),
+            fga: avg(|g| g.fga),
+            fta: avg(|g| g.fta),
+        };
+
+        (line, played.len() as i64)
+    }
+}
+
+// Row from the home/away split aggregation
+#[derive(Debug, sqlx::FromRow)]
+pub struct SplitRow {
+    pub is_home: bool,
+    pub games: i64,
+    #[sqlx(flatten)]
+    pub line: SplitLine,
+}
+
+// Per-36 production and a usage proxy. Rate stats are None when the player's minutes
+// per game are below the endpoint's threshold (or unknown)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerAdvanced {
+    pub player_id: i64,
+    pub player_name: String,
+    pub minutes_per_game: Option<f64>,
+    pub pts_per36: Option<f64>,
+    pub reb_per36: Option<f64>,
+    pub ast_per36: Option<f64>,
+    pub stl_per36: Option<f64>,
+    pub blk_per36: Option<f64>,
+    /// FGA + 0.44 * FTA + TOV per game (possessions used)
+    pub usage_proxy: f64,
+    pub usage_per36: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HomeAwaySplits {
+    pub home: SplitLine,
+    pub away: SplitLine,
+    pub games_home: i64,
+    pub games_away: i64,
+}
+
+// Every game a player has logged against one opponent, plus averages
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OpponentHistory {
+    pub player_name: String,
+    pub opponent_name: String,
+    pub season: String,
+    pub games_played: i64,
+    pub averages: SplitLine,
+    pub games: Vec<PlayerGameLog>,
+}
+
+// Player whose recent minutes are running above their season average
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSpike {
+    pub player_id: i64,
+    pub player_name: String,
+    pub team_id: Option<i64>,
+    pub recent_minutes: f32,   // Average minutes over the last 3 games
+    pub season_minutes: f32,   // Average minutes over the season's earlier games
+    pub minutes_delta: f32,
+    pub games_played: i64,
+    pub last_game_date: String,
+}
+
+// Trailing averages up to and including a game (None until the player has played a game)
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingAverages {
+    pub pts_l5: Option<f32>,
+    pub reb_l5: Option<f32>,
+    pub ast_l5: Option<f32>,
+    pub pts_l10: Option<f32>,
+    pub reb_l10: Option<f32>,
+    pub ast_l10: Option<f32>,
+}
+
 // Game log with DNP players included
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GameLogWithDnp {
     #[serde(flatten)]
     pub game_log: PlayerGameLog,
     pub dnp_players: Vec<DnpPlayer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rolling: Option<RollingAverages>,
+}
+
+/// Active run of consecutive played games (newest backwards) at or above a threshold
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Streak {
+    pub stat: String,              // Underdog stat name (points, rebounds, ...)
+    pub threshold: i32,
+    pub games: i64,
+    pub label: String,             // e.g. "20+ points in 5 straight games"
+}
+
+/// Game logs plus the player's active streaks (`include_streaks=true`)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameLogsWithStreaks {
+    pub logs: Vec<GameLogWithDnp>,
+    pub streaks: Vec<Streak>,
+}
+
+/// Plain game logs by default, or wrapped with active streaks with `include_streaks=true`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum GameLogsResult {
+    Logs(Vec<GameLogWithDnp>),
+    WithStreaks(GameLogsWithStreaks),
 }
 
 // Underdog prop line from database
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct UnderdogProp {
     pub id: i64,
@@ -359,18 +1000,41 @@ pub struct UnderdogProp {
     pub scheduled_at: Option<String>,
 }
 
+// One recorded version of an underdog prop line, for line-movement history
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PropLineSnapshot {
+    pub updated_at: String,
+    pub stat_value: f64,
+    pub american_price: Option<i64>,
+    pub choice: String,
+}
+
+/// A player with upcoming props, for discovering who is on the slate
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PropPlayer {
+    pub player_id: i64,
+    pub player_name: String,
+    pub team: Option<String>,      // Team abbreviation
+    pub prop_count: i64,           // Distinct (stat, line) props
+}
+
 // Response for player props endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerPropsResponse {
     pub player_name: String,
     pub opponent_id: Option<i64>,
     pub opponent_name: Option<String>,
+    /// Latest injury report status (Out, Questionable, ...), None if not listed
+    pub injury_status: Option<String>,
+    pub injury_description: Option<String>,
     pub props: Vec<PropLine>,
 }
 
 // Grouped prop line (over/under combined)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PropLine {
     pub stat_name: String,
@@ -379,10 +1043,60 @@ pub struct PropLine {
     pub under_odds: Option<i64>,
     pub opponent: Option<String>,
     pub scheduled_at: Option<String>,
+    pub hit_rate_last_10: Option<HitRate>,
+    pub hit_rate_last_20: Option<HitRate>,
+    pub season_avg: Option<f32>,
+    pub line_vs_avg_pct: Option<f32>, // (line - season_avg) / season_avg * 100
+}
+
+// Message pushed to /api/ws/props clients
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PropsSocketMessage {
+    // A subscribed player's current lines; empty once they have no upcoming props
+    #[serde(rename_all = "camelCase")]
+    Props {
+        player_id: i64,
+        updated_at: Option<String>,
+        props: Vec<PropLine>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+// One player's prop lines within a slate game
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SlatePlayerProps {
+    pub player_name: String,
+    pub team_name: Option<String>,
+    pub props: Vec<PropLine>,
+}
+
+// A game on the slate with every player's props
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SlateGameProps {
+    pub game_id: String,
+    pub game_date: String,
+    pub game_time: String,
+    pub home_team: TeamInfo,
+    pub away_team: TeamInfo,
+    pub players: Vec<SlatePlayerProps>,
+}
+
+// Response for /api/slate/props-by-game
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SlatePropsResponse {
+    pub date: String,
+    pub games: Vec<SlateGameProps>,
+    pub count: usize,
 }
 
 // Play type matchup analysis
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayTypeMatchup {
     pub play_type: String,
@@ -392,7 +1106,7 @@ pub struct PlayTypeMatchup {
     pub opp_rank: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayTypeMatchupResponse {
     pub player_name: String,
@@ -400,6 +1114,73 @@ pub struct PlayTypeMatchupResponse {
     pub matchups: Vec<PlayTypeMatchup>,
 }
 
+/// A player's top play type against one opponent's defense of it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttackPoint {
+    pub player_name: String,
+    pub opponent_name: String,
+    pub play_type: String,
+    pub pct_of_total: f32,         // Share of the player's points, 0-100
+    pub player_ppg: f32,
+    pub opp_ppp: Option<f32>,      // None when the opponent has no data for the play type
+    pub opp_rank: Option<i32>,     // 1 = best defense; None when the opponent has no data
+    pub teams_ranked: i32,         // Teams with data for the play type
+    pub league_avg_ppp: Option<f32>,
+    pub verdict: String,           // "attack", "neutral", "avoid" or "no_data"
+}
+
+/// One shooting zone or play type feeding a matchup grade. `edge` runs from -1 (the
+/// opponent's best defense) to +1 (its worst); `impact` is `weight * edge`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchupFactor {
+    pub kind: String,              // "zone" or "play_type"
+    pub name: String,
+    pub weight: f32,               // Share of the player's FGA (zones) or points (play types), 0-1
+    pub opp_rank: i32,             // 1 = best defense
+    pub teams_ranked: i32,         // Teams with data for this zone or play type
+    pub edge: f32,
+    pub impact: f32,
+}
+
+/// Letter grade for a player's matchup against one opponent's defense
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchupGrade {
+    pub player_name: String,
+    pub opponent_name: String,
+    pub grade: String,             // "A+" through "F"
+    pub score: f32,                // 0-100, 50 = neutral
+    pub zone_score: Option<f32>,   // Same scale over shooting zones alone
+    pub play_type_score: Option<f32>,
+    pub favorable: Vec<MatchupFactor>,
+    pub unfavorable: Vec<MatchupFactor>,
+}
+
+/// League-wide pace and rating averages from team_pace
+#[derive(Debug, sqlx::FromRow)]
+pub struct LeagueTeamAverages {
+    pub pace: Option<f32>,
+    pub off_rating: Option<f32>,
+    pub def_rating: Option<f32>,
+}
+
+/// Projected stat line: season average scaled by opponent pace, defense and matchup ranks
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Projection {
+    pub player_name: String,
+    pub opponent_name: String,
+    pub stat_type: String,
+    pub base: f32,                 // Season average
+    pub pace_factor: f32,          // Opponent pace / league average
+    pub defense_factor: f32,       // Opponent DefRtg / league average
+    pub matchup_factor: f32,       // From the dominant zone / play type ranks
+    pub matchup_ranks: Vec<i32>,   // Ranks behind matchup_factor (1 = best defense)
+    pub projected: f32,
+}
+
 // ── Top Picks (Underdog vs Sharp Books) ──
 
 /// Raw row: one per sharp-book × Underdog line match
@@ -422,17 +1203,62 @@ pub struct TopPickRow {
 }
 
 /// One sharp book's line + odds for the expanded view
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SharpBookLine {
     pub sportsbook: String,
     pub line: f64,
     pub over_odds: Option<i32>,
     pub under_odds: Option<i32>,
+    /// Devigged percentages; None unless both sides are quoted
+    pub over_fair_prob: Option<f64>,
+    pub under_fair_prob: Option<f64>,
+}
+
+/// A single side's price at one book
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BookPrice {
+    pub sportsbook: String,
+    pub odds: i32,
+}
+
+/// Best over and under prices across sharp books at a player's Underdog line
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BestOdds {
+    pub player_name: String,
+    pub stat: String,
+    pub ud_line: f64,
+    pub ud_odds: Option<i32>,
+    pub best_over: Option<BookPrice>,
+    pub best_under: Option<BookPrice>,
+    /// Every book quoting the Underdog line
+    pub books: Vec<SharpBookLine>,
+}
+
+/// How a top pick's edge was derived, so the number can be audited in the UI.
+/// Probabilities are percentages for the pick's direction, rounded to 1 decimal.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PickExplanation {
+    pub ud_line: f64,
+    /// Underdog odds used for the implied prob (-110 when Underdog shows none)
+    pub ud_odds_used: i32,
+    pub ud_implied_prob: f64,
+    pub book: String,
+    pub book_line: f64,
+    pub book_over_odds: i32,
+    pub book_under_odds: i32,
+    pub devig_method: String,
+    pub fair_prob: f64,
+    pub edge_pct: f64,
+    /// e.g. "pinnacle OVER 24.5: fair 55.2% - Underdog implied 52.4% = 2.8% edge"
+    pub summary: String,
 }
 
 /// Computed top pick for the API response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TopPick {
     pub player_name: String,
@@ -448,20 +1274,162 @@ pub struct TopPick {
     pub home_team: String,
     pub away_team: String,
     pub game_date: String,
+    pub game_time: Option<String>,
     pub injury_status: Option<String>,
     pub injury_description: Option<String>,
+    pub explanation: PickExplanation,
 }
 
 /// Top-level response for /api/screener/top-picks
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TopPicksResponse {
     pub picks: Vec<TopPick>,
+    pub total_qualifying: usize,    // Picks with an edge before the limit was applied
     pub last_updated: Option<String>,
 }
 
+/// Top picks for one game, for the `group_by=game` slate view
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameTopPicks {
+    pub home_team: String,
+    pub away_team: String,
+    pub game_date: String,
+    pub game_time: Option<String>,
+    pub picks: Vec<TopPick>,
+}
+
+/// /api/screener/top-picks response with `group_by=game`: games ordered by their
+/// best pick, picks within a game by edge
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TopPicksByGameResponse {
+    pub games: Vec<GameTopPicks>,
+    pub total_qualifying: usize,
+    pub last_updated: Option<String>,
+}
+
+/// Flat top picks by default, or nested by game with `group_by=game`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum TopPicksResult {
+    Flat(TopPicksResponse),
+    ByGame(TopPicksByGameResponse),
+}
+
+/// Two sharp books disagreeing enough at the same line that backing the over at one and
+/// the under at the other locks in a profit. Probabilities and returns are percentages.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SharpArb {
+    pub player_name: String,
+    pub stat_type: String,
+    pub line: f64,
+    pub over_book: String,
+    pub over_odds: i32,
+    pub under_book: String,
+    pub under_odds: i32,
+    /// Sum of both sides' implied probabilities (under 100 for an arb)
+    pub combined_implied_prob: f64,
+    /// Guaranteed return on the total stake when it is split to pay out equally
+    pub return_pct: f64,
+    pub home_team: String,
+    pub away_team: String,
+    pub game_date: String,
+    pub game_time: Option<String>,
+}
+
+/// One priced leg of a parlay. `fair_prob` is the consensus devigged percentage for the
+/// leg's side across `books`, or None when no sharp book quotes the line.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParlayLegResult {
+    pub player_id: i64,
+    pub player_name: Option<String>,
+    pub stat_name: String,
+    pub line: f64,
+    pub choice: String,
+    pub has_sharp_line: bool,
+    pub fair_prob: Option<f64>,
+    pub books: Vec<String>,
+}
+
+/// Parlay EV from independent leg fair probabilities. Probabilities and EV are
+/// percentages; EV is per unit staked at `payout_multiplier`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParlayEv {
+    pub legs: Vec<ParlayLegResult>,
+    /// False when some legs had no sharp line and were left out of the product
+    pub complete: bool,
+    pub legs_priced: usize,
+    pub payout_multiplier: f64,
+    /// Combined probability needed to break even at the payout
+    pub breakeven_prob: f64,
+    pub combined_fair_prob: Option<f64>,
+    pub ev_pct: Option<f64>,
+}
+
+/// Flagged top pick as recorded at flag time, plus the closing quote once captured.
+/// Probabilities are stored as percentages, matching `TopPick`.
+#[derive(Debug, sqlx::FromRow)]
+pub struct ClvSnapshotRow {
+    pub id: i64,
+    pub player_name: String,
+    pub stat_type: String,
+    pub direction: String,
+    pub game_date: String,
+    pub game_time: Option<String>,
+    pub best_book: String,
+    pub open_line: f64,
+    pub open_ud_odds: Option<i32>,
+    pub open_implied_prob: f64,
+    pub open_fair_prob: f64,
+    pub flagged_at: String,
+    pub close_line: Option<f64>,
+    pub close_ud_odds: Option<i32>,
+    pub close_fair_prob: Option<f64>,
+    pub closed_at: Option<String>,
+}
+
+/// One flagged pick in the CLV report
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClvPick {
+    pub player_name: String,
+    pub stat_type: String,
+    pub direction: String,
+    pub game_date: String,
+    pub best_book: String,
+    pub flagged_at: String,
+    pub open_line: f64,
+    pub open_ud_odds: Option<i32>,
+    pub open_implied_prob: f64,
+    pub open_fair_prob: f64,
+    pub closed_at: Option<String>,
+    pub close_line: Option<f64>,
+    pub close_ud_odds: Option<i32>,
+    pub close_fair_prob: Option<f64>,
+    pub clv_pct: Option<f64>,      // Closing fair prob at the flagged line minus the implied prob we were offered
+    pub line_move: Option<f64>,    // Positive = line moved in our favour
+    pub is_final: bool,            // Game has tipped, so the close is locked in
+}
+
+/// Response for /api/screener/clv-report
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClvReport {
+    pub picks_flagged: usize,
+    pub picks_closed: usize,
+    pub avg_clv_pct: Option<f64>,
+    pub beat_close_pct: Option<f64>,
+    pub avg_line_move: Option<f64>,
+    pub picks: Vec<ClvPick>,
+}
+
 /// Team pace and rating stats
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct TeamStats {
     pub team_id: i64,
@@ -475,8 +1443,163 @@ pub struct TeamStats {
     pub losses: Option<i64>,
 }
 
+/// A team's place in a league leaderboard for one metric (rank 1 = best)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamRanking {
+    pub rank: i32,
+    pub team_name: String,
+    pub abbreviation: String,
+    pub metric: String,
+    pub value: f32,
+    #[serde(flatten)]
+    pub stats: TeamStats,
+}
+
+/// Projected points for a team against one opponent, with the inputs used
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedPoints {
+    pub team_id: i64,
+    pub team_name: String,
+    pub opponent_id: i64,
+    pub opponent_name: String,
+    pub team_pace: f32,
+    pub opp_pace: f32,
+    pub league_avg_pace: f32,
+    pub expected_pace: f32,        // Possessions per team in this matchup
+    pub team_off_rating: f32,
+    pub opp_def_rating: f32,
+    pub league_avg_rating: f32,
+    pub expected_off_rating: f32,  // Points per 100 possessions in this matchup
+    pub projected_points: f32,
+}
+
+/// Pace-based game environment for one scheduled game: each side's projected points
+/// (see `ProjectedPoints`) and the total and spread they imply. No home-court adjustment.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameContext {
+    pub game: ScheduleGame,
+    pub expected_possessions: f32, // Per team; the same for both sides
+    pub home: ProjectedPoints,
+    pub away: ProjectedPoints,
+    pub projected_total: f32,
+    pub projected_spread: f32,     // Home line, sportsbook style: negative when home is favored
+}
+
+/// A team's row in the standings (rank 1 = best record)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Standing {
+    pub rank: i32,
+    pub team_id: i64,
+    pub team_name: String,
+    pub abbreviation: String,
+    pub conference: Option<String>,
+    pub wins: i64,
+    pub losses: i64,
+    pub win_pct: f32,
+    pub games_back: f32,           // Behind the league or conference leader
+}
+
+/// Average rebounds a team allows per game
+#[derive(Debug, sqlx::FromRow)]
+pub struct TeamReboundsAllowed {
+    pub team_id: i64,
+    pub reb_allowed: f32,
+    pub oreb_allowed: f32,
+    pub dreb_allowed: f32,
+}
+
+/// Average points a team allows per game, with its team_pace pace and defensive rating
+#[derive(Debug, sqlx::FromRow)]
+pub struct TeamPointsAllowed {
+    pub team_id: i64,
+    pub games: i64,
+    pub pts_allowed: f32,
+    pub pace: Option<f32>,
+    pub def_rating: Option<f32>,
+}
+
+/// Points a team allows per game and per 100 possessions, ranked across the league.
+/// Points-allowed ranks are 1 = allows the fewest; pace rank is 1 = fastest. Pace-based
+/// fields are None when the team has no team_pace row for the season, and those ranks
+/// only count teams that do.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoringDefense {
+    pub team_id: i64,
+    pub team_name: String,
+    pub season: String,
+    pub games: i64,
+    pub pts_allowed: f32,
+    pub pts_allowed_rank: i32,
+    pub pace: Option<f32>,
+    pub pace_rank: Option<i32>,
+    /// Points allowed per game scaled to 100 possessions by the team's pace
+    pub pts_allowed_per_100: Option<f32>,
+    pub pts_allowed_per_100_rank: Option<i32>,
+    /// team_pace defensive rating, for comparison with `pts_allowed_per_100`
+    pub def_rating: Option<f32>,
+    pub teams_ranked: i32,
+    pub league_avg_pts_allowed: f32,
+    pub league_avg_pts_allowed_per_100: Option<f32>,
+}
+
+/// Average of one stat a team allows per game to a position
+#[derive(Debug, sqlx::FromRow)]
+pub struct TeamStatAllowed {
+    pub team_id: i64,
+    pub allowed: f32,
+    pub games: i64,
+}
+
+/// Defense vs position: how much of a stat a team allows to one position (rank 1 = allows fewest)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionDefense {
+    pub team_id: i64,
+    pub team_name: String,
+    pub stat: String,
+    pub position: String,
+    pub allowed_per_game: f32,
+    pub games: i64,
+    pub rank: i32,
+    pub teams_ranked: i32,
+    pub league_avg: f32,
+}
+
+/// A player's rebounding averages over their played games
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerRebounding {
+    pub games: i64,
+    pub reb: f32,
+    pub oreb: Option<f32>,
+    pub dreb: Option<f32>,
+    pub reb_per_36: Option<f32>,
+}
+
+/// Player rebounding vs how much the opponent allows (ranks: 1 = allows fewest)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReboundMatchup {
+    pub player_name: String,
+    pub opponent_name: String,
+    pub player: PlayerRebounding,
+    pub reb_allowed: f32,
+    pub reb_rank: i32,
+    pub oreb_allowed: f32,
+    pub oreb_rank: i32,
+    pub dreb_allowed: f32,
+    pub dreb_rank: i32,
+    pub league_avg_reb_allowed: f32,
+    pub verdict: String,           // "attack", "neutral" or "avoid"
+}
+
 /// Upcoming matchup defensive context response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpcomingMatchupResponse {
     pub opponent_name: String,
@@ -504,5 +1627,74 @@ pub struct UpcomingMatchupResponse {
     pub rebounds_allowed: Option<f32>,
     pub oreb_allowed: Option<f32>,
     pub dreb_allowed: Option<f32>,
+    /// Rebounds allowed rank among all teams (1 = allows the fewest)
+    pub total_reb_rank: Option<i32>,
+    pub oreb_rank: Option<i32>,
+    pub dreb_rank: Option<i32>,
+}
+
+/// Upcoming matchup context for points, assists and rebounds in one response
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingMatchupAllResponse {
+    pub opponent_name: String,
+    pub points: UpcomingMatchupResponse,
+    pub assists: UpcomingMatchupResponse,
+    pub rebounds: UpcomingMatchupResponse,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn box_score(pts: i32, reb: i32, ast: i32, stl: i32, blk: i32, tov: i32, fg3m: i32) -> PlayerGameLog {
+        PlayerGameLog {
+            game_id: "g1".to_string(),
+            player_id: "1".to_string(),
+            team_id: None,
+            season: None,
+            game_date: None,
+            matchup: None,
+            wl: None,
+            min: Some(30.0),
+            pts: Some(pts),
+            reb: Some(reb),
+            ast: Some(ast),
+            stl: Some(stl),
+            blk: Some(blk),
+            fgm: None,
+            fga: None,
+            fg3m: Some(fg3m),
+            fg3a: None,
+            ftm: None,
+            fta: None,
+            tov: Some(tov),
+            game_margin: None,
+            oreb: None,
+            dreb: None,
+        }
+    }
+
+    #[test]
+    fn draftkings_scoring_with_bonuses() {
+        // 20 + 0.5*2 + 1.25*4 + 1.5*6 + 2*1 + 2*1 - 0.5*2
+        assert_eq!(box_score(20, 4, 6, 1, 1, 2, 2).fantasy_points(FantasyScoring::DraftKings), Some(38.0));
+        // Double-double adds 1.5
+        assert_eq!(box_score(20, 10, 6, 1, 1, 2, 2).fantasy_points(FantasyScoring::DraftKings), Some(47.0));
+        // Triple-double adds 1.5 + 3
+        assert_eq!(box_score(20, 10, 10, 1, 1, 2, 2).fantasy_points(FantasyScoring::DraftKings), Some(56.0));
+    }
+
+    #[test]
+    fn fanduel_scoring_has_no_bonus() {
+        // 20 + 1.2*10 + 1.5*10 + 3*1 + 3*1 - 2
+        assert_eq!(box_score(20, 10, 10, 1, 1, 2, 2).fantasy_points(FantasyScoring::FanDuel), Some(51.0));
+    }
+
+    #[test]
+    fn fantasy_points_need_every_counting_stat() {
+        let log = PlayerGameLog { stl: None, ..box_score(20, 4, 6, 1, 1, 2, 2) };
+        assert_eq!(log.fantasy_points(FantasyScoring::DraftKings), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! OpenAPI description of the HTTP API, built from the `#[utoipa::path]` annotations
+//! on the route handlers. Served as JSON at /api/openapi.json with Swagger UI at /docs.
+
+use axum::response::{Html, Json};
+use std::sync::LazyLock;
+use utoipa::OpenApi;
+use crate::routes;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "NBA Stats API", description = "Player, team, matchup and props data for the NBA dashboard"),
+    paths(
+        routes::health::health_check,
+        routes::health::liveness,
+        routes::health::readiness,
+        routes::players::get_players,
+        routes::players::get_player_by_id,
+        routes::players::get_players_batch,
+        routes::players::search_players,
+        routes::players::autocomplete_players,
+        routes::players::get_usage_spikes,
+        routes::players::get_player_shooting_zones,
+        routes::players::get_player_assist_zones,
+        routes::players::get_player_play_types,
+        routes::players::get_player_game_logs,
+        routes::players::get_player_game_logs_csv,
+        routes::players::get_player_recent_averages,
+        routes::players::get_player_boom_bust,
+        routes::players::get_player_fantasy_consistency,
+        routes::players::get_player_consistency,
+        routes::players::get_player_adjusted_stats,
+        routes::players::get_player_percentiles,
+        routes::players::get_player_data_coverage,
+        routes::players::get_player_home_away_splits,
+        routes::players::get_player_advanced,
+        routes::props::get_player_props,
+        routes::props::get_player_prop_history,
+        routes::line_shopping::get_best_odds,
+        routes::players::get_player_play_type_matchup,
+        routes::players::get_player_attack_point,
+        routes::players::get_player_matchup_grade,
+        routes::players::get_player_rebound_matchup,
+        routes::players::get_player_assist_zone_matchup,
+        routes::players::get_player_shooting_zone_matchup,
+        routes::players::get_player_opponent_history,
+        routes::players::get_upcoming_matchup_context,
+        routes::players::get_upcoming_matchup_context_all,
+        routes::players::get_player_projection,
+        routes::injuries::get_injuries,
+        routes::teams::get_teams,
+        routes::teams::search_team,
+        routes::teams::get_team_rankings,
+        routes::teams::get_team_by_id,
+        routes::teams::get_team_stats,
+        routes::teams::get_team_allowed_by_position,
+        routes::teams::get_team_scoring_defense,
+        routes::teams::get_team_projected_points,
+        routes::zones::get_team_defensive_zones,
+        routes::zones::get_team_defensive_zone_ranks,
+        routes::zones::get_team_zone_strengths,
+        routes::zones::get_zone_league_averages,
+        routes::play_types::get_team_defensive_play_types,
+        routes::play_types::get_team_defensive_play_type_ranks,
+        routes::teams::get_standings,
+        routes::line_shopping::get_top_picks,
+        routes::line_shopping::get_clv_report,
+        routes::line_shopping::compute_parlay_ev,
+        routes::line_shopping::get_arbs,
+        routes::props::get_batch_props,
+        routes::props::get_stat_types,
+        routes::props::get_prop_players,
+        routes::slate::get_props_by_game,
+        routes::schedule::get_schedule,
+        routes::schedule::get_todays_games,
+        routes::schedule::get_upcoming_games,
+        routes::schedule::get_upcoming_rosters,
+        routes::schedule::get_game_context,
+        routes::schedule::get_team_schedule_context,
+    ),
+    components(schemas(crate::models::PlayerPage)),
+    tags(
+        (name = "health", description = "Service health"),
+        (name = "players", description = "Player stats, splits, matchups and props"),
+        (name = "teams", description = "Team ratings, standings and defense"),
+        (name = "zones", description = "League-wide shooting zone defense"),
+        (name = "screener", description = "Line shopping picks and CLV"),
+        (name = "props", description = "Underdog props"),
+        (name = "slate", description = "Daily slate views"),
+        (name = "schedule", description = "Game schedule and rosters"),
+    )
+)]
+pub struct ApiDoc;
+
+/// The spec never changes at runtime, so build it once
+static SPEC: LazyLock<utoipa::openapi::OpenApi> = LazyLock::new(ApiDoc::openapi);
+
+/// Swagger UI page loading its assets from a CDN and pointed at our spec
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>NBA Stats API docs</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js" crossorigin></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>"##;
+
+// GET /api/openapi.json - OpenAPI 3 document for every endpoint
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(SPEC.clone())
+}
+
+// GET /docs - Swagger UI
+pub async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
@@ -0,0 +1,148 @@
+//! DEV_MODE query diagnostics: counts the SQL statements each request runs and their total
+//! execution time, reported as `X-Query-Count` and `X-DB-Time-Ms` response headers.
+//!
+//! sqlx emits a `sqlx::query` tracing event for every statement, inside the span that was
+//! current when the query was issued (the SQLite worker thread re-enters it). The middleware
+//! runs each request in its own span carrying a `QueryStats`, and `QueryStatsLayer` adds
+//! every statement event to the stats of the request span it happened under.
+
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Instrument, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Per-request statement count and total execution time
+#[derive(Debug, Default)]
+pub struct QueryStats {
+    count: AtomicU64,
+    micros: AtomicU64,
+}
+
+impl QueryStats {
+    fn record(&self, elapsed_secs: f64) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.micros.fetch_add((elapsed_secs * 1_000_000.0) as u64, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.micros.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+}
+
+/// Pulls `elapsed_secs` out of a `sqlx::query` event
+pub struct ElapsedVisitor(pub Option<f64>);
+
+impl Visit for ElapsedVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+/// Tracing layer attributing sqlx statement events to the enclosing request's `QueryStats`
+pub struct QueryStatsLayer;
+
+impl<S> Layer<S> for QueryStatsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != "sqlx::query" {
+            return;
+        }
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        for span in scope {
+            if let Some(stats) = span.extensions().get::<Arc<QueryStats>>() {
+                let mut elapsed = ElapsedVisitor(None);
+                event.record(&mut elapsed);
+                stats.record(elapsed.0.unwrap_or_default());
+                return;
+            }
+        }
+    }
+}
+
+/// Middleware running the request in a span that collects its query stats, then
+/// reporting them as response headers. The stats are also put in the request extensions.
+pub async fn query_stats_headers(mut req: Request, next: Next) -> Response {
+    let stats = Arc::new(QueryStats::default());
+    req.extensions_mut().insert(stats.clone());
+
+    let span = tracing::info_span!("query_stats");
+    span.with_subscriber(|(id, dispatch)| {
+        if let Some(registry) = dispatch.downcast_ref::<tracing_subscriber::Registry>()
+            && let Some(span_ref) = registry.span(id)
+        {
+            span_ref.extensions_mut().insert(stats.clone());
+        }
+    });
+
+    let mut response = next.run(req).instrument(span).await;
+
+    let headers = response.headers_mut();
+    headers.insert("x-query-count", HeaderValue::from(stats.count()));
+    if let Ok(value) = HeaderValue::from_str(&format!("{:.2}", stats.total_ms())) {
+        headers.insert("x-db-time-ms", value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::MatchupCache;
+    use crate::db::test_support::*;
+    use crate::state::AppState;
+    use axum::{body::Body, routing::get, Router};
+    use std::time::Duration;
+    use tower::ServiceExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[tokio::test]
+    async fn bundled_endpoint_reports_several_queries() {
+        // The SQLite worker thread emits statement events, so the layer must be global
+        let _ = tracing::subscriber::set_global_default(tracing_subscriber::registry().with(QueryStatsLayer));
+
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_team(&pool, 2, "AWY").await;
+        insert_player(&pool, 10, "Bundled Player", 1, "G").await;
+        let state = AppState {
+            pool,
+            matchup_cache: Arc::new(MatchupCache::new(Duration::from_secs(60))),
+        };
+        let app = Router::new()
+            .route(
+                "/api/players/{id}/upcoming-matchup/all",
+                get(crate::routes::players::get_upcoming_matchup_context_all),
+            )
+            .with_state(state)
+            .layer(axum::middleware::from_fn(query_stats_headers));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/players/10/upcoming-matchup/all?opponent_id=2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let count: u64 = response.headers()["x-query-count"].to_str().unwrap().parse().unwrap();
+        assert!(count > 1, "expected several queries, got {}", count);
+        assert!(response.headers().contains_key("x-db-time-ms"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Per-client-IP token bucket rate limiting. Each IP gets a bucket holding up to
+//! `RATE_LIMIT_PER_MINUTE` requests that refills continuously at that rate; a request
+//! with an empty bucket gets 429 with `Retry-After`. Health, metrics and docs are exempt.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use crate::error::ApiError;
+
+/// Paths never rate limited
+const EXEMPT_PATHS: [&str; 6] = [
+    "/health", "/health/live", "/health/ready", "/metrics", "/docs", "/api/openapi.json",
+];
+
+/// Most IPs tracked at once; past this the least recently seen buckets are evicted
+const MAX_TRACKED_IPS: usize = 10_000;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    per_sec: f64,
+    trust_forwarded: bool,
+    max_tracked: usize,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Allow `per_minute` requests per IP, bursting up to the same amount. With
+    /// `trust_forwarded` the client IP is the last `X-Forwarded-For` entry, the one the
+    /// proxy appended; earlier entries come from the client and can be spoofed.
+    pub fn new(per_minute: u32, trust_forwarded: bool) -> Self {
+        RateLimiter {
+            capacity: per_minute as f64,
+            per_sec: per_minute as f64 / 60.0,
+            trust_forwarded,
+            max_tracked: MAX_TRACKED_IPS,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `ip`, or return the seconds until one is available
+    fn acquire(&self, ip: IpAddr) -> Result<(), u64> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= self.max_tracked && !buckets.contains_key(&ip) {
+            evict_oldest(&mut buckets, self.max_tracked / 4);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket { tokens: self.capacity, refilled_at: now });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_sec).min(self.capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / self.per_sec).ceil().max(1.0) as u64)
+        }
+    }
+
+    fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        if self.trust_forwarded
+            && let Some(ip) = req
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.rsplit(',').next())
+                .and_then(|v| v.trim().parse().ok())
+        {
+            return Some(ip);
+        }
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip())
+    }
+}
+
+/// Drop the `count` least recently seen buckets. Evicting a batch keeps the O(n) scan
+/// to once per `count` new IPs rather than once per request.
+fn evict_oldest(buckets: &mut HashMap<IpAddr, Bucket>, count: usize) {
+    let mut seen: Vec<Instant> = buckets.values().map(|b| b.refilled_at).collect();
+    let count = count.clamp(1, seen.len());
+    let (_, &mut cutoff, _) = seen.select_nth_unstable(count - 1);
+    let mut to_drop = count;
+    buckets.retain(|_, b| {
+        if to_drop > 0 && b.refilled_at <= cutoff {
+            to_drop -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Middleware rejecting requests from IPs that have used up their bucket
+pub async fn rate_limit(State(limiter): State<Arc<RateLimiter>>, req: Request, next: Next) -> Response {
+    if EXEMPT_PATHS.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+    let Some(ip) = limiter.client_ip(&req) else {
+        return next.run(req).await;
+    };
+
+    match limiter.acquire(ip) {
+        Ok(()) => next.run(req).await,
+        Err(retry_after_secs) => ApiError::RateLimited(retry_after_secs).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn ip(n: u32) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::from(n))
+    }
+
+    #[test]
+    fn tracked_ips_stay_bounded_with_partly_drained_buckets() {
+        let limiter = RateLimiter { max_tracked: 100, ..RateLimiter::new(60, false) };
+        for n in 0..1_000 {
+            // Two requests each, so no bucket is full and none would be pruned as idle
+            limiter.acquire(ip(n)).unwrap();
+            limiter.acquire(ip(n)).unwrap();
+            assert!(limiter.buckets.lock().unwrap().len() <= 100);
+        }
+        // The most recent IP keeps its drained bucket
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets[&ip(999)].tokens.floor(), 58.0);
+    }
+
+    #[test]
+    fn client_ip_uses_last_forwarded_entry() {
+        let limiter = RateLimiter::new(60, true);
+        let req = Request::builder()
+            .header("x-forwarded-for", "1.2.3.4, 10.0.0.7")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert_eq!(limiter.client_ip(&req), Some("10.0.0.7".parse().unwrap()));
+
+        let untrusted = RateLimiter::new(60, false);
+        assert_eq!(untrusted.client_ip(&req), None);
+    }
+}
@@ -0,0 +1,64 @@
+//! Per-request correlation ID. The middleware assigns each request an ID (keeping a sane
+//! inbound `X-Request-Id`, e.g. from a proxy) and echoes it back in `X-Request-Id`;
+//! `make_span` gives the trace layer's request span that ID, so handler and DB query logs
+//! can be grepped by it.
+//!
+//! The ID lives on the trace layer's span rather than one opened here: sqlx logs each
+//! statement under the span that was current when it was issued, which is the innermost
+//! one, and the log filter drops events whose innermost span it has filtered out.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest inbound request ID honored; longer ones are replaced
+const MAX_INBOUND_ID_LEN: usize = 128;
+
+/// Random (version 4) UUID in its hyphenated form
+fn new_request_id() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// Middleware setting the request's `X-Request-Id` (for `make_span` and handlers) and
+/// copying it onto the response
+pub async fn request_id(mut req: Request, next: Next) -> Response {
+    let inbound = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && id.len() <= MAX_INBOUND_ID_LEN)
+        .and_then(|id| HeaderValue::from_str(id).ok());
+    let id = inbound.unwrap_or_else(|| {
+        HeaderValue::from_str(&new_request_id()).expect("UUIDs are valid header values")
+    });
+    req.headers_mut().insert(X_REQUEST_ID, id.clone());
+
+    let mut response = next.run(req).await;
+    response.headers_mut().insert(X_REQUEST_ID, id);
+    response
+}
+
+/// Span for the trace layer, tagged with the ID `request_id` assigned
+pub fn make_span<B>(req: &axum::http::Request<B>) -> tracing::Span {
+    let id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %req.method(),
+        uri = %req.uri(),
+    )
+}
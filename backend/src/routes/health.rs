@@ -1,17 +1,74 @@
-use axum::{response::Json, http::StatusCode};
+use axum::{extract::State, response::Json, http::StatusCode};
 use serde::Serialize;
+use sqlx::sqlite::SqlitePool;
+use std::time::Duration;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+/// How long the readiness probe waits on the database
+const DB_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, ToSchema)]
 pub struct HealthResponse {
     status: String,
     timestamp: i64,
 }
 
-pub async fn health_check() -> (StatusCode, Json<HealthResponse>) {
+fn health_response(status: StatusCode, label: &str) -> (StatusCode, Json<HealthResponse>) {
     let response = HealthResponse {
-        status: "ok".to_string(),
+        status: label.to_string(),
         timestamp: chrono::Utc::now().timestamp(),
     };
 
-    (StatusCode::OK, Json(response))
-}
\ No newline at end of file
+    (status, Json(response))
+}
+
+/// Whether a trivial query succeeds within `DB_CHECK_TIMEOUT`
+async fn database_reachable(pool: &SqlitePool) -> bool {
+    let query = sqlx::query_scalar::<_, i64>("SELECT 1").fetch_one(pool);
+    matches!(tokio::time::timeout(DB_CHECK_TIMEOUT, query).await, Ok(Ok(1)))
+}
+
+/// GET /health - "ok", or 503 "degraded" when the database can't be queried
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, body = HealthResponse),
+        (status = 503, description = "Database unreachable", body = HealthResponse),
+    )
+)]
+pub async fn health_check(State(pool): State<SqlitePool>) -> (StatusCode, Json<HealthResponse>) {
+    if database_reachable(&pool).await {
+        health_response(StatusCode::OK, "ok")
+    } else {
+        health_response(StatusCode::SERVICE_UNAVAILABLE, "degraded")
+    }
+}
+
+/// GET /health/live - Liveness: the process is up and serving, without touching the database
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, body = HealthResponse),
+    )
+)]
+pub async fn liveness() -> (StatusCode, Json<HealthResponse>) {
+    health_response(StatusCode::OK, "ok")
+}
+
+/// GET /health/ready - Readiness: 503 "degraded" until the database answers queries
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, body = HealthResponse),
+        (status = 503, description = "Database unreachable", body = HealthResponse),
+    )
+)]
+pub async fn readiness(State(pool): State<SqlitePool>) -> (StatusCode, Json<HealthResponse>) {
+    health_check(State(pool)).await
+}
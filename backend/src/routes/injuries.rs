@@ -0,0 +1,49 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use sqlx::sqlite::SqlitePool;
+use crate::db;
+use crate::error::{ApiError, ErrorResponse};
+use crate::models::InjuredPlayer;
+
+/// Query parameters for the injury report
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct InjuriesQuery {
+    /// Only players on this team
+    #[serde(default)]
+    pub team_id: Option<i64>,
+    /// Only players with this status (Out, Questionable, Doubtful, ...), case-insensitive
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// GET /api/injuries?team_id=&status= - League-wide injury report
+///
+/// Every player on the latest injury report whose status isn't "Available".
+#[utoipa::path(
+    get,
+    path = "/api/injuries",
+    tag = "players",
+    params(InjuriesQuery),
+    responses(
+        (status = 200, body = Vec<InjuredPlayer>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_injuries(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<InjuriesQuery>,
+) -> Result<Json<Vec<InjuredPlayer>>, ApiError> {
+    let injuries = db::get_injured_players(&pool, params.team_id, params.status.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get injuries: {}", e);
+            ApiError::from(e)
+        })?;
+
+    Ok(Json(injuries))
+}
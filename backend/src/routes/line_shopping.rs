@@ -1,20 +1,50 @@
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
 };
-use chrono::Timelike;
-use chrono_tz::America::New_York;
 use sqlx::sqlite::SqlitePool;
 use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+use crate::cache;
 use crate::db;
-use crate::models::{SharpBookLine, TopPick, TopPicksResponse};
+use crate::error::{ApiError, ErrorResponse};
+use crate::models::{
+    BestOdds, BookPrice, ClvPick, ClvReport, ClvSnapshotRow, GameTopPicks, ParlayEv, ParlayLegResult,
+    PickExplanation, SharpArb, SharpBookLine, TopPick, TopPickRow, TopPicksByGameResponse, TopPicksResponse, TopPicksResult,
+};
+use crate::util::game_time::has_game_started;
+use crate::util::today::Today;
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ScreenerQuery {
     pub game_date: Option<String>,
+    /// Number of picks to return (default: 20, max: 100)
+    pub limit: Option<usize>,
+    /// Minimum edge in percent (default: 0.5)
+    pub min_edge: Option<f64>,
+    /// Only picks for this Underdog stat (points, rebounds, ...)
+    pub stat_type: Option<String>,
+    /// "game" nests the picks under their game; omitted returns a flat list
+    pub group_by: Option<String>,
+    /// Only picks from games involving this team (abbreviation, e.g. LAL)
+    pub team_abbr: Option<String>,
+    /// Only picks from games involving this team too, e.g. team_abbr=LAL&opponent_abbr=BOS
+    pub opponent_abbr: Option<String>,
 }
 
+/// Default screener `min_edge` in percent; `compute_top_picks` never keeps smaller edges
+const DEFAULT_MIN_EDGE_PCT: f64 = 0.5;
+
+/// Picks returned by the screener when no `limit` is given
+const DEFAULT_TOP_PICKS: usize = 20;
+
+/// Upper bound on the screener `limit`
+const MAX_TOP_PICKS: usize = 100;
+
+/// Underdog odds assumed when a line shows none
+const UD_DEFAULT_ODDS: i32 = -110;
+
 /// Convert American odds to implied probability (0.0–1.0)
 fn implied_prob(odds: i32) -> f64 {
     if odds < 0 {
@@ -37,39 +67,38 @@ fn devigged_over_prob(over_odds: Option<i32>, under_odds: Option<i32>) -> Option
     Some(over / total)
 }
 
-/// Check if a game has started based on its date and time (ET).
-fn has_game_started(game_date: &str, game_time: &Option<String>) -> bool {
-    let now_et = chrono::Utc::now().with_timezone(&New_York);
-    let parsed_date = match chrono::NaiveDate::parse_from_str(game_date, "%Y-%m-%d") {
-        Ok(d) => d,
-        Err(_) => return false,
-    };
-    let today_et = now_et.date_naive();
-    if parsed_date > today_et {
-        return false;
+/// A sharp-book quote with both sides devigged like the picks (as percentages)
+fn sharp_book_line(row: &TopPickRow) -> SharpBookLine {
+    let over_fair = devigged_over_prob(row.over_odds, row.under_odds);
+    SharpBookLine {
+        sportsbook: row.sportsbook.clone(),
+        line: row.book_line,
+        over_odds: row.over_odds,
+        under_odds: row.under_odds,
+        over_fair_prob: over_fair.map(|p| (p * 1000.0).round() / 10.0),
+        under_fair_prob: over_fair.map(|p| ((1.0 - p) * 1000.0).round() / 10.0),
     }
-    if parsed_date < today_et {
-        return true;
-    }
-    // Game is today — check time
-    let time_str = match game_time {
-        Some(t) if t != "TBD" && t != "Scheduled" && t != "12:00 AM" => t,
-        _ => return false,
-    };
-    let re = regex::Regex::new(r"(\d{1,2}):(\d{2})\s*(AM|PM|am|pm)").unwrap();
-    let caps = match re.captures(time_str) {
-        Some(c) => c,
-        None => return false,
-    };
-    let mut hours: u32 = caps.get(1).unwrap().as_str().parse().unwrap_or(0);
-    let minutes: u32 = caps.get(2).unwrap().as_str().parse().unwrap_or(0);
-    let am_pm = caps.get(3).unwrap().as_str().to_uppercase();
-    if am_pm == "PM" && hours != 12 {
-        hours += 12;
-    } else if am_pm == "AM" && hours == 12 {
-        hours = 0;
+}
+
+/// Among books quoting exactly `line`, the one whose devigged over prob is furthest from
+/// the Underdog implied prob, with that devigged prob and the signed edge.
+/// Returns None if no book at the line quotes both sides.
+fn best_edge_book(books: &[SharpBookLine], line: f64, ud_prob: f64) -> Option<(&SharpBookLine, f64, f64)> {
+    let mut best: Option<(&SharpBookLine, f64, f64)> = None;
+    for book in books {
+        if (book.line - line).abs() < 0.01
+            && let Some(sharp_over) = devigged_over_prob(book.over_odds, book.under_odds)
+        {
+            // Edge = sharp over prob - UD over implied prob
+            // Positive → sharp thinks over hits more often → take OVER
+            // Negative → sharp thinks under hits more often → take UNDER
+            let edge = sharp_over - ud_prob;
+            if best.is_none_or(|(_, _, best_edge)| edge.abs() > best_edge.abs()) {
+                best = Some((book, sharp_over, edge));
+            }
+        }
     }
-    now_et.hour() > hours || (now_et.hour() == hours && now_et.minute() >= minutes)
+    best
 }
 
 /// Intermediate: all book data grouped for one player+stat
@@ -81,28 +110,491 @@ struct CandidateGroup {
     home_team: String,
     away_team: String,
     game_date: String,
+    game_time: Option<String>,
     books: Vec<SharpBookLine>,
     injury_status: Option<String>,
     injury_description: Option<String>,
 }
 
-/// GET /api/screener/top-picks?game_date=
+/// GET /api/screener/top-picks?game_date=&limit=&min_edge=&stat_type=&team_abbr=&opponent_abbr=&group_by=
+///
+/// Served from the warm cache when the props data hasn't changed since it was built.
+/// `min_edge`, `stat_type` and the team filters apply before the `limit`, and
+/// `total_qualifying` counts every pick passing them, so clients can tell when the
+/// response was capped. With `group_by=game` the limited picks are returned as a
+/// `TopPicksByGameResponse` instead of a flat `TopPicksResponse`.
+#[utoipa::path(
+    get,
+    path = "/api/screener/top-picks",
+    tag = "screener",
+    params(ScreenerQuery),
+    responses(
+        (status = 200, body = TopPicksResult),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_top_picks(
     State(pool): State<SqlitePool>,
+    today: Today,
     Query(params): Query<ScreenerQuery>,
-) -> Result<Json<TopPicksResponse>, StatusCode> {
-    let game_date = params.game_date.unwrap_or_else(|| {
-        chrono::Local::now().format("%Y-%m-%d").to_string()
+) -> Result<Json<TopPicksResult>, ApiError> {
+    let by_game = match params.group_by.as_deref() {
+        None => false,
+        Some("game") => true,
+        Some(other) => {
+            return Err(ApiError::BadRequest(format!("Invalid group_by '{}'. Use 'game'", other)));
+        }
+    };
+
+    let game_date = params.game_date.unwrap_or_else(|| today.ymd());
+
+    let limit = params.limit.unwrap_or(DEFAULT_TOP_PICKS).clamp(1, MAX_TOP_PICKS);
+
+    // Picks carry full team names, so resolve the abbreviations up front
+    let mut teams = Vec::new();
+    for abbr in [&params.team_abbr, &params.opponent_abbr].into_iter().flatten() {
+        let team = db::get_team_by_abbreviation(&pool, &abbr.to_uppercase())
+            .await?
+            .ok_or_else(|| ApiError::BadRequest(format!("Unknown team abbreviation '{}'", abbr)))?;
+        teams.push(team.full_name);
+    }
+
+    let data_version = db::get_props_data_version(&pool).await?;
+    let cached = (!today.is_debug())
+        .then(|| cache::TOP_PICKS.get(&game_date, &data_version))
+        .flatten();
+    let mut picks: Vec<TopPick> = match cached {
+        // Games may have tipped off since the picks were cached
+        Some(cached) => cached
+            .into_iter()
+            .filter(|pick| !has_game_started(&pick.game_date, &pick.game_time, &today))
+            .collect(),
+        None => {
+            let picks = compute_top_picks(&pool, &game_date, &today).await?;
+            if !today.is_debug() {
+                cache::TOP_PICKS.put(&game_date, &data_version, picks.clone());
+            }
+            picks
+        }
+    };
+
+    let min_edge = params.min_edge.unwrap_or(DEFAULT_MIN_EDGE_PCT);
+    let total_qualifying = filter_top_picks(&mut picks, min_edge, params.stat_type.as_deref(), &teams, limit);
+
+    if by_game {
+        return Ok(Json(TopPicksResult::ByGame(TopPicksByGameResponse {
+            games: group_picks_by_game(picks),
+            total_qualifying,
+            last_updated: Some(game_date),
+        })));
+    }
+
+    Ok(Json(TopPicksResult::Flat(TopPicksResponse {
+        picks,
+        total_qualifying,
+        last_updated: Some(game_date),
+    })))
+}
+
+/// Keep the picks with at least `min_edge`, of `stat_type` if given, from games involving
+/// every one of `teams` (full names), then cut to `limit`. Returns how many qualified
+/// before the cut.
+fn filter_top_picks(picks: &mut Vec<TopPick>, min_edge: f64, stat_type: Option<&str>, teams: &[String], limit: usize) -> usize {
+    picks.retain(|pick| {
+        pick.edge_pct >= min_edge
+            && stat_type.is_none_or(|stat| pick.stat_type == stat)
+            && teams.iter().all(|team| pick.home_team == *team || pick.away_team == *team)
     });
 
-    let all_rows = db::get_top_pick_candidates(&pool, &game_date)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let total_qualifying = picks.len();
+    picks.truncate(limit);
+    total_qualifying
+}
+
+/// Nest edge-sorted picks under their game. Games keep the order of their first
+/// (best) pick, so the order within each game is preserved too.
+fn group_picks_by_game(picks: Vec<TopPick>) -> Vec<GameTopPicks> {
+    let mut games: Vec<GameTopPicks> = Vec::new();
+    for pick in picks {
+        let game = games.iter_mut().find(|game| {
+            game.home_team == pick.home_team
+                && game.away_team == pick.away_team
+                && game.game_date == pick.game_date
+        });
+        match game {
+            Some(game) => game.picks.push(pick),
+            None => games.push(GameTopPicks {
+                home_team: pick.home_team.clone(),
+                away_team: pick.away_team.clone(),
+                game_date: pick.game_date.clone(),
+                game_time: pick.game_time.clone(),
+                picks: vec![pick],
+            }),
+        }
+    }
+    games
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BestOddsQuery {
+    /// Underdog stat name (points, rebounds, pts_rebs_asts, ...)
+    pub stat: String,
+    pub game_date: Option<String>,
+}
+
+/// GET /api/players/:id/best-odds?stat=points&game_date=
+///
+/// Best over and best under price among the sharp books quoting the player's Underdog
+/// line, regardless of edge. Higher American odds always pay more, so "best" is the max.
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/best-odds",
+    tag = "players",
+    params(("id" = i64, Path, description = "Player id"), BestOddsQuery),
+    responses(
+        (status = 200, body = BestOdds),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_best_odds(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Path(player_id): Path<i64>,
+    Query(params): Query<BestOddsQuery>,
+) -> Result<Json<BestOdds>, ApiError> {
+    let game_date = params.game_date.unwrap_or_else(|| today.ymd());
+
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    // Candidate names are lowercased Underdog names, which may or may not keep accents
+    let key = db::name_key(&player.player_name);
+    let rows: Vec<_> = db::get_top_pick_candidates(&pool, &game_date)
+        .await?
+        .into_iter()
+        .filter(|row| row.stat_type == params.stat && db::name_key(&row.player_name) == key)
+        .filter(|row| (row.book_line - row.ud_line).abs() < 0.01)
+        .collect();
+
+    let Some(first) = rows.first() else {
+        return Err(ApiError::NotFound(format!(
+            "no sharp-book odds for player {} {} at the Underdog line",
+            player_id, params.stat
+        )));
+    };
+    let (ud_line, ud_odds) = (first.ud_line, first.ud_odds);
+
+    let best_over = best_price(&rows, |row| row.over_odds);
+    let best_under = best_price(&rows, |row| row.under_odds);
+
+    let books = rows
+        .iter()
+        .map(sharp_book_line)
+        .collect();
+
+    Ok(Json(BestOdds {
+        player_name: player.player_name,
+        stat: params.stat,
+        ud_line,
+        ud_odds,
+        best_over,
+        best_under,
+        books,
+    }))
+}
+
+/// The book paying the most on one side (`odds` picks it from a row); books not quoting
+/// that side are skipped
+fn best_price(rows: &[TopPickRow], odds: fn(&TopPickRow) -> Option<i32>) -> Option<BookPrice> {
+    rows.iter()
+        .filter_map(|row| Some((row, odds(row)?)))
+        .max_by_key(|(_, price)| *price)
+        .map(|(row, price)| BookPrice { sportsbook: row.sportsbook.clone(), odds: price })
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ArbsQuery {
+    pub game_date: Option<String>,
+}
+
+/// GET /api/screener/arbs?game_date=
+///
+/// For each player, stat and line quoted by two or more sharp books, pairs the over at
+/// one book with the under at another and keeps the cheapest pair when its implied
+/// probabilities sum to under 100%. Sorted by guaranteed return, best first.
+#[utoipa::path(
+    get,
+    path = "/api/screener/arbs",
+    tag = "screener",
+    params(ArbsQuery),
+    responses(
+        (status = 200, body = Vec<SharpArb>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_arbs(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<ArbsQuery>,
+) -> Result<Json<Vec<SharpArb>>, ApiError> {
+    let game_date = params.game_date.unwrap_or_else(|| today.ymd());
+
+    let rows: Vec<TopPickRow> = db::get_top_pick_candidates(&pool, &game_date)
+        .await?
+        .into_iter()
+        .filter(|row| !has_game_started(&row.game_date, &row.game_time, &today))
+        .collect();
+
+    Ok(Json(find_arbs(&rows)))
+}
+
+/// Group quotes by (player, stat, line), comparing lines to the tenth, and keep each
+/// group's arb. Sorted by guaranteed return, best first.
+fn find_arbs(rows: &[TopPickRow]) -> Vec<SharpArb> {
+    let mut groups: HashMap<(String, String, i64), Vec<&TopPickRow>> = HashMap::new();
+    for row in rows {
+        let key = (row.player_name.clone(), row.stat_type.clone(), (row.book_line * 10.0).round() as i64);
+        groups.entry(key).or_default().push(row);
+    }
+
+    let mut arbs: Vec<SharpArb> = groups
+        .into_values()
+        .filter_map(|quotes| arb_from_group(&quotes))
+        .collect();
+
+    arbs.sort_by(|a, b| b.return_pct.partial_cmp(&a.return_pct).unwrap_or(std::cmp::Ordering::Equal));
+    arbs
+}
+
+/// The cheapest over-at-one-book, under-at-another pair among quotes for the same player,
+/// stat and line, when its implied probabilities sum to under 100%
+fn arb_from_group(quotes: &[&TopPickRow]) -> Option<SharpArb> {
+    let mut best: Option<(&TopPickRow, i32, &TopPickRow, i32, f64)> = None;
+    for over in quotes {
+        let Some(over_odds) = over.over_odds else { continue };
+        for under in quotes {
+            let Some(under_odds) = under.under_odds else { continue };
+            if over.sportsbook == under.sportsbook {
+                continue;
+            }
+            let combined = implied_prob(over_odds) + implied_prob(under_odds);
+            if best.is_none_or(|(_, _, _, _, best_combined)| combined < best_combined) {
+                best = Some((over, over_odds, under, under_odds, combined));
+            }
+        }
+    }
+
+    let (over, over_odds, under, under_odds, combined) = best?;
+    if combined >= 1.0 {
+        return None;
+    }
+    Some(SharpArb {
+        player_name: over.player_name.clone(),
+        stat_type: over.stat_type.clone(),
+        line: over.book_line,
+        over_book: over.sportsbook.clone(),
+        over_odds,
+        under_book: under.sportsbook.clone(),
+        under_odds,
+        combined_implied_prob: (combined * 1000.0).round() / 10.0,
+        return_pct: ((1.0 / combined - 1.0) * 10000.0).round() / 100.0,
+        home_team: over.home_team.clone(),
+        away_team: over.away_team.clone(),
+        game_date: over.game_date.clone(),
+        game_time: over.game_time.clone(),
+    })
+}
+
+/// Underdog power-play payout multipliers by leg count, used when the request doesn't
+/// give its own
+const PARLAY_PAYOUTS: [(usize, f64); 5] = [(2, 3.0), (3, 6.0), (4, 10.0), (5, 20.0), (6, 35.0)];
+
+/// One parlay leg: a player's over or under at a line
+#[derive(serde::Deserialize, ToSchema)]
+pub struct ParlayLeg {
+    pub player_id: i64,
+    /// Underdog stat name (points, rebounds, pts_rebs_asts, ...)
+    pub stat_name: String,
+    pub line: f64,
+    /// "over" or "under"
+    pub choice: String,
+}
+
+/// Request body for the parlay calculator
+#[derive(serde::Deserialize, ToSchema)]
+pub struct ParlayRequest {
+    /// 2 to 6 legs
+    pub legs: Vec<ParlayLeg>,
+    /// Game date (YYYY-MM-DD, default: today)
+    #[serde(default)]
+    pub game_date: Option<String>,
+    /// Payout multiplier; defaults to Underdog's power-play payout for the leg count
+    #[serde(default)]
+    pub payout_multiplier: Option<f64>,
+}
+
+/// POST /api/screener/parlay
+///
+/// Prices each leg at the consensus of the sharp books quoting its line: the mean of
+/// their devigged probabilities for the leg's side. Fair probabilities are multiplied
+/// assuming the legs are independent. Legs with no sharp line at their number are flagged
+/// and left out of the product, so an incomplete parlay's combined probability and EV
+/// are an upper bound.
+#[utoipa::path(
+    post,
+    path = "/api/screener/parlay",
+    tag = "screener",
+    request_body = ParlayRequest,
+    responses(
+        (status = 200, body = ParlayEv),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn compute_parlay_ev(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Json(body): Json<ParlayRequest>,
+) -> Result<Json<ParlayEv>, ApiError> {
+    let payout_multiplier = parlay_payout(body.legs.len(), body.payout_multiplier)?;
+    let mut choices = Vec::with_capacity(body.legs.len());
+    for leg in &body.legs {
+        match leg.choice.to_lowercase().as_str() {
+            "over" => choices.push(true),
+            "under" => choices.push(false),
+            _ => {
+                return Err(ApiError::BadRequest(format!(
+                    "Invalid choice '{}'. Must be one of: over, under",
+                    leg.choice
+                )));
+            }
+        }
+    }
+
+    let game_date = body.game_date.unwrap_or_else(|| today.ymd());
+
+    let player_ids: Vec<i64> = body.legs.iter().map(|leg| leg.player_id).collect();
+    let names: HashMap<i64, String> = db::get_players_by_ids(&pool, &player_ids)
+        .await?
+        .into_iter()
+        .map(|player| (player.player_id, player.player_name))
+        .collect();
+    let rows: Vec<_> = db::get_top_pick_candidates(&pool, &game_date)
+        .await?
+        .into_iter()
+        .filter(|row| !has_game_started(&row.game_date, &row.game_time, &today))
+        .collect();
+
+    let legs = body
+        .legs
+        .into_iter()
+        .zip(choices)
+        .map(|(leg, is_over)| {
+            let player_name = names.get(&leg.player_id).cloned();
+            let key = player_name.as_deref().map(db::name_key);
+            let books: Vec<SharpBookLine> = rows
+                .iter()
+                .filter(|row| row.stat_type == leg.stat_name && key.as_ref() == Some(&db::name_key(&row.player_name)))
+                .map(sharp_book_line)
+                .collect();
+            let priced = consensus_fair_prob(&books, leg.line, is_over);
+
+            let result = ParlayLegResult {
+                player_id: leg.player_id,
+                player_name,
+                stat_name: leg.stat_name,
+                line: leg.line,
+                choice: if is_over { "over" } else { "under" }.to_string(),
+                has_sharp_line: priced.is_some(),
+                fair_prob: priced.as_ref().map(|(fair, _)| (fair * 1000.0).round() / 10.0),
+                books: priced.as_ref().map(|(_, books)| books.clone()).unwrap_or_default(),
+            };
+            (result, priced.map(|(fair, _)| fair))
+        })
+        .collect();
+
+    Ok(Json(parlay_ev(legs, payout_multiplier)))
+}
+
+/// The payout multiplier for a parlay of `legs` legs: `requested` when given, otherwise
+/// Underdog's power-play payout for the leg count
+fn parlay_payout(legs: usize, requested: Option<f64>) -> Result<f64, ApiError> {
+    let default_payout = PARLAY_PAYOUTS
+        .iter()
+        .find(|(count, _)| *count == legs)
+        .map(|(_, payout)| *payout)
+        .ok_or_else(|| ApiError::BadRequest("A parlay needs 2 to 6 legs".to_string()))?;
+    match requested {
+        Some(payout) if payout <= 1.0 || !payout.is_finite() => {
+            Err(ApiError::BadRequest("payout_multiplier must be greater than 1".to_string()))
+        }
+        Some(payout) => Ok(payout),
+        None => Ok(default_payout),
+    }
+}
+
+/// Consensus fair probability (0.0–1.0) for one side at `line`: the mean devigged prob
+/// across every book quoting both sides there, with the books averaged. None if no book does.
+fn consensus_fair_prob(books: &[SharpBookLine], line: f64, is_over: bool) -> Option<(f64, Vec<String>)> {
+    let quotes: Vec<(&SharpBookLine, f64)> = books
+        .iter()
+        .filter(|book| (book.line - line).abs() < 0.01)
+        .filter_map(|book| Some((book, devigged_over_prob(book.over_odds, book.under_odds)?)))
+        .collect();
+    if quotes.is_empty() {
+        return None;
+    }
+
+    let sharp_over = quotes.iter().map(|(_, p)| p).sum::<f64>() / quotes.len() as f64;
+    let fair = if is_over { sharp_over } else { 1.0 - sharp_over };
+    Some((fair, quotes.iter().map(|(book, _)| book.sportsbook.clone()).collect()))
+}
+
+/// Combine legs and their unrounded fair probs (None when unpriced) into the parlay's
+/// fair probability and EV at `payout_multiplier`
+fn parlay_ev(priced: Vec<(ParlayLegResult, Option<f64>)>, payout_multiplier: f64) -> ParlayEv {
+    let combined: f64 = priced.iter().filter_map(|(_, fair)| *fair).product();
+    let legs: Vec<ParlayLegResult> = priced.into_iter().map(|(leg, _)| leg).collect();
+
+    let legs_priced = legs.iter().filter(|leg| leg.has_sharp_line).count();
+    let (combined_fair_prob, ev_pct) = if legs_priced == 0 {
+        (None, None)
+    } else {
+        (
+            Some((combined * 10000.0).round() / 100.0),
+            Some(((combined * payout_multiplier - 1.0) * 1000.0).round() / 10.0),
+        )
+    };
+
+    ParlayEv {
+        complete: legs_priced == legs.len(),
+        legs_priced,
+        payout_multiplier,
+        breakeven_prob: (10000.0 / payout_multiplier).round() / 100.0,
+        combined_fair_prob,
+        ev_pct,
+        legs,
+    }
+}
+
+/// Compute every Underdog pick with a meaningful devigged edge for games on `game_date`
+/// that haven't started as of `today`, sorted by edge descending. Callers apply their own limit.
+pub async fn compute_top_picks(pool: &SqlitePool, game_date: &str, today: &Today) -> Result<Vec<TopPick>, sqlx::Error> {
+    let all_rows = db::get_top_pick_candidates(pool, game_date)
+        .await?;
 
     // Filter out rows for games that have already started
     let rows: Vec<_> = all_rows
         .into_iter()
-        .filter(|row| !has_game_started(&row.game_date, &row.game_time))
+        .filter(|row| !has_game_started(&row.game_date, &row.game_time, today))
         .collect();
 
     // Group rows by (player_name, stat_type)
@@ -117,86 +609,676 @@ pub async fn get_top_picks(
             home_team: row.home_team.clone(),
             away_team: row.away_team.clone(),
             game_date: row.game_date.clone(),
+            game_time: row.game_time.clone(),
             books: Vec::new(),
             injury_status: row.injury_status.clone(),
             injury_description: row.injury_description.clone(),
         });
-        group.books.push(SharpBookLine {
-            sportsbook: row.sportsbook,
-            line: row.book_line,
-            over_odds: row.over_odds,
-            under_odds: row.under_odds,
-        });
+        group.books.push(sharp_book_line(&row));
     }
 
     // For each group, find the best edge from books with the exact matching line
-    let ud_default_odds = -110;
     let mut picks: Vec<TopPick> = groups
         .into_values()
-        .filter_map(|group| {
-            let ud_odds_val = group.ud_odds.unwrap_or(ud_default_odds);
-            let ud_prob = implied_prob(ud_odds_val);
-
-            // Find best devigged edge from books at the exact UD line
-            let mut best_edge: f64 = 0.0;
-            let mut best_book = String::new();
-            let mut best_devigged = 0.0;
-
-            for book in &group.books {
-                if (book.line - group.ud_line).abs() < 0.01 {
-                    if let Some(sharp_over) = devigged_over_prob(book.over_odds, book.under_odds) {
-                        // Edge = sharp over prob - UD over implied prob
-                        // Positive → sharp thinks over hits more often → take OVER
-                        // Negative → sharp thinks under hits more often → take UNDER
-                        let edge = sharp_over - ud_prob;
-
-                        if edge.abs() > best_edge.abs() {
-                            best_edge = edge;
-                            best_book = book.sportsbook.clone();
-                            // Store the fair prob for the direction we'd take
-                            best_devigged = if edge > 0.0 { sharp_over } else { 1.0 - sharp_over };
-                        }
-                    }
+        .filter_map(pick_from_group)
+        .collect();
+
+    // Sort by edge descending
+    picks.sort_by(|a, b| b.edge_pct.partial_cmp(&a.edge_pct).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(picks)
+}
+
+/// The pick for one player+stat: the side the best-edge book at the Underdog line favors,
+/// with an explanation whose fair and implied probs reconcile to `edge_pct`. None if no
+/// book quotes both sides at the line or the edge is negligible.
+fn pick_from_group(group: CandidateGroup) -> Option<TopPick> {
+    let ud_odds_val = group.ud_odds.unwrap_or(UD_DEFAULT_ODDS);
+    let ud_prob = implied_prob(ud_odds_val);
+
+    // Skip if no matching-line book found or edge is negligible
+    let (best, sharp_over, best_edge) = best_edge_book(&group.books, group.ud_line, ud_prob)?;
+    if best_edge.abs() < 0.005 {
+        return None;
+    }
+    // Store the fair prob for the direction we'd take
+    let best_devigged = if best_edge > 0.0 { sharp_over } else { 1.0 - sharp_over };
+    let best_book = best.sportsbook.clone();
+
+    let is_over = best_edge > 0.0;
+    let direction = if is_over { "OVER" } else { "UNDER" };
+    let edge_pct = (best_edge.abs() * 1000.0).round() / 10.0; // to 1 decimal %
+    // Show UD implied prob for the direction we're taking
+    let ud_dir_prob = if is_over { ud_prob } else { 1.0 - ud_prob };
+    let ud_implied_prob = (ud_dir_prob * 1000.0).round() / 10.0;
+    let fair_prob = (best_devigged * 1000.0).round() / 10.0;
+
+    let explanation = PickExplanation {
+        ud_line: group.ud_line,
+        ud_odds_used: ud_odds_val,
+        ud_implied_prob,
+        book: best_book.clone(),
+        book_line: best.line,
+        // Devigging only succeeds when both sides are quoted
+        book_over_odds: best.over_odds.unwrap_or_default(),
+        book_under_odds: best.under_odds.unwrap_or_default(),
+        devig_method: "multiplicative".to_string(),
+        fair_prob,
+        edge_pct,
+        summary: format!(
+            "{} {} {}: fair {:.1}% - Underdog implied {:.1}% = {:.1}% edge",
+            best_book, direction, group.ud_line, fair_prob, ud_implied_prob, edge_pct
+        ),
+    };
+
+    Some(TopPick {
+        player_name: group.player_name,
+        stat_type: group.stat_type,
+        direction: direction.to_string(),
+        ud_line: group.ud_line,
+        ud_odds: group.ud_odds,
+        ud_implied_prob,
+        edge_pct,
+        best_book,
+        best_book_devigged_prob: fair_prob,
+        books: group.books,
+        home_team: group.home_team,
+        away_team: group.away_team,
+        game_date: group.game_date,
+        game_time: group.game_time,
+        injury_status: group.injury_status,
+        injury_description: group.injury_description,
+        explanation,
+    })
+}
+
+/// Record the screener's own default page of `picks` (edge-sorted, as from
+/// `compute_top_picks`) as flagged, so closing line value is measured on what the
+/// screener shows by default rather than on whatever filters callers happened to use
+pub async fn snapshot_screener_picks(writer: &SqlitePool, picks: &[TopPick]) -> Result<(), sqlx::Error> {
+    let flagged = picks
+        .iter()
+        .take(DEFAULT_TOP_PICKS)
+        .take_while(|pick| pick.edge_pct >= DEFAULT_MIN_EDGE_PCT)
+        .count();
+    db::record_pick_snapshots(writer, &picks[..flagged]).await
+}
+
+/// Compute (clv_pct, line_move) for a flagged pick given its closing quote.
+/// CLV is the closing fair prob for our side at the flagged line minus the implied prob
+/// we were offered, in percentage points; None when the book stopped quoting that line,
+/// since a fair prob at another number isn't comparable. Line move is signed so positive
+/// means the line moved our way (up for an OVER taken at a lower number, down for an UNDER).
+fn compute_clv(
+    direction: &str,
+    open_line: f64,
+    open_implied_prob: f64,
+    close_line: f64,
+    close_fair_prob: Option<f64>,
+) -> (Option<f64>, f64) {
+    let clv_pct = close_fair_prob.map(|fair| ((fair - open_implied_prob) * 10.0).round() / 10.0);
+    let line_move = if direction == "OVER" { close_line - open_line } else { open_line - close_line };
+    (clv_pct, line_move)
+}
+
+/// The closing quote for a flagged pick from the same book: its line closest to the
+/// flagged one (to measure the move) and the fair prob for our side priced at the flagged
+/// line itself, or None once the book no longer quotes it. Returns
+/// (close_line, close_ud_odds, close_fair_prob as a percentage), or None if the book
+/// quotes no line with both sides.
+fn closing_quote(rows: &[TopPickRow], snap: &ClvSnapshotRow) -> Option<(f64, Option<i32>, Option<f64>)> {
+    let quotes: Vec<&TopPickRow> = rows
+        .iter()
+        .filter(|r| {
+            r.player_name == snap.player_name
+                && r.stat_type == snap.stat_type
+                && r.sportsbook == snap.best_book
+                && r.over_odds.is_some()
+                && r.under_odds.is_some()
+        })
+        .collect();
+
+    let nearest = quotes.iter().min_by(|a, b| {
+        (a.book_line - snap.open_line)
+            .abs()
+            .total_cmp(&(b.book_line - snap.open_line).abs())
+    })?;
+    let at_open = quotes.iter().find(|r| (r.book_line - snap.open_line).abs() < 0.01);
+
+    let fair_pct = at_open
+        .and_then(|r| devigged_over_prob(r.over_odds, r.under_odds))
+        .map(|sharp_over| {
+            let fair = if snap.direction == "OVER" { sharp_over } else { 1.0 - sharp_over };
+            (fair * 1000.0).round() / 10.0
+        });
+    let ud_odds = at_open.map_or(nearest.ud_odds, |r| r.ud_odds);
+
+    Some((nearest.book_line, ud_odds, fair_pct))
+}
+
+/// Refresh the closing quote for every flagged pick whose game hasn't tipped yet.
+/// The last capture before tip-off is what the CLV report treats as the close.
+/// Returns the number of picks updated.
+pub async fn capture_closing_lines(pool: &SqlitePool) -> Result<usize, sqlx::Error> {
+    let today = Today::now();
+    let pending: Vec<ClvSnapshotRow> = db::get_open_clv_snapshots(pool, &today.ymd())
+        .await?
+        .into_iter()
+        .filter(|snap| !has_game_started(&snap.game_date, &snap.game_time, &today))
+        .collect();
+
+    let mut dates: Vec<&str> = pending.iter().map(|snap| snap.game_date.as_str()).collect();
+    dates.sort();
+    dates.dedup();
+
+    let mut updated = 0;
+    for date in dates {
+        let rows = db::get_top_pick_candidates(pool, date).await?;
+
+        for snap in pending.iter().filter(|snap| snap.game_date == date) {
+            let Some((close_line, close_ud_odds, fair_pct)) = closing_quote(&rows, snap) else { continue };
+            db::update_clv_close(pool, snap.id, close_line, close_ud_odds, fair_pct).await?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClvReportQuery {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+/// GET /api/screener/clv-report?start_date=&end_date=
+///
+/// Summary stats only count picks whose game has tipped, since their close is final.
+#[utoipa::path(
+    get,
+    path = "/api/screener/clv-report",
+    tag = "screener",
+    params(ClvReportQuery),
+    responses(
+        (status = 200, body = ClvReport),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_clv_report(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<ClvReportQuery>,
+) -> Result<Json<ClvReport>, ApiError> {
+    let snapshots = db::get_clv_snapshots(
+        &pool,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    )
+    .await?;
+
+    let picks: Vec<ClvPick> = snapshots
+        .into_iter()
+        .map(|snap| {
+            let is_final = has_game_started(&snap.game_date, &snap.game_time, &today);
+            let (clv_pct, line_move) = match snap.close_line {
+                Some(close_line) => {
+                    let (clv, mv) = compute_clv(
+                        &snap.direction,
+                        snap.open_line,
+                        snap.open_implied_prob,
+                        close_line,
+                        snap.close_fair_prob,
+                    );
+                    (clv, Some(mv))
                 }
-            }
+                None => (None, None),
+            };
 
-            // Skip if no matching-line book found or edge is negligible
-            if best_book.is_empty() || best_edge.abs() < 0.005 {
-                return None;
+            ClvPick {
+                player_name: snap.player_name,
+                stat_type: snap.stat_type,
+                direction: snap.direction,
+                game_date: snap.game_date,
+                best_book: snap.best_book,
+                flagged_at: snap.flagged_at,
+                open_line: snap.open_line,
+                open_ud_odds: snap.open_ud_odds,
+                open_implied_prob: snap.open_implied_prob,
+                open_fair_prob: snap.open_fair_prob,
+                closed_at: snap.closed_at,
+                close_line: snap.close_line,
+                close_ud_odds: snap.close_ud_odds,
+                close_fair_prob: snap.close_fair_prob,
+                clv_pct,
+                line_move,
+                is_final,
             }
-
-            let is_over = best_edge > 0.0;
-            let direction = if is_over { "OVER" } else { "UNDER" };
-            let edge_pct = (best_edge.abs() * 1000.0).round() / 10.0; // to 1 decimal %
-            // Show UD implied prob for the direction we're taking
-            let ud_dir_prob = if is_over { ud_prob } else { 1.0 - ud_prob };
-
-            Some(TopPick {
-                player_name: group.player_name,
-                stat_type: group.stat_type,
-                direction: direction.to_string(),
-                ud_line: group.ud_line,
-                ud_odds: group.ud_odds,
-                ud_implied_prob: (ud_dir_prob * 1000.0).round() / 10.0,
-                edge_pct,
-                best_book,
-                best_book_devigged_prob: (best_devigged * 1000.0).round() / 10.0,
-                books: group.books,
-                home_team: group.home_team,
-                away_team: group.away_team,
-                game_date: group.game_date,
-                injury_status: group.injury_status,
-                injury_description: group.injury_description,
-            })
         })
         .collect();
 
-    // Sort by edge descending, take top 10
-    picks.sort_by(|a, b| b.edge_pct.partial_cmp(&a.edge_pct).unwrap_or(std::cmp::Ordering::Equal));
-    picks.truncate(20);
+    let closed: Vec<&ClvPick> = picks
+        .iter()
+        .filter(|p| p.is_final && p.clv_pct.is_some())
+        .collect();
+    let n = closed.len() as f64;
+
+    let (avg_clv_pct, beat_close_pct, avg_line_move) = if closed.is_empty() {
+        (None, None, None)
+    } else {
+        let avg_clv = closed.iter().filter_map(|p| p.clv_pct).sum::<f64>() / n;
+        let beat = closed.iter().filter(|p| p.clv_pct.unwrap_or(0.0) > 0.0).count() as f64 / n;
+        let avg_move = closed.iter().filter_map(|p| p.line_move).sum::<f64>() / n;
+        (
+            Some((avg_clv * 100.0).round() / 100.0),
+            Some((beat * 1000.0).round() / 10.0),
+            Some((avg_move * 100.0).round() / 100.0),
+        )
+    };
 
-    Ok(Json(TopPicksResponse {
+    Ok(Json(ClvReport {
+        picks_flagged: picks.len(),
+        picks_closed: closed.len(),
+        avg_clv_pct,
+        beat_close_pct,
+        avg_line_move,
         picks,
-        last_updated: Some(game_date),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clv_over_line_moved_up() {
+        // Took OVER 24.5 at 52.4% implied; the book moved to 25.5 and priced our side at
+        // 24.5 at 58% by the close
+        let (clv, line_move) = compute_clv("OVER", 24.5, 52.4, 25.5, Some(58.0));
+        assert_eq!(clv, Some(5.6));
+        assert_eq!(line_move, 1.0);
+    }
+
+    #[test]
+    fn clv_over_line_moved_down() {
+        let (clv, line_move) = compute_clv("OVER", 24.5, 52.4, 23.5, Some(48.0));
+        assert_eq!(clv, Some(-4.4));
+        assert_eq!(line_move, -1.0);
+    }
+
+    #[test]
+    fn clv_under_line_moved_down() {
+        // An UNDER gains when the line drops below the number we took
+        let (clv, line_move) = compute_clv("UNDER", 8.5, 52.4, 7.5, Some(56.1));
+        assert_eq!(clv, Some(3.7));
+        assert_eq!(line_move, 1.0);
+    }
+
+    #[test]
+    fn clv_under_line_moved_up() {
+        let (clv, line_move) = compute_clv("UNDER", 8.5, 52.4, 9.5, Some(50.0));
+        assert_eq!(clv, Some(-2.4));
+        assert_eq!(line_move, -1.0);
+    }
+
+    #[test]
+    fn clv_is_none_when_the_flagged_line_is_gone() {
+        let (clv, line_move) = compute_clv("OVER", 24.5, 52.4, 25.5, None);
+        assert_eq!(clv, None);
+        assert_eq!(line_move, 1.0);
+    }
+
+    fn snapshot(direction: &str, open_line: f64) -> ClvSnapshotRow {
+        ClvSnapshotRow {
+            id: 1,
+            player_name: "alpha guard".to_string(),
+            stat_type: "points".to_string(),
+            direction: direction.to_string(),
+            game_date: "2026-01-10".to_string(),
+            game_time: None,
+            best_book: "pinnacle".to_string(),
+            open_line,
+            open_ud_odds: None,
+            open_implied_prob: 52.4,
+            open_fair_prob: 55.0,
+            flagged_at: "2026-01-10 12:00:00".to_string(),
+            close_line: None,
+            close_ud_odds: None,
+            close_fair_prob: None,
+            closed_at: None,
+        }
+    }
+
+    #[test]
+    fn closing_quote_prices_the_flagged_line_after_a_move() {
+        // The book now hangs 25.5 as its main line but still quotes the flagged 24.5
+        let rows = [
+            TopPickRow { book_line: 25.5, ..book_row("pinnacle", Some(-110), Some(-110)) },
+            book_row("pinnacle", Some(-150), Some(130)),
+            book_row("fanduel", Some(-200), Some(170)),
+        ];
+        let (close_line, _, fair) = closing_quote(&rows, &snapshot("OVER", 24.5)).unwrap();
+        assert_eq!(close_line, 24.5);
+        assert_eq!(fair, Some(58.0));
+
+        let (_, _, fair) = closing_quote(&rows, &snapshot("UNDER", 24.5)).unwrap();
+        assert_eq!(fair, Some(42.0));
+    }
+
+    #[test]
+    fn closing_quote_has_no_fair_prob_once_the_flagged_line_is_gone() {
+        let rows = [
+            TopPickRow { book_line: 25.5, ..book_row("pinnacle", Some(-110), Some(-110)) },
+            TopPickRow { book_line: 26.5, ..book_row("pinnacle", Some(120), Some(-140)) },
+        ];
+        let (close_line, _, fair) = closing_quote(&rows, &snapshot("OVER", 24.5)).unwrap();
+        assert_eq!(close_line, 25.5);
+        assert_eq!(fair, None);
+    }
+
+    #[test]
+    fn closing_quote_ignores_other_books() {
+        let rows = [book_row("fanduel", Some(-150), Some(130))];
+        assert!(closing_quote(&rows, &snapshot("OVER", 24.5)).is_none());
+    }
+
+    fn group_with_book(over_odds: i32, under_odds: i32) -> CandidateGroup {
+        CandidateGroup {
+            player_name: "Alpha Guard".to_string(),
+            stat_type: "points".to_string(),
+            ud_line: 24.5,
+            ud_odds: None,
+            home_team: "HOM".to_string(),
+            away_team: "AWY".to_string(),
+            game_date: "2026-01-10".to_string(),
+            game_time: Some("7:30 PM".to_string()),
+            books: vec![SharpBookLine {
+                sportsbook: "pinnacle".to_string(),
+                line: 24.5,
+                over_odds: Some(over_odds),
+                under_odds: Some(under_odds),
+                over_fair_prob: None,
+                under_fair_prob: None,
+            }],
+            injury_status: None,
+            injury_description: None,
+        }
+    }
+
+    fn assert_explanation_reconciles(pick: &TopPick) {
+        let explanation = &pick.explanation;
+        assert_eq!(explanation.edge_pct, pick.edge_pct);
+        assert_eq!(explanation.fair_prob, pick.best_book_devigged_prob);
+        assert_eq!(explanation.ud_implied_prob, pick.ud_implied_prob);
+        // Each figure is rounded to 0.1 on its own, so the difference can be off by one step
+        let difference = explanation.fair_prob - explanation.ud_implied_prob;
+        assert!((difference - pick.edge_pct).abs() <= 0.1 + 1e-9, "{} vs {}", difference, pick.edge_pct);
+        assert!(explanation.summary.ends_with(&format!("= {:.1}% edge", pick.edge_pct)));
+    }
+
+    #[test]
+    fn over_pick_explanation_reconciles_with_edge() {
+        let pick = pick_from_group(group_with_book(-130, 110)).unwrap();
+        assert_eq!(pick.direction, "OVER");
+        assert_eq!(pick.explanation.ud_odds_used, UD_DEFAULT_ODDS);
+        assert_eq!(pick.explanation.fair_prob, 54.3);
+        assert_eq!(pick.explanation.ud_implied_prob, 52.4);
+        assert_eq!(pick.edge_pct, 1.9);
+        assert_explanation_reconciles(&pick);
+    }
+
+    #[test]
+    fn under_pick_explanation_reconciles_with_edge() {
+        let pick = pick_from_group(group_with_book(-110, -150)).unwrap();
+        assert_eq!(pick.direction, "UNDER");
+        assert_eq!(pick.explanation.ud_implied_prob, 47.6);
+        assert_explanation_reconciles(&pick);
+    }
+
+    #[test]
+    fn negligible_edge_is_not_a_pick() {
+        // Devigs to 52.2% over against Underdog's 52.4%
+        assert!(pick_from_group(group_with_book(-120, -100)).is_none());
+    }
+
+    #[test]
+    fn top_picks_filter_counts_every_qualifier_before_the_limit() {
+        let template = pick_from_group(group_with_book(-130, 110)).unwrap();
+        // 25 picks at 1.0%..25.0% edge qualify; 5 more fall under the default min edge
+        let mut picks: Vec<TopPick> = (1..=25)
+            .map(|i| TopPick { edge_pct: i as f64, ..template.clone() })
+            .chain((0..5).map(|_| TopPick { edge_pct: 0.3, ..template.clone() }))
+            .collect();
+
+        let total_qualifying = filter_top_picks(&mut picks, DEFAULT_MIN_EDGE_PCT, None, &[], DEFAULT_TOP_PICKS);
+
+        assert_eq!(total_qualifying, 25);
+        assert_eq!(picks.len(), 20);
+        assert!(picks.iter().all(|pick| pick.edge_pct >= 1.0));
+    }
+
+    #[test]
+    fn top_picks_filter_by_stat_and_teams() {
+        let template = pick_from_group(group_with_book(-130, 110)).unwrap();
+        let mut picks = vec![
+            template.clone(),
+            TopPick { stat_type: "rebounds".to_string(), ..template.clone() },
+            TopPick { away_team: "OTH".to_string(), ..template.clone() },
+        ];
+
+        let teams = ["HOM".to_string(), "AWY".to_string()];
+        let total_qualifying = filter_top_picks(&mut picks, DEFAULT_MIN_EDGE_PCT, Some("points"), &teams, DEFAULT_TOP_PICKS);
+
+        assert_eq!(total_qualifying, 1);
+        assert_eq!(picks[0].stat_type, "points");
+        assert_eq!(picks[0].away_team, "AWY");
+    }
+
+    fn book_row(sportsbook: &str, over_odds: Option<i32>, under_odds: Option<i32>) -> TopPickRow {
+        TopPickRow {
+            player_name: "alpha guard".to_string(),
+            stat_type: "points".to_string(),
+            ud_line: 24.5,
+            ud_odds: None,
+            sportsbook: sportsbook.to_string(),
+            book_line: 24.5,
+            over_odds,
+            under_odds,
+            home_team: "HOM".to_string(),
+            away_team: "AWY".to_string(),
+            game_date: "2026-01-10".to_string(),
+            game_time: None,
+            injury_status: None,
+            injury_description: None,
+        }
+    }
+
+    #[test]
+    fn best_price_takes_the_highest_american_odds() {
+        let rows = [
+            book_row("pinnacle", Some(-115), Some(-105)),
+            book_row("fanduel", Some(102), Some(-125)),
+            book_row("draftkings", Some(-105), None),
+        ];
+
+        let over = best_price(&rows, |row| row.over_odds).unwrap();
+        assert_eq!((over.sportsbook.as_str(), over.odds), ("fanduel", 102));
+        let under = best_price(&rows, |row| row.under_odds).unwrap();
+        assert_eq!((under.sportsbook.as_str(), under.odds), ("pinnacle", -105));
+    }
+
+    #[test]
+    fn best_price_is_none_when_no_book_quotes_the_side() {
+        let rows = [book_row("pinnacle", Some(-110), None)];
+        assert!(best_price(&rows, |row| row.under_odds).is_none());
+    }
+
+    fn sharp_quote(sportsbook: &str, line: f64, over_odds: i32, under_odds: i32) -> SharpBookLine {
+        SharpBookLine {
+            sportsbook: sportsbook.to_string(),
+            line,
+            over_odds: Some(over_odds),
+            under_odds: Some(under_odds),
+            over_fair_prob: None,
+            under_fair_prob: None,
+        }
+    }
+
+    fn parlay_leg(choice: &str, fair: Option<(f64, Vec<String>)>) -> (ParlayLegResult, Option<f64>) {
+        let leg = ParlayLegResult {
+            player_id: 1,
+            player_name: Some("Alpha Guard".to_string()),
+            stat_name: "points".to_string(),
+            line: 24.5,
+            choice: choice.to_string(),
+            has_sharp_line: fair.is_some(),
+            fair_prob: fair.as_ref().map(|(p, _)| (p * 1000.0).round() / 10.0),
+            books: fair.as_ref().map(|(_, books)| books.clone()).unwrap_or_default(),
+        };
+        (leg, fair.map(|(p, _)| p))
+    }
+
+    #[test]
+    fn parlay_leg_takes_the_consensus_of_books_at_its_line() {
+        let books = [
+            sharp_quote("draftkings", 24.5, -130, 110),
+            sharp_quote("fanduel", 24.5, -120, 100),
+            // A different number doesn't price this leg
+            sharp_quote("betmgm", 25.5, -300, 240),
+        ];
+
+        // DraftKings devigs the over to 54.3%, FanDuel to 52.2%
+        let (over, over_books) = consensus_fair_prob(&books, 24.5, true).unwrap();
+        assert!((over - 0.5322).abs() < 1e-4, "{}", over);
+        assert_eq!(over_books, ["draftkings", "fanduel"]);
+        let (under, _) = consensus_fair_prob(&books, 24.5, false).unwrap();
+        assert!((over + under - 1.0).abs() < 1e-12);
+
+        assert!(consensus_fair_prob(&books, 23.5, true).is_none());
+    }
+
+    #[test]
+    fn two_leg_over_under_parlay_multiplies_the_legs() {
+        let over_books = [sharp_quote("draftkings", 24.5, -130, 110), sharp_quote("fanduel", 24.5, -120, 100)];
+        let under_books = [sharp_quote("fanduel", 8.5, -110, -110)];
+        let legs = vec![
+            parlay_leg("over", consensus_fair_prob(&over_books, 24.5, true)),
+            parlay_leg("under", consensus_fair_prob(&under_books, 8.5, false)),
+        ];
+
+        let ev = parlay_ev(legs, parlay_payout(2, None).unwrap());
+
+        assert!(ev.complete);
+        assert_eq!(ev.legs_priced, 2);
+        assert_eq!(ev.payout_multiplier, 3.0);
+        assert_eq!(ev.breakeven_prob, 33.33);
+        assert_eq!(ev.legs[1].fair_prob, Some(50.0));
+        assert_eq!(ev.combined_fair_prob, Some(26.61));
+        assert_eq!(ev.ev_pct, Some(-20.2));
+    }
+
+    #[test]
+    fn parlay_leg_without_a_sharp_line_is_left_out() {
+        let books = [sharp_quote("fanduel", 8.5, -110, -110)];
+        let legs = vec![
+            parlay_leg("under", consensus_fair_prob(&books, 8.5, false)),
+            parlay_leg("over", consensus_fair_prob(&books, 24.5, true)),
+        ];
+
+        let ev = parlay_ev(legs, 3.0);
+
+        assert!(!ev.complete);
+        assert_eq!(ev.legs_priced, 1);
+        assert!(!ev.legs[1].has_sharp_line);
+        assert_eq!(ev.legs[1].fair_prob, None);
+        assert!(ev.legs[1].books.is_empty());
+        // Only the priced leg counts, so this is an upper bound
+        assert_eq!(ev.combined_fair_prob, Some(50.0));
+        assert_eq!(ev.ev_pct, Some(50.0));
+
+        let none = parlay_ev(vec![parlay_leg("over", None), parlay_leg("over", None)], 3.0);
+        assert_eq!(none.combined_fair_prob, None);
+        assert_eq!(none.ev_pct, None);
+    }
+
+    #[test]
+    fn parlay_payout_needs_two_to_six_legs() {
+        assert_eq!(parlay_payout(2, None).unwrap(), 3.0);
+        assert_eq!(parlay_payout(6, None).unwrap(), 35.0);
+        for legs in [0, 1, 7] {
+            assert!(matches!(parlay_payout(legs, None), Err(ApiError::BadRequest(_))), "{} legs", legs);
+            assert!(matches!(parlay_payout(legs, Some(5.0)), Err(ApiError::BadRequest(_))), "{} legs", legs);
+        }
+    }
+
+    #[test]
+    fn parlay_payout_must_exceed_one() {
+        assert_eq!(parlay_payout(3, Some(5.5)).unwrap(), 5.5);
+        for payout in [1.0, 0.5, -2.0, f64::INFINITY, f64::NAN] {
+            assert!(matches!(parlay_payout(3, Some(payout)), Err(ApiError::BadRequest(_))), "{}", payout);
+        }
+    }
+
+    #[test]
+    fn arb_pairs_the_over_and_under_at_different_books() {
+        let rows = [
+            book_row("fanduel", Some(110), Some(-140)),
+            book_row("draftkings", Some(-135), Some(105)),
+            book_row("betmgm", Some(-110), Some(-110)),
+        ];
+        let quotes: Vec<&TopPickRow> = rows.iter().collect();
+
+        let arb = arb_from_group(&quotes).unwrap();
+
+        assert_eq!((arb.over_book.as_str(), arb.over_odds), ("fanduel", 110));
+        assert_eq!((arb.under_book.as_str(), arb.under_odds), ("draftkings", 105));
+        // 47.62% + 48.78%
+        assert_eq!(arb.combined_implied_prob, 96.4);
+        assert_eq!(arb.return_pct, 3.73);
+    }
+
+    #[test]
+    fn no_arb_when_the_best_pair_costs_over_100_pct() {
+        let rows = [book_row("fanduel", Some(-105), Some(-115)), book_row("draftkings", Some(-110), Some(-110))];
+        let quotes: Vec<&TopPickRow> = rows.iter().collect();
+        assert!(arb_from_group(&quotes).is_none());
+    }
+
+    #[test]
+    fn arb_never_pairs_a_book_with_itself() {
+        // Plus money on both sides at one book isn't an arb across books
+        let rows = [book_row("fanduel", Some(105), Some(105)), book_row("draftkings", Some(-150), Some(-150))];
+        let quotes: Vec<&TopPickRow> = rows.iter().collect();
+        assert!(arb_from_group(&quotes).is_none());
+    }
+
+    #[test]
+    fn arb_from_books_quoting_one_side_each() {
+        let rows = [book_row("fanduel", Some(110), None), book_row("draftkings", None, Some(110))];
+        let quotes: Vec<&TopPickRow> = rows.iter().collect();
+
+        let arb = arb_from_group(&quotes).unwrap();
+
+        assert_eq!(arb.over_book, "fanduel");
+        assert_eq!(arb.under_book, "draftkings");
+        assert_eq!(arb.combined_implied_prob, 95.2);
+        assert_eq!(arb.return_pct, 5.0);
+    }
+
+    #[test]
+    fn arbs_only_pair_quotes_at_the_same_line() {
+        let rows = [
+            book_row("fanduel", Some(110), None),
+            TopPickRow { book_line: 25.5, ..book_row("draftkings", None, Some(110)) },
+            // Float noise in a scraped line still groups with 24.5
+            TopPickRow { book_line: 24.500001, ..book_row("betmgm", None, Some(105)) },
+        ];
+
+        let arbs = find_arbs(&rows);
+
+        assert_eq!(arbs.len(), 1);
+        assert_eq!(arbs[0].under_book, "betmgm");
+        assert_eq!(arbs[0].line, 24.5);
+    }
+}
@@ -5,4 +5,7 @@ pub mod zones;
 pub mod play_types;
 pub mod schedule;
 pub mod props;
-pub mod line_shopping;
\ No newline at end of file
+pub mod line_shopping;
+pub mod slate;
+pub mod ws;
+pub mod injuries;
\ No newline at end of file
@@ -1,24 +1,103 @@
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
     response::Json,
 };
 use sqlx::sqlite::SqlitePool;
-use crate::models::TeamDefensivePlayTypes;
+use crate::models::{PlayTypeRank, TeamDefensivePlayTypes};
 use crate::db;
+use crate::error::{ApiError, ErrorResponse};
 
 // GET /api/teams/:id/defensive-play-types - Get team's defensive play types
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/defensive-play-types",
+    tag = "teams",
+    params(("id" = i64, Path, description = "Team id")),
+    responses(
+        (status = 200, body = Vec<TeamDefensivePlayTypes>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_team_defensive_play_types(
     State(pool): State<SqlitePool>,
     Path(team_id): Path<i64>,
-) -> Result<Json<Vec<TeamDefensivePlayTypes>>, StatusCode> {
+) -> Result<Json<Vec<TeamDefensivePlayTypes>>, ApiError> {
     let play_types = db::get_defensive_play_types(&pool, team_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if play_types.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound(format!("no defensive play types for team {}", team_id)));
     }
 
     Ok(Json(play_types))
 }
+
+// GET /api/teams/:id/defensive-play-types/ranks - Team's league rank defending each play type
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/defensive-play-types/ranks",
+    tag = "teams",
+    params(("id" = i64, Path, description = "Team id")),
+    responses(
+        (status = 200, body = Vec<PlayTypeRank>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_defensive_play_type_ranks(
+    State(pool): State<SqlitePool>,
+    Path(team_id): Path<i64>,
+) -> Result<Json<Vec<PlayTypeRank>>, ApiError> {
+    let play_types = db::get_defensive_play_types(&pool, team_id)
+        .await?;
+
+    if play_types.is_empty() {
+        return Err(ApiError::NotFound(format!("no defensive play types for team {}", team_id)));
+    }
+
+    let ranks = db::get_team_defensive_play_type_ranks(&pool)
+        .await?;
+
+    // Ranks come from the same rows, so every play type the team has is ranked
+    let play_type_ranks = play_types
+        .into_iter()
+        .filter_map(|pt| {
+            Some(PlayTypeRank {
+                rank: *ranks.get(&(team_id, pt.play_type.clone()))?,
+                teams_ranked: db::teams_ranked(&ranks, &pt.play_type),
+                play_type: pt.play_type,
+                ppp: pt.ppp,
+            })
+        })
+        .collect();
+
+    Ok(Json(play_type_ranks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::*;
+
+    #[tokio::test]
+    async fn play_type_ranks_count_only_teams_with_data() {
+        let pool = test_pool().await;
+        for (id, abbr) in [(1, "AAA"), (2, "BBB"), (3, "CCC"), (4, "DDD"), (5, "EEE")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        for (team, ppp) in [(1, 1.1), (2, 0.9), (3, 1.0)] {
+            insert_defensive_play_type(&pool, team, "Isolation", ppp).await;
+        }
+        insert_defensive_play_type(&pool, 1, "Postup", 1.2).await;
+        insert_defensive_play_type(&pool, 2, "Postup", 0.8).await;
+
+        let Json(mut ranks) = get_team_defensive_play_type_ranks(State(pool.clone()), Path(1)).await.unwrap();
+        ranks.sort_by(|a, b| a.play_type.cmp(&b.play_type));
+        let summary: Vec<(&str, i32, i32)> = ranks.iter().map(|r| (r.play_type.as_str(), r.rank, r.teams_ranked)).collect();
+        assert_eq!(summary, [("Isolation", 3, 3), ("Postup", 2, 2)]);
+
+        let missing = get_team_defensive_play_type_ranks(State(pool), Path(4)).await;
+        assert!(matches!(missing, Err(ApiError::NotFound(_))));
+    }
+}
@@ -1,135 +1,513 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::header,
+    response::{IntoResponse, Json, Response},
 };
 use serde::Deserialize;
+use utoipa::IntoParams;
 use sqlx::sqlite::SqlitePool;
-use crate::models::{PlayerStats, PlayTypeMatchup, PlayTypeMatchupResponse, UpcomingMatchupResponse};
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::cache::{self, MatchupCache, X_CACHE};
+use crate::models::{AttackPoint, BoomBust, DataCoverage, FantasyConsistency, FantasyGame, FantasyScoring, GAME_LOG_CSV_COLUMNS, HomeAwaySplits, MatchupFactor, MatchupGrade, OpponentHistory, PlayerAdjustedStats, PlayerAdvanced, PlayerBatch, PlayerConsistency, PlayerPage, PlayerPercentiles, PlayerResponse, PlayerStats, PlayerSuggestion, Projection, ReboundMatchup, RollingAverages, SplitLine, StatConsistency, StatPercentile, PlayTypeMatchup, PlayTypeMatchupResponse, UpcomingMatchupAllResponse, UpcomingMatchupResponse, UsageSpike};
 use crate::db;
+use crate::error::{ApiError, ErrorResponse};
+use crate::util::freshness::data_as_of;
+use crate::util::rank::rank_of;
+use crate::util::today::Today;
 
 // Query parameters for listing players
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListPlayersQuery {
     #[serde(default)]
     limit: Option<i64>,
     #[serde(default)]
     offset: Option<i64>,
+    /// Wrap the page with the total player count instead of returning a bare array
+    #[serde(default)]
+    paginated: bool,
+    /// Stat column to sort by (points, rebounds, assists, ...; default: player_name)
+    #[serde(default)]
+    sort_by: Option<String>,
+    /// "asc" or "desc" (default: desc for stats, asc for player_name)
+    #[serde(default)]
+    order: Option<String>,
+    #[serde(default)]
+    team_id: Option<i64>,
+    #[serde(default)]
+    min_games: Option<i64>,
+}
+
+// Query parameters for boom/bust rate
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BoomBustQuery {
+    /// Underdog stat name (points, rebounds, pts_rebs_asts, ...)
+    stat: String,
+    line: f64,
+    /// How far past the line a game must land to count as a boom or bust (default: 5)
+    #[serde(default = "default_boom_margin")]
+    margin: f64,
+    /// Number of most recent played games (default: 15, max: 82)
+    #[serde(default = "default_boom_games")]
+    games: i64,
+}
+
+fn default_boom_margin() -> f64 {
+    5.0
+}
+
+fn default_boom_games() -> i64 {
+    15
 }
 
 // Query parameters for searching players
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchQuery {
     name: String,
+    /// Partial, case- and accent-insensitive match, best first (ignored by autocomplete)
+    #[serde(default)]
+    fuzzy: bool,
 }
 
-// GET /api/players - List all players
+// GET /api/players?sort_by=points&order=desc&team_id=123&min_games=10&limit=50&offset=0&paginated=true
+// List players, optionally sorted, filtered and paginated
+#[utoipa::path(
+    get,
+    path = "/api/players",
+    tag = "players",
+    summary = "List players, optionally sorted, filtered and paginated",
+    params(ListPlayersQuery),
+    responses(
+        (status = 200, description = "Array of players, or a `PlayerPage` when `paginated=true`", body = Vec<PlayerResponse>),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_players(
     State(pool): State<SqlitePool>,
     Query(params): Query<ListPlayersQuery>,
-) -> Result<Json<Vec<PlayerStats>>, StatusCode> {
-    // Get all players from database
-    let players = db::get_all_players(&pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Response, ApiError> {
+    if let Some(sort_by) = params.sort_by.as_deref()
+        && !db::PLAYER_SORT_COLUMNS.contains(&sort_by)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid sort_by '{}'. Valid columns: {}",
+            sort_by,
+            db::PLAYER_SORT_COLUMNS.join(", ")
+        )));
+    }
+
+    let sorting_by_name = params.sort_by.as_deref().is_none_or(|col| col == "player_name");
+    let descending = match params.order.as_deref() {
+        Some("asc") => false,
+        Some("desc") => true,
+        Some(other) => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid order '{}'. Use 'asc' or 'desc'",
+                other
+            )))
+        }
+        None => !sorting_by_name,
+    };
 
-    // Apply pagination if provided
-    let start = params.offset.unwrap_or(0) as usize;
-    let end = params.limit.map(|l| start + l as usize).unwrap_or(players.len());
+    let opts = db::PlayerListOptions {
+        sort_by: params.sort_by,
+        descending,
+        team_id: params.team_id,
+        min_games: params.min_games,
+    };
 
-    let paginated = players.into_iter().skip(start).take(end - start).collect();
+    // Negative values are treated as 0; no limit fetches every player
+    let offset = params.offset.unwrap_or(0).max(0);
+    let limit = params.limit.map(|l| l.max(0));
 
-    Ok(Json(paginated))
+    let players = db::get_players_paginated(&pool, limit.unwrap_or(-1), offset, &opts)
+        .await?;
+    let players = with_shooting(&pool, players).await?;
+
+    if !params.paginated {
+        return Ok(Json(players).into_response());
+    }
+
+    let total = db::count_players(&pool, &opts)
+        .await?;
+
+    let data_as_of = data_as_of(players.iter().map(|p| p.stats.last_updated.as_str()));
+    Ok(Json(PlayerPage {
+        players,
+        total,
+        limit,
+        offset,
+        data_as_of,
+    })
+    .into_response())
+}
+
+/// Map players to their API shape, with FG%/eFG% from one batched game-log query
+async fn with_shooting(pool: &SqlitePool, players: Vec<PlayerStats>) -> Result<Vec<PlayerResponse>, sqlx::Error> {
+    let ids: Vec<i64> = players.iter().map(|player| player.player_id).collect();
+    let shooting = db::get_season_shooting_totals(pool, &ids).await?;
+    Ok(players
+        .iter()
+        .map(|player| player.to_response(shooting.get(&player.player_id)))
+        .collect())
+}
+
+/// Maximum number of ids accepted by the batch players endpoint
+const MAX_BATCH_PLAYERS: usize = 100;
+
+// Query parameters for batch player lookup
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BatchPlayersQuery {
+    /// Comma-separated player ids, e.g. "2544,203999"
+    ids: String,
+}
+
+// GET /api/players/batch?ids=2544,203999 - Several players in one request
+// Players come back in the requested order (duplicates dropped); unknown ids are listed in `missing`
+#[utoipa::path(
+    get,
+    path = "/api/players/batch",
+    tag = "players",
+    summary = "Several players in one request",
+    params(BatchPlayersQuery),
+    responses(
+        (status = 200, body = PlayerBatch),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_players_batch(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<BatchPlayersQuery>,
+) -> Result<Json<PlayerBatch>, ApiError> {
+    let mut ids: Vec<i64> = Vec::new();
+    for raw in params.ids.split(',').map(str::trim).filter(|raw| !raw.is_empty()) {
+        let id = raw
+            .parse()
+            .map_err(|_| ApiError::BadRequest(format!("Invalid player id '{}'", raw)))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.len() > MAX_BATCH_PLAYERS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} player ids per request",
+            MAX_BATCH_PLAYERS
+        )));
+    }
+
+    let mut by_id: HashMap<i64, PlayerStats> = db::get_players_by_ids(&pool, &ids)
+        .await?
+        .into_iter()
+        .map(|player| (player.player_id, player))
+        .collect();
+
+    let mut players = Vec::with_capacity(ids.len());
+    let mut missing = Vec::new();
+    for id in ids {
+        match by_id.remove(&id) {
+            Some(player) => players.push(player),
+            None => missing.push(id),
+        }
+    }
+    let players = with_shooting(&pool, players).await?;
+
+    Ok(Json(PlayerBatch { players, missing }))
 }
 
 // GET /api/players/:id - Get player by ID
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}",
+    tag = "players",
+    summary = "Get player by ID",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = PlayerResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_by_id(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
-) -> Result<Json<PlayerStats>, StatusCode> {
+) -> Result<Json<PlayerResponse>, ApiError> {
     let player = db::get_player_by_id(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+    let shooting = db::get_season_shooting_totals(&pool, &[player_id])
+        .await?;
+
+    Ok(Json(player.to_response(shooting.get(&player_id))))
+}
+
+// Query parameters for recent averages
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RecentAveragesQuery {
+    /// Number of most recent played games to average (default: 10, max: 82)
+    #[serde(default = "default_last")]
+    last: i64,
+}
 
-    Ok(Json(player))
+fn default_last() -> i64 {
+    10
 }
 
-// GET /api/players/search?name=LeBron - Search players by name
+// GET /api/players/:id/averages?last=10 - Per-game averages over the last N played games
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/averages",
+    tag = "players",
+    summary = "Per-game averages over the last N played games",
+    params(("id" = i64, Path, description = "Player id"), RecentAveragesQuery),
+    responses(
+        (status = 200, body = PlayerStats),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_recent_averages(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<RecentAveragesQuery>,
+) -> Result<Json<PlayerStats>, ApiError> {
+    // Cap at 82 (max games in a season)
+    let last = params.last.clamp(1, 82);
+
+    let averages = db::get_player_recent_averages(&pool, player_id, last)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("no played games for player {}", player_id)))?;
+
+    Ok(Json(averages))
+}
+
+/// Maximum number of players returned by search and autocomplete
+const MAX_SEARCH_RESULTS: usize = 25;
+
+// GET /api/players/search?name=LeBron&fuzzy=true - Search players by name
+// Exact name matches by default; fuzzy returns partial matches, best first
+#[utoipa::path(
+    get,
+    path = "/api/players/search",
+    tag = "players",
+    summary = "Search players by name",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = Vec<PlayerResponse>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn search_players(
     State(pool): State<SqlitePool>,
     Query(params): Query<SearchQuery>,
-) -> Result<Json<PlayerStats>, StatusCode> {
-    let player = db::search_players(&pool, &params.name)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+) -> Result<Json<Vec<PlayerResponse>>, ApiError> {
+    let players = if params.fuzzy {
+        db::fuzzy_search_players(&pool, &params.name, MAX_SEARCH_RESULTS).await?
+    } else {
+        db::search_players(&pool, &params.name, MAX_SEARCH_RESULTS as i64).await?
+    };
 
-    Ok(Json(player))
+    Ok(Json(with_shooting(&pool, players).await?))
+}
+
+// GET /api/players/autocomplete?name=jok - Partial-name matches with just id, name and team
+#[utoipa::path(
+    get,
+    path = "/api/players/autocomplete",
+    tag = "players",
+    summary = "Partial-name matches with just id, name and team",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = Vec<PlayerSuggestion>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn autocomplete_players(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<SearchQuery>,
+) -> Result<Json<Vec<PlayerSuggestion>>, ApiError> {
+    let players = db::autocomplete_players(&pool, &params.name, MAX_SEARCH_RESULTS)
+        .await?;
+
+    Ok(Json(players))
+}
+
+// Query parameters for usage spikes
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageSpikesQuery {
+    /// Evaluate recent games up to this date (YYYY-MM-DD, default: today)
+    #[serde(default)]
+    date: Option<String>,
+    #[serde(default = "default_spikes_limit")]
+    limit: i64,
+}
+
+fn default_spikes_limit() -> i64 {
+    20
+}
+
+// GET /api/players/usage-spikes?date=2026-01-15 - Players whose last-3-game minutes jumped above their season average
+#[utoipa::path(
+    get,
+    path = "/api/players/usage-spikes",
+    tag = "players",
+    summary = "Players whose last-3-game minutes jumped above their season average",
+    params(UsageSpikesQuery),
+    responses(
+        (status = 200, body = Vec<UsageSpike>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_usage_spikes(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<UsageSpikesQuery>,
+) -> Result<Json<Vec<UsageSpike>>, ApiError> {
+    let date = params.date.unwrap_or_else(|| today.ymd());
+    let limit = params.limit.clamp(1, 100);
+
+    let spikes = db::get_usage_spikes(&pool, &date, &db::CURRENT_SEASON, limit)
+        .await?;
+
+    Ok(Json(spikes))
 }
 
 // GET /api/players/:id/shooting-zones - Get player's shooting zones
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/shooting-zones",
+    tag = "players",
+    summary = "Get player's shooting zones",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = Vec<crate::models::PlayerShootingZones>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_shooting_zones(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
-) -> Result<Json<Vec<crate::models::PlayerShootingZones>>, StatusCode> {
+) -> Result<Json<Vec<crate::models::PlayerShootingZones>>, ApiError> {
     let zones = db::get_shooting_zones(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if zones.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound(format!("no shooting zones for player {}", player_id)));
     }
 
     Ok(Json(zones))
 }
 
+// Query parameters for cached endpoints
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CacheQuery {
+    /// Skip the response cache (the fresh result still replaces the cached one)
+    #[serde(default)]
+    no_cache: bool,
+}
+
 // GET /api/players/:player_id/shooting-zones/vs/:opponent_id - Get shooting zone matchup with league context
+#[utoipa::path(
+    get,
+    path = "/api/players/{player_id}/shooting-zones/vs/{opponent_id}",
+    tag = "players",
+    summary = "Get shooting zone matchup with league context",
+    params(
+        ("player_id" = i64, Path, description = "Player id"),
+        ("opponent_id" = i64, Path, description = "Opponent team id"),
+        CacheQuery,
+    ),
+    responses(
+        (status = 200, body = crate::models::ShootingZoneMatchupResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_shooting_zone_matchup(
     State(pool): State<SqlitePool>,
+    State(cache): State<Arc<MatchupCache>>,
     Path((player_id, opponent_id)): Path<(i64, i64)>,
-) -> Result<Json<crate::models::ShootingZoneMatchupResponse>, StatusCode> {
+    Query(params): Query<CacheQuery>,
+) -> Result<([(&'static str, &'static str); 1], Json<crate::models::ShootingZoneMatchupResponse>), ApiError> {
+    let key = (player_id, opponent_id);
+    if !params.no_cache
+        && let Some(cached) = cache.shooting_zones.get(&key)
+    {
+        return Ok(([(X_CACHE, "HIT")], Json(cached)));
+    }
+
     let matchup = db::get_shooting_zone_matchup(&pool, player_id, opponent_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
+    cache.shooting_zones.insert(key, matchup.clone());
 
-    Ok(Json(matchup))
+    Ok(([(X_CACHE, "MISS")], Json(matchup)))
 }
 
 // GET /api/players/:id/assist-zones - Get player's assist zones
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/assist-zones",
+    tag = "players",
+    summary = "Get player's assist zones",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = Vec<crate::models::PlayerAssistZones>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_assist_zones(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
-) -> Result<Json<Vec<crate::models::PlayerAssistZones>>, StatusCode> {
+) -> Result<Json<Vec<crate::models::PlayerAssistZones>>, ApiError> {
     let zones = db::get_assist_zones(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if zones.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound(format!("no assist zones for player {}", player_id)));
     }
 
     Ok(Json(zones))
 }
 
 // GET /api/players/:id/play-types - Get player's play types breakdown
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/play-types",
+    tag = "players",
+    summary = "Get player's play types breakdown",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = Vec<crate::models::PlayerPlayTypes>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_play_types(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
-) -> Result<Json<Vec<crate::models::PlayerPlayTypes>>, StatusCode> {
+) -> Result<Json<Vec<crate::models::PlayerPlayTypes>>, ApiError> {
     let play_types = db::get_player_playtypes(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if play_types.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound(format!("no play types for player {}", player_id)));
     }
 
     Ok(Json(play_types))
 }
 
 // Query parameters for game logs
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GameLogsQuery {
     /// Number of games to return (default: 20, max: 82)
     /// Matches the "games" slider in the frontend UI
@@ -138,24 +516,150 @@ pub struct GameLogsQuery {
     /// Stat category for DNP players (points, assists, rebounds, etc.)
     /// Used to determine which stat to show for DNP players
     stat_category: Option<String>,
+    /// Attach trailing 5/10-game pts/reb/ast averages to each game
+    #[serde(default)]
+    include_rolling: bool,
+    /// Wrap the logs as `{ logs, streaks }` with the player's active streaks this season
+    #[serde(default)]
+    include_streaks: bool,
 }
 
 fn default_limit() -> i64 {
     20
 }
 
+/// Thresholds checked for active streaks: (Underdog stat name, threshold, label noun)
+const STREAK_THRESHOLDS: [(&str, i32, &str); 8] = [
+    ("points", 10, "points"),
+    ("points", 20, "points"),
+    ("points", 30, "points"),
+    ("rebounds", 10, "rebounds"),
+    ("assists", 10, "assists"),
+    ("three_points_made", 3, "threes"),
+    ("blks_stls", 3, "stocks"),
+    ("pts_rebs_asts", 40, "PRA"),
+];
+
+/// Shortest run reported as a streak
+const MIN_STREAK_GAMES: i64 = 2;
+
+/// Active streaks in `logs` (newest first) for each of `STREAK_THRESHOLDS`, longest first.
+/// Walks played games oldest to newest, resetting a run on any miss, so what's left at
+/// the end is the streak still alive. DNPs neither extend nor break a streak.
+fn active_streaks(logs: &[crate::models::PlayerGameLog]) -> Vec<crate::models::Streak> {
+    let mut streaks: Vec<_> = STREAK_THRESHOLDS
+        .iter()
+        .filter_map(|&(stat, threshold, noun)| {
+            let games = logs
+                .iter()
+                .rev()
+                .filter(|log| log.min.is_some_and(|m| m > 0.0))
+                .fold(0i64, |run, log| match log.underdog_stat(stat) {
+                    Some(value) if value >= threshold => run + 1,
+                    _ => 0,
+                });
+            (games >= MIN_STREAK_GAMES).then(|| crate::models::Streak {
+                stat: stat.to_string(),
+                threshold,
+                games,
+                label: format!("{}+ {} in {} straight games", threshold, noun, games),
+            })
+        })
+        .collect();
+    streaks.sort_by_key(|streak| std::cmp::Reverse(streak.games));
+    streaks
+}
+
+/// Trailing 5- and 10-game pts/reb/ast averages for each log, up to and including that
+/// game. `logs` are newest-first and the result is parallel to them. Windows are filled
+/// with played games only (DNPs skipped), and short windows average whatever exists.
+fn rolling_averages(logs: &[crate::models::PlayerGameLog]) -> Vec<RollingAverages> {
+    fn window_avg(played: &[(i32, i32, i32)], size: usize) -> (Option<f32>, Option<f32>, Option<f32>) {
+        let window = &played[played.len().saturating_sub(size)..];
+        if window.is_empty() {
+            return (None, None, None);
+        }
+        let n = window.len() as f32;
+        (
+            Some(window.iter().map(|g| g.0).sum::<i32>() as f32 / n),
+            Some(window.iter().map(|g| g.1).sum::<i32>() as f32 / n),
+            Some(window.iter().map(|g| g.2).sum::<i32>() as f32 / n),
+        )
+    }
+
+    // Walk chronologically, oldest game first
+    let mut played: Vec<(i32, i32, i32)> = Vec::new();
+    let mut averages: Vec<RollingAverages> = logs
+        .iter()
+        .rev()
+        .map(|log| {
+            if log.min.is_some_and(|m| m > 0.0) {
+                played.push((log.pts.unwrap_or(0), log.reb.unwrap_or(0), log.ast.unwrap_or(0)));
+            }
+            let (pts_l5, reb_l5, ast_l5) = window_avg(&played, 5);
+            let (pts_l10, reb_l10, ast_l10) = window_avg(&played, 10);
+            RollingAverages { pts_l5, reb_l5, ast_l5, pts_l10, reb_l10, ast_l10 }
+        })
+        .collect();
+
+    averages.reverse();
+    averages
+}
+
 // GET /api/players/:id/game-logs - Get player's game-by-game stats with DNP players
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/game-logs",
+    tag = "players",
+    summary = "Get player's game-by-game stats with DNP players",
+    params(("id" = i64, Path, description = "Player id"), GameLogsQuery),
+    responses(
+        (status = 200, body = crate::models::GameLogsResult),
+        (status = 400, description = "Invalid stat_category", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_game_logs(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
     Query(params): Query<GameLogsQuery>,
-) -> Result<Json<Vec<crate::models::GameLogWithDnp>>, StatusCode> {
+) -> Result<Json<crate::models::GameLogsResult>, ApiError> {
+    // Get stat column name for DNP queries
+    let stat_column = params.stat_category.as_deref().unwrap_or("points");
+    if !db::DNP_STAT_COLUMNS.contains(&stat_column) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid stat_category '{}'. Valid categories: {}",
+            stat_column,
+            db::DNP_STAT_COLUMNS.join(", ")
+        )));
+    }
+
     // Cap limit at 82 (max games in a season)
     let limit = params.limit.min(82);
 
-    let game_logs = db::get_player_game_logs(&pool, player_id, limit)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Rolling windows for the oldest returned games need up to 9 earlier games
+    let fetch_limit = if params.include_rolling { limit + 9 } else { limit };
+
+    let mut game_logs = db::get_player_game_logs(&pool, player_id, fetch_limit)
+        .await?;
+
+    let mut rolling = if params.include_rolling {
+        rolling_averages(&game_logs).into_iter().map(Some).collect()
+    } else {
+        vec![None; game_logs.len()]
+    };
+
+    // Streaks look back over the current season only, so one can't carry over the summer
+    let streaks = if params.include_streaks {
+        let opts = db::TrailingLogOptions { season: Some(db::CURRENT_SEASON.clone()), ..Default::default() };
+        let season_logs = db::get_player_trailing_logs(&pool, player_id, 82, &opts)
+            .await?;
+        Some(active_streaks(&season_logs))
+    } else {
+        None
+    };
+    game_logs.truncate(limit.max(0) as usize);
+    rolling.truncate(game_logs.len());
 
     // Get the player's current team from player_stats
     let player_team_id: Option<i64> = sqlx::query_scalar(
@@ -163,173 +667,1210 @@ pub async fn get_player_game_logs(
     )
     .bind(player_id)
     .fetch_optional(&pool)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .await?
     .flatten();
 
-    // Get stat column name for DNP queries
-    let stat_column = params.stat_category.as_deref().unwrap_or("points");
-
-    // For each game, get DNP players from the SAME team (teammates)
+    // DNP players from the SAME team (teammates) for every game, fetched in one go
     // DNP teammates affect playing time and usage for the player
-    let mut logs_with_dnp = Vec::new();
-
-    for game_log in game_logs {
-        let dnp_players = if let Some(team_id) = player_team_id {
-            db::get_dnp_players_for_game(&pool, &game_log.game_id, team_id, stat_column)
-                .await
-                .unwrap_or_default()
-        } else {
-            vec![]
-        };
+    let mut dnp_by_game = if let Some(team_id) = player_team_id {
+        let game_ids: Vec<String> = game_logs.iter().map(|log| log.game_id.clone()).collect();
+        db::get_dnp_players_for_games(&pool, &game_ids, team_id, stat_column)
+            .await
+            .unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
 
-        logs_with_dnp.push(crate::models::GameLogWithDnp {
+    let logs_with_dnp: Vec<_> = game_logs
+        .into_iter()
+        .zip(rolling)
+        .map(|(game_log, rolling)| crate::models::GameLogWithDnp {
+            dnp_players: dnp_by_game.remove(&game_log.game_id).unwrap_or_default(),
             game_log,
-            dnp_players,
-        });
+            rolling,
+        })
+        .collect();
+
+    Ok(Json(match streaks {
+        Some(streaks) => crate::models::GameLogsResult::WithStreaks(crate::models::GameLogsWithStreaks {
+            logs: logs_with_dnp,
+            streaks,
+        }),
+        None => crate::models::GameLogsResult::Logs(logs_with_dnp),
+    }))
+}
+
+// Query parameters for the game log CSV export
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GameLogsCsvQuery {
+    /// Number of most recent games to export (default: 82, max: 82)
+    #[serde(default = "default_csv_limit")]
+    limit: i64,
+}
+
+fn default_csv_limit() -> i64 {
+    82
+}
+
+// GET /api/players/:id/game-logs.csv?limit=82 - Game logs as a CSV download, newest first
+// Columns follow GAME_LOG_CSV_COLUMNS; missing stats are empty cells
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/game-logs.csv",
+    tag = "players",
+    summary = "Game logs as a CSV download, newest first",
+    params(("id" = i64, Path, description = "Player id"), GameLogsCsvQuery),
+    responses(
+        (status = 200, description = "CSV with a header row", body = String, content_type = "text/csv"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_game_logs_csv(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<GameLogsCsvQuery>,
+) -> Result<Response, ApiError> {
+    let game_logs = db::get_player_game_logs(&pool, player_id, params.limit.clamp(0, 82))
+        .await?;
+
+    let mut csv = GAME_LOG_CSV_COLUMNS.join(",");
+    csv.push('\n');
+    for game_log in &game_logs {
+        csv.push_str(&game_log.csv_row());
+        csv.push('\n');
+    }
+
+    let disposition = format!("attachment; filename=\"player_{}_game_logs.csv\"", player_id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    )
+        .into_response())
+}
+
+// GET /api/players/:id/data-coverage - Which datasets exist for a player
+// 404 only when the player appears in none of them
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/data-coverage",
+    tag = "players",
+    summary = "Which datasets exist for a player",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = DataCoverage),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_data_coverage(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Path(player_id): Path<i64>,
+) -> Result<Json<DataCoverage>, ApiError> {
+    let mut coverage = db::get_player_data_coverage(&pool, player_id)
+        .await?;
+
+    coverage.has_props = !db::get_player_props_by_id(&pool, player_id, &today)
+        .await?
+        .is_empty();
+
+    let has_any = coverage.season_stats
+        || coverage.shooting_zones > 0
+        || coverage.assist_zones > 0
+        || coverage.play_types > 0
+        || coverage.game_logs > 0;
+    if !has_any {
+        return Err(ApiError::NotFound(format!("player {} not found", player_id)));
     }
 
-    Ok(Json(logs_with_dnp))
+    Ok(Json(coverage))
+}
+
+// GET /api/players/:id/splits/home-away - Get player's home vs away averages
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/splits/home-away",
+    tag = "players",
+    summary = "Get player's home vs away averages",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = HomeAwaySplits),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_home_away_splits(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+) -> Result<Json<HomeAwaySplits>, ApiError> {
+    let splits = db::get_home_away_splits(&pool, player_id)
+        .await?;
+
+    if splits.games_home == 0 && splits.games_away == 0 {
+        return Err(ApiError::NotFound(format!("no games for player {}", player_id)));
+    }
+
+    Ok(Json(splits))
+}
+
+/// Below this many minutes per game, per-36 rates are too noisy to report
+const MIN_ADVANCED_MINUTES: f64 = 10.0;
+
+// GET /api/players/:id/advanced - Get player's per-36 production and usage proxy
+// Season averages scaled by 36 / minutes per game, with minutes from the game logs
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/advanced",
+    tag = "players",
+    summary = "Get player's per-36 production and usage proxy",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = PlayerAdvanced),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_advanced(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+) -> Result<Json<PlayerAdvanced>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+    let minutes = db::get_player_minutes_per_game(&pool, player_id, &player.season)
+        .await?;
+
+    let per36_scale = minutes.filter(|&mpg| mpg >= MIN_ADVANCED_MINUTES).map(|mpg| 36.0 / mpg);
+    let per36 = |value: f32| per36_scale.map(|scale| (value as f64 * scale * 10.0).round() / 10.0);
+    let usage = player.fg_attempted.unwrap_or(0.0) + 0.44 * player.ft_attempted + player.turnovers;
+
+    Ok(Json(PlayerAdvanced {
+        player_id,
+        minutes_per_game: minutes.map(|mpg| (mpg * 10.0).round() / 10.0),
+        pts_per36: per36(player.points),
+        reb_per36: per36(player.rebounds),
+        ast_per36: per36(player.assists),
+        stl_per36: per36(player.steals),
+        blk_per36: per36(player.blocks),
+        usage_proxy: (usage as f64 * 10.0).round() / 10.0,
+        usage_per36: per36(usage),
+        player_name: player.player_name,
+    }))
+}
+
+// GET /api/players/:id/vs/:opponent_id/history - Get player's games this season against one opponent
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/vs/{opponent_id}/history",
+    tag = "players",
+    summary = "Get player's games this season against one opponent",
+    params(
+        ("id" = i64, Path, description = "Player id"),
+        ("opponent_id" = i64, Path, description = "Opponent team id"),
+    ),
+    responses(
+        (status = 200, body = OpponentHistory),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_opponent_history(
+    State(pool): State<SqlitePool>,
+    Path((player_id, opponent_id)): Path<(i64, i64)>,
+) -> Result<Json<OpponentHistory>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let opponent = db::get_team_by_id(&pool, opponent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", opponent_id)))?;
+
+    // Opponent is derived from the schedule join, since the matchup string is unreliable
+    let opts = db::TrailingLogOptions {
+        opponent_id: Some(opponent_id),
+        season: Some(player.season.clone()),
+        ..Default::default()
+    };
+    let games = db::get_player_trailing_logs(&pool, player_id, 82, &opts)
+        .await?;
+
+    let (averages, games_played) = SplitLine::from_logs(&games);
+
+    Ok(Json(OpponentHistory {
+        player_name: player.player_name,
+        opponent_name: opponent.full_name,
+        season: player.season,
+        games_played,
+        averages,
+        games,
+    }))
+}
+
+// GET /api/players/:id/boom-bust?stat=points&line=24.5&margin=5&games=15
+// Share of recent played games that beat the line by at least `margin` (boom), fell short
+// of it by at least `margin` (bust), or landed in between (neutral)
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/boom-bust",
+    tag = "players",
+    summary = "Boom, bust and neutral rates against a prop line",
+    params(("id" = i64, Path, description = "Player id"), BoomBustQuery),
+    responses(
+        (status = 200, body = BoomBust),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_boom_bust(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<BoomBustQuery>,
+) -> Result<Json<BoomBust>, ApiError> {
+    let games = params.games.clamp(1, 82);
+    let margin = params.margin.abs();
+
+    let opts = db::TrailingLogOptions {
+        exclude_dnp: true,
+        ..Default::default()
+    };
+    let logs = db::get_player_trailing_logs(&pool, player_id, games, &opts)
+        .await?;
+
+    if logs.is_empty() {
+        return Err(ApiError::NotFound(format!("no played games for player {}", player_id)));
+    }
+
+    let values: Vec<f64> = logs
+        .iter()
+        .filter_map(|log| log.underdog_stat(&params.stat))
+        .map(f64::from)
+        .collect();
+
+    // Games exist but none map to this stat, so the stat name is unknown
+    if values.is_empty() {
+        return Err(ApiError::BadRequest(format!("Unknown stat '{}'", params.stat)));
+    }
+
+    Ok(Json(classify_boom_bust(params.stat, params.line, margin, &values)))
+}
+
+/// Count the games at least `margin` over the line (boom) or under it (bust); a game
+/// landing exactly `margin` away counts. `values` must not be empty.
+fn classify_boom_bust(stat: String, line: f64, margin: f64, values: &[f64]) -> BoomBust {
+    let total = values.len() as i64;
+    let booms = values.iter().filter(|&&v| v >= line + margin).count() as i64;
+    let busts = values.iter().filter(|&&v| v <= line - margin).count() as i64;
+    let pct = |count: i64| count as f32 / total as f32 * 100.0;
+
+    BoomBust {
+        stat,
+        line,
+        margin,
+        games: total,
+        booms,
+        busts,
+        boom_pct: pct(booms),
+        bust_pct: pct(busts),
+        neutral_pct: pct(total - booms - busts),
+    }
+}
+
+// Query parameters for fantasy consistency
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FantasyConsistencyQuery {
+    /// Scoring rules: "dk" (DraftKings, default) or "fd" (FanDuel)
+    #[serde(default = "default_scoring")]
+    scoring: String,
+    /// Number of most recent played games this season (default: 15, max: 82)
+    #[serde(default = "default_boom_games")]
+    games: i64,
+}
+
+fn default_scoring() -> String {
+    "dk".to_string()
+}
+
+/// Opponent defensive rating over the league average: above 1 for a weak defense, below 1
+/// for a strong one, 1 when either is unknown. Dividing a stat by it adjusts for the opponent.
+fn def_rating_factor(opp_def_rating: Option<f32>, league_def_rating: Option<f32>) -> f32 {
+    match (opp_def_rating, league_def_rating) {
+        (Some(opp), Some(avg)) if opp > 0.0 && avg > 0.0 => opp / avg,
+        _ => 1.0,
+    }
+}
+
+// GET /api/players/:id/fantasy-consistency?scoring=dk&games=15
+// Mean, spread and consistency score of opponent-adjusted fantasy points
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/fantasy-consistency",
+    tag = "players",
+    summary = "Mean, spread and consistency score of opponent-adjusted fantasy points",
+    params(
+        ("id" = i64, Path, description = "Player id"),
+        FantasyConsistencyQuery,
+    ),
+    responses(
+        (status = 200, body = FantasyConsistency),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_fantasy_consistency(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<FantasyConsistencyQuery>,
+) -> Result<Json<FantasyConsistency>, ApiError> {
+    let scoring = FantasyScoring::parse(&params.scoring).ok_or_else(|| {
+        ApiError::BadRequest(format!("Unknown scoring '{}'. Use 'dk' or 'fd'", params.scoring))
+    })?;
+    let games = params.games.clamp(1, 82);
+
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    // This season only, since opponent ratings are this season's
+    let opts = db::TrailingLogOptions {
+        exclude_dnp: true,
+        season: Some(db::CURRENT_SEASON.clone()),
+        ..Default::default()
+    };
+    let logs = db::get_player_trailing_logs(&pool, player_id, games, &opts)
+        .await?;
+
+    // Opponent strength: DefRtg relative to the league, neutral when unknown
+    let game_ids: Vec<String> = logs.iter().map(|log| log.game_id.clone()).collect();
+    let game_teams = db::get_game_teams(&pool, &game_ids).await?;
+    let team_stats = db::get_all_team_stats(&pool, &db::CURRENT_SEASON).await?;
+    let league = db::get_league_team_averages(&pool, &db::CURRENT_SEASON).await?;
+    let opp_factor = |opponent_id: Option<i64>| {
+        let opp_def = team_stats
+            .iter()
+            .find(|t| Some(t.team_id) == opponent_id)
+            .and_then(|t| t.def_rating);
+        def_rating_factor(opp_def, league.def_rating)
+    };
+
+    let game_log: Vec<FantasyGame> = logs
+        .iter()
+        .filter_map(|log| {
+            let fantasy_points = log.fantasy_points(scoring)?;
+            let opponent_id = game_teams.get(&log.game_id).map(|&(home, away)| {
+                if log.team_id == Some(home) { away } else { home }
+            });
+            let opp_factor = opp_factor(opponent_id);
+            Some(FantasyGame {
+                game_id: log.game_id.clone(),
+                game_date: log.game_date.clone(),
+                opponent_id,
+                fantasy_points,
+                opp_factor,
+                adjusted_points: fantasy_points / opp_factor,
+            })
+        })
+        .collect();
+
+    if game_log.is_empty() {
+        return Err(ApiError::NotFound(format!("no played games for player {}", player_id)));
+    }
+
+    let (mean, std_dev) = mean_std(game_log.iter().map(|g| g.fantasy_points));
+    let (adjusted_mean, adjusted_std_dev) = mean_std(game_log.iter().map(|g| g.adjusted_points));
+    let consistency = consistency_score(adjusted_mean, adjusted_std_dev);
+
+    Ok(Json(FantasyConsistency {
+        player_name: player.player_name,
+        scoring: scoring.as_str().to_string(),
+        games: game_log.len() as i64,
+        mean,
+        std_dev,
+        adjusted_mean,
+        adjusted_std_dev,
+        consistency,
+        game_log,
+    }))
+}
+
+/// Season-average columns ranked by the percentiles endpoint
+const PERCENTILE_STATS: [&str; 9] = [
+    "points", "rebounds", "assists", "threes_made", "steals", "blocks", "turnovers",
+    "pts_plus_ast_plus_reb", "steals_plus_blocks",
+];
+
+/// Games played a player needs to count toward the percentile reference pool
+const MIN_PERCENTILE_GAMES: i64 = 10;
+
+/// Share of `sorted` below `value`, with ties counting half, as 0-100 to one decimal.
+/// None for an empty pool.
+fn percentile_of(sorted: &[f32], value: f32) -> Option<f32> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let below = sorted.partition_point(|&v| v < value);
+    let ties = sorted.partition_point(|&v| v <= value) - below;
+    let pct = (below as f32 + ties as f32 / 2.0) / sorted.len() as f32 * 100.0;
+    Some((pct * 10.0).round() / 10.0)
+}
+
+// GET /api/players/:id/percentiles
+// League percentile (0-100) of the player's season averages among qualified players
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/percentiles",
+    tag = "players",
+    summary = "League percentile ranks of a player's season averages",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = PlayerPercentiles),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_percentiles(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+) -> Result<Json<PlayerPercentiles>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let data_version = db::get_player_stats_data_version(&pool).await?;
+    let distributions = match cache::STAT_DISTRIBUTIONS.get(&data_version) {
+        Some(cached) => cached,
+        None => {
+            let qualified = db::get_qualified_player_stats(&pool, MIN_PERCENTILE_GAMES).await?;
+            let distributions: HashMap<String, Vec<f32>> = PERCENTILE_STATS
+                .iter()
+                .map(|&stat| {
+                    let mut values: Vec<f32> = qualified.iter().filter_map(|p| p.season_avg(stat)).collect();
+                    values.sort_by(|a, b| a.total_cmp(b));
+                    (stat.to_string(), values)
+                })
+                .collect();
+            cache::STAT_DISTRIBUTIONS.put(&data_version, distributions.clone());
+            distributions
+        }
+    };
+
+    let percentiles = PERCENTILE_STATS
+        .iter()
+        .filter_map(|&stat| {
+            let value = player.season_avg(stat)?;
+            let sorted = distributions.get(stat).map(Vec::as_slice).unwrap_or_default();
+            let percentile = percentile_of(sorted, value);
+            Some(StatPercentile { stat: stat.to_string(), value, percentile })
+        })
+        .collect();
+
+    Ok(Json(PlayerPercentiles {
+        player_id,
+        pool_size: distributions.get(PERCENTILE_STATS[0]).map_or(0, |v| v.len() as i64),
+        player_name: player.player_name,
+        games_played: player.games_played,
+        min_games: MIN_PERCENTILE_GAMES,
+        qualified: player.games_played >= MIN_PERCENTILE_GAMES,
+        percentiles,
+    }))
+}
+
+// Query parameters for stat consistency
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConsistencyQuery {
+    /// Number of most recent played games (default: 15, max: 82)
+    #[serde(default = "default_boom_games")]
+    games: i64,
+}
+
+// GET /api/players/:id/consistency?games=15
+// Coefficient of variation, floor and ceiling of pts/reb/ast over recent played games
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/consistency",
+    tag = "players",
+    summary = "Volatility and consistency score of pts/reb/ast over recent games",
+    params(("id" = i64, Path, description = "Player id"), ConsistencyQuery),
+    responses(
+        (status = 200, body = PlayerConsistency),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_consistency(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<ConsistencyQuery>,
+) -> Result<Json<PlayerConsistency>, ApiError> {
+    let games = params.games.clamp(1, 82);
+
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let opts = db::TrailingLogOptions {
+        exclude_dnp: true,
+        ..Default::default()
+    };
+    let logs = db::get_player_trailing_logs(&pool, player_id, games, &opts)
+        .await?;
+
+    if logs.is_empty() {
+        return Err(ApiError::NotFound(format!("no played games for player {}", player_id)));
+    }
+
+    let consistency = |values: Vec<i32>| {
+        let (mean, std_dev) = mean_std(values.iter().map(|&v| v as f32));
+        StatConsistency {
+            mean,
+            std_dev,
+            coefficient_of_variation: (mean > 0.0).then(|| std_dev / mean),
+            min: values.iter().copied().min().unwrap_or(0),
+            max: values.iter().copied().max().unwrap_or(0),
+            consistency_score: consistency_score(mean, std_dev),
+        }
+    };
+
+    Ok(Json(PlayerConsistency {
+        player_name: player.player_name,
+        games: logs.len() as i64,
+        points: consistency(logs.iter().map(|log| log.pts.unwrap_or(0)).collect()),
+        rebounds: consistency(logs.iter().map(|log| log.reb.unwrap_or(0)).collect()),
+        assists: consistency(logs.iter().map(|log| log.ast.unwrap_or(0)).collect()),
+    }))
+}
+
+// GET /api/players/:id/adjusted - Scoring average adjusted for the defenses faced
+// Each game is scaled by league-average DefRtg / opponent DefRtg (current season), see
+// PlayerAdjustedStats
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/adjusted",
+    tag = "players",
+    summary = "Scoring average raw and adjusted for opponent defensive rating",
+    params(("id" = i64, Path, description = "Player id")),
+    responses(
+        (status = 200, body = PlayerAdjustedStats),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_adjusted_stats(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+) -> Result<Json<PlayerAdjustedStats>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let season = db::CURRENT_SEASON.clone();
+    let games = db::get_player_games_vs_defense(&pool, player_id, &season)
+        .await?;
+
+    if games.is_empty() {
+        return Err(ApiError::NotFound(format!(
+            "no played games for player {} in {}",
+            player_id, season
+        )));
+    }
+
+    let league = db::get_league_team_averages(&pool, &season)
+        .await?;
+    let rated: Vec<f32> = games.iter().filter_map(|g| g.opp_def_rating).collect();
+    let n = games.len() as f32;
+
+    Ok(Json(PlayerAdjustedStats {
+        player_id,
+        player_name: player.player_name,
+        season,
+        games: games.len() as i64,
+        games_with_rating: rated.len() as i64,
+        raw_points: games.iter().map(|g| g.pts as f32).sum::<f32>() / n,
+        adjusted_points: games
+            .iter()
+            .map(|g| g.pts as f32 / def_rating_factor(g.opp_def_rating, league.def_rating))
+            .sum::<f32>() / n,
+        avg_opp_def_rating: (!rated.is_empty()).then(|| rated.iter().sum::<f32>() / rated.len() as f32),
+        league_avg_def_rating: league.def_rating,
+    }))
+}
+
+/// Mean and population standard deviation of `values` (0s when empty)
+fn mean_std(values: impl Iterator<Item = f32> + Clone) -> (f32, f32) {
+    let n = values.clone().count();
+    if n == 0 {
+        return (0.0, 0.0);
+    }
+    let mean = values.clone().sum::<f32>() / n as f32;
+    let variance = values.map(|v| (v - mean).powi(2)).sum::<f32>() / n as f32;
+    (mean, variance.sqrt())
+}
+
+/// 100 * (1 - coefficient of variation), floored at 0; 0 when the mean isn't positive
+fn consistency_score(mean: f32, std_dev: f32) -> f32 {
+    if mean > 0.0 {
+        (100.0 * (1.0 - std_dev / mean)).max(0.0)
+    } else {
+        0.0
+    }
+}
+
+// Query parameters for play type matchup
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PlayTypeMatchupQuery {
+    opponent_id: i64,
+}
+
+// GET /api/players/:id/play-type-matchup?opponent_id=123 - Get player's play type matchup vs opponent
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/play-type-matchup",
+    tag = "players",
+    summary = "Get player's play type matchup vs opponent",
+    params(("id" = i64, Path, description = "Player id"), PlayTypeMatchupQuery),
+    responses(
+        (status = 200, body = PlayTypeMatchupResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_play_type_matchup(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<PlayTypeMatchupQuery>,
+) -> Result<Json<PlayTypeMatchupResponse>, ApiError> {
+    // Get player info
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    // Get opponent team info
+    let opponent = db::get_team_by_id(&pool, params.opponent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", params.opponent_id)))?;
+
+    // Get player play types
+    let player_play_types = db::get_player_playtypes(&pool, player_id)
+        .await?;
+
+    // Get opponent defensive play types
+    let opp_defense = db::get_defensive_play_types(&pool, params.opponent_id)
+        .await?;
+
+    // Get all team defensive rankings
+    let ranks = db::get_team_defensive_play_type_ranks(&pool)
+        .await?;
+
+    // Build matchup data
+    let mut matchups: Vec<PlayTypeMatchup> = player_play_types
+        .iter()
+        .filter_map(|pt| {
+            // Find opponent's defensive stats for this play type
+            let opp_def = opp_defense.iter().find(|d| d.play_type == pt.play_type)?;
+            let rank = ranks.get(&(params.opponent_id, pt.play_type.clone())).copied().unwrap_or(0);
+
+            Some(PlayTypeMatchup {
+                play_type: pt.play_type.clone(),
+                player_ppg: pt.points_per_game,
+                pct_of_total: pt.pct_of_total_points,
+                opp_ppp: opp_def.ppp,
+                opp_rank: rank,
+            })
+        })
+        .collect();
+
+    // Sort by player PPG descending
+    matchups.sort_by(|a, b| b.player_ppg.partial_cmp(&a.player_ppg).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(Json(PlayTypeMatchupResponse {
+        player_name: player.player_name,
+        opponent_name: opponent.full_name,
+        matchups,
+    }))
+}
+
+// GET /api/players/:id/attack-point?opponent_id=123 - How the opponent defends the player's top play type
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/attack-point",
+    tag = "players",
+    summary = "How the opponent defends the player's top play type",
+    params(("id" = i64, Path, description = "Player id"), PlayTypeMatchupQuery),
+    responses(
+        (status = 200, body = AttackPoint),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_attack_point(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<PlayTypeMatchupQuery>,
+) -> Result<Json<AttackPoint>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let opponent = db::get_team_by_id(&pool, params.opponent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", params.opponent_id)))?;
+
+    let player_play_types = db::get_player_playtypes(&pool, player_id)
+        .await?;
+    let opp_defense = db::get_defensive_play_types(&pool, params.opponent_id)
+        .await?;
+
+    // Dominant play type by share of the player's points
+    let top = player_play_types
+        .iter()
+        .max_by(|a, b| {
+            a.pct_of_total_points
+                .partial_cmp(&b.pct_of_total_points)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .ok_or_else(|| ApiError::NotFound(format!("no play types for player {}", player_id)))?;
+    let opp_def = opp_defense.iter().find(|d| d.play_type == top.play_type);
+
+    let ranks = db::get_team_defensive_play_type_ranks(&pool)
+        .await?;
+    let league_avg_ppp = db::get_league_avg_play_type_ppp(&pool, &top.play_type)
+        .await?;
+
+    // Thirds of the teams ranked on this play type, which may not be the whole league
+    let opp_rank = ranks.get(&(params.opponent_id, top.play_type.clone())).copied();
+    let teams_ranked = db::teams_ranked(&ranks, &top.play_type);
+    let verdict = opp_rank.map_or("no_data", |rank| matchup_verdict(rank, teams_ranked));
+
+    Ok(Json(AttackPoint {
+        player_name: player.player_name,
+        opponent_name: opponent.full_name,
+        play_type: top.play_type.clone(),
+        pct_of_total: top.pct_of_total_points,
+        player_ppg: top.points_per_game,
+        opp_ppp: opp_def.map(|d| d.ppp),
+        opp_rank,
+        teams_ranked,
+        league_avg_ppp,
+        verdict: verdict.to_string(),
+    }))
+}
+
+/// Verdict for a defensive rank among `teams_ranked` teams where 1 is the best defense: the
+/// bottom third is a spot to attack, the top third one to avoid
+fn matchup_verdict(opp_rank: i32, teams_ranked: i32) -> &'static str {
+    if opp_rank > teams_ranked * 2 / 3 {
+        "attack"
+    } else if opp_rank <= teams_ranked / 3 {
+        "avoid"
+    } else {
+        "neutral"
+    }
+}
+
+// Matchup grade weighting:
+// 1. Each shooting zone the player shoots from (with opponent data) is a factor weighted by
+//    its share of the player's FGA; each play type the opponent is ranked on is weighted by
+//    its share of the player's points. Both shares are stored as 0-100 percentages and
+//    scaled to fractions, so zone and play-type impacts compare on the same footing.
+// 2. A factor's edge maps the opponent's rank onto -1 (best defense) .. +1 (worst); its
+//    impact is weight * edge.
+// 3. Zones and play types are each reduced to a weighted mean edge, then blended by
+//    ZONE_GRADE_WEIGHT.
+// 4. score = 50 + 50 * blended edge, lettered by GRADE_CUTOFFS.
+
+/// Share of the matchup grade from shooting zones; play types get the rest. When either
+/// side has no factors the other carries the whole grade.
+const ZONE_GRADE_WEIGHT: f32 = 0.5;
+
+/// Lowest score (0-100) for each letter grade, best first; anything lower is an F
+const GRADE_CUTOFFS: [(f32, &str); 7] = [
+    (80.0, "A+"),
+    (70.0, "A"),
+    (62.0, "B+"),
+    (55.0, "B"),
+    (50.0, "C+"),
+    (45.0, "C"),
+    (35.0, "D"),
+];
+
+/// Factors surfaced on each side of a matchup grade
+const GRADE_TOP_FACTORS: usize = 2;
+
+/// Defensive rank among `teams_ranked` teams mapped linearly onto -1 (rank 1, the best
+/// defense) .. +1 (the worst)
+fn rank_edge(opp_rank: i32, teams_ranked: i32) -> f32 {
+    if teams_ranked <= 1 {
+        return 0.0;
+    }
+    (2 * opp_rank - (teams_ranked + 1)) as f32 / (teams_ranked - 1) as f32
+}
+
+/// Weighted mean edge of `factors`, or None when they carry no weight
+fn weighted_edge(factors: &[MatchupFactor]) -> Option<f32> {
+    let total: f32 = factors.iter().map(|f| f.weight).sum();
+    (total > 0.0).then(|| factors.iter().map(|f| f.impact).sum::<f32>() / total)
+}
+
+/// Edge (-1..1) onto the 0-100 grade scale, 50 being a neutral matchup
+fn edge_score(edge: f32) -> f32 {
+    50.0 + 50.0 * edge.clamp(-1.0, 1.0)
+}
+
+fn letter_grade(score: f32) -> &'static str {
+    GRADE_CUTOFFS
+        .iter()
+        .find(|(min, _)| score >= *min)
+        .map_or("F", |(_, grade)| grade)
 }
 
-// Helper to get opponent team ID from a game
-async fn get_opponent_team_id(
-    pool: &SqlitePool,
-    game_id: &str,
-    player_team_id: Option<i64>,
-) -> Result<Option<i64>, sqlx::Error> {
-    if player_team_id.is_none() {
-        return Ok(None);
-    }
+// GET /api/players/:id/matchup-grade?opponent_id=123 - Letter grade for the player's matchup
+// Favorable and unfavorable factors are the two with the largest positive and negative impact
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/matchup-grade",
+    tag = "players",
+    summary = "Letter grade for the player's matchup against an opponent's defense",
+    params(("id" = i64, Path, description = "Player id"), PlayTypeMatchupQuery),
+    responses(
+        (status = 200, body = MatchupGrade),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_matchup_grade(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<PlayTypeMatchupQuery>,
+) -> Result<Json<MatchupGrade>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let opponent = db::get_team_by_id(&pool, params.opponent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", params.opponent_id)))?;
 
-    let player_team = player_team_id.unwrap();
+    let zone_matchup = db::get_shooting_zone_matchup(&pool, player_id, params.opponent_id)
+        .await?;
+    let player_play_types = db::get_player_playtypes(&pool, player_id)
+        .await?;
+    let zone_ranks = db::compute_zone_ranks(&pool)
+        .await?;
+    let ranks = db::get_team_defensive_play_type_ranks(&pool)
+        .await?;
+
+    // Edges scale against the teams ranked on that zone or play type, not the whole league
+    let factor = |kind: &str, name: &str, weight: f32, opp_rank: i32, teams_ranked: i32| {
+        let edge = rank_edge(opp_rank, teams_ranked);
+        MatchupFactor {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            weight,
+            opp_rank,
+            teams_ranked,
+            edge,
+            impact: weight * edge,
+        }
+    };
 
-    let result: Option<(i64, i64)> = sqlx::query_as(
-        r#"SELECT home_team_id, away_team_id FROM schedule WHERE game_id = ?"#
-    )
-    .bind(game_id)
-    .fetch_optional(pool)
-    .await?;
+    let zones: Vec<MatchupFactor> = zone_matchup
+        .zones
+        .iter()
+        .filter(|z| z.has_data && z.player_fga > 0.0)
+        .filter_map(|z| {
+            let teams_ranked = db::teams_ranked(&zone_ranks, &z.zone_name);
+            Some(factor("zone", &z.zone_name, z.player_volume_pct / 100.0, z.opp_rank?, teams_ranked))
+        })
+        .collect();
+    let play_types: Vec<MatchupFactor> = player_play_types
+        .iter()
+        .filter(|pt| pt.pct_of_total_points > 0.0)
+        .filter_map(|pt| {
+            let opp_rank = *ranks.get(&(params.opponent_id, pt.play_type.clone()))?;
+            let teams_ranked = db::teams_ranked(&ranks, &pt.play_type);
+            Some(factor("play_type", &pt.play_type, pt.pct_of_total_points / 100.0, opp_rank, teams_ranked))
+        })
+        .collect();
 
-    Ok(result.and_then(|(home_id, away_id)| {
-        if home_id == player_team {
-            Some(away_id)
-        } else {
-            Some(home_id)
+    let zone_edge = weighted_edge(&zones);
+    let play_type_edge = weighted_edge(&play_types);
+    let edge = match (zone_edge, play_type_edge) {
+        (Some(z), Some(p)) => z * ZONE_GRADE_WEIGHT + p * (1.0 - ZONE_GRADE_WEIGHT),
+        (Some(e), None) | (None, Some(e)) => e,
+        (None, None) => {
+            return Err(ApiError::NotFound(format!(
+                "no shooting zone or play type data for player {} against team {}",
+                player_id, params.opponent_id
+            )))
         }
+    };
+    let score = edge_score(edge);
+
+    let mut factors: Vec<MatchupFactor> = zones.into_iter().chain(play_types).collect();
+    factors.sort_by(|a, b| b.impact.partial_cmp(&a.impact).unwrap_or(std::cmp::Ordering::Equal));
+    let negative = factors.iter().rev().take_while(|f| f.impact < 0.0).count().min(GRADE_TOP_FACTORS);
+    let unfavorable: Vec<MatchupFactor> = factors.split_off(factors.len() - negative).into_iter().rev().collect();
+    let favorable = factors
+        .into_iter()
+        .take_while(|f| f.impact > 0.0)
+        .take(GRADE_TOP_FACTORS)
+        .collect();
+
+    Ok(Json(MatchupGrade {
+        player_name: player.player_name,
+        opponent_name: opponent.full_name,
+        grade: letter_grade(score).to_string(),
+        score,
+        zone_score: zone_edge.map(edge_score),
+        play_type_score: play_type_edge.map(edge_score),
+        favorable,
+        unfavorable,
     }))
 }
 
-// Query parameters for play type matchup
-#[derive(Deserialize)]
-pub struct PlayTypeMatchupQuery {
-    opponent_id: i64,
+/// Competition rank of `team_id` when lower `key` is better (e.g. rebounds allowed)
+fn rank_ascending<T>(rows: &[T], team_id: i64, id: impl Fn(&T) -> i64, key: impl Fn(&T) -> f32) -> Option<i32> {
+    rank_of(rows, key, true, |row| id(row) == team_id)
 }
 
-// GET /api/players/:id/play-type-matchup?opponent_id=123 - Get player's play type matchup vs opponent
-pub async fn get_player_play_type_matchup(
+// GET /api/players/:id/rebound-matchup?opponent_id=123 - Player rebounding vs opponent rebounds allowed
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/rebound-matchup",
+    tag = "players",
+    summary = "Player rebounding vs opponent rebounds allowed",
+    params(("id" = i64, Path, description = "Player id"), PlayTypeMatchupQuery),
+    responses(
+        (status = 200, body = ReboundMatchup),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_rebound_matchup(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
     Query(params): Query<PlayTypeMatchupQuery>,
-) -> Result<Json<PlayTypeMatchupResponse>, StatusCode> {
-    // Get player info
+) -> Result<Json<ReboundMatchup>, ApiError> {
     let player = db::get_player_by_id(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
 
-    // Get opponent team info
     let opponent = db::get_team_by_id(&pool, params.opponent_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Get player play types
-    let player_play_types = db::get_player_playtypes(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Get opponent defensive play types
-    let opp_defense = db::get_defensive_play_types(&pool, params.opponent_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", params.opponent_id)))?;
 
-    // Get all team defensive rankings
-    let ranks = db::get_team_defensive_play_type_ranks(&pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let rebounding = db::get_player_rebounding(&pool, player_id, &db::CURRENT_SEASON)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("no played games for player {}", player_id)))?;
 
-    // Build matchup data
-    let mut matchups: Vec<PlayTypeMatchup> = player_play_types
+    let teams = db::get_team_rebounds_allowed(&pool, &db::CURRENT_SEASON).await?;
+    let opp = teams
         .iter()
-        .filter_map(|pt| {
-            // Find opponent's defensive stats for this play type
-            let opp_def = opp_defense.iter().find(|d| d.play_type == pt.play_type)?;
-            let rank = ranks.get(&(params.opponent_id, pt.play_type.clone())).copied().unwrap_or(0);
+        .find(|t| t.team_id == params.opponent_id)
+        .ok_or_else(|| ApiError::NotFound(format!("no rebounding data for team {}", params.opponent_id)))?;
 
-            Some(PlayTypeMatchup {
-                play_type: pt.play_type.clone(),
-                player_ppg: pt.points_per_game,
-                pct_of_total: pt.pct_of_total_points,
-                opp_ppp: opp_def.ppp,
-                opp_rank: rank,
-            })
-        })
-        .collect();
+    let team_id = |t: &crate::models::TeamReboundsAllowed| t.team_id;
+    let reb_rank = rank_ascending(&teams, opp.team_id, team_id, |t| t.reb_allowed).unwrap_or_default();
+    let oreb_rank = rank_ascending(&teams, opp.team_id, team_id, |t| t.oreb_allowed).unwrap_or_default();
+    let dreb_rank = rank_ascending(&teams, opp.team_id, team_id, |t| t.dreb_allowed).unwrap_or_default();
+    let league_avg_reb_allowed = teams.iter().map(|t| t.reb_allowed).sum::<f32>() / teams.len() as f32;
 
-    // Sort by player PPG descending
-    matchups.sort_by(|a, b| b.player_ppg.partial_cmp(&a.player_ppg).unwrap_or(std::cmp::Ordering::Equal));
+    // Rank among teams with rebounding data, so the thirds use that count
+    let verdict = matchup_verdict(reb_rank, teams.len() as i32);
 
-    Ok(Json(PlayTypeMatchupResponse {
+    Ok(Json(ReboundMatchup {
         player_name: player.player_name,
         opponent_name: opponent.full_name,
-        matchups,
+        player: rebounding,
+        reb_allowed: opp.reb_allowed,
+        reb_rank,
+        oreb_allowed: opp.oreb_allowed,
+        oreb_rank,
+        dreb_allowed: opp.dreb_allowed,
+        dreb_rank,
+        league_avg_reb_allowed,
+        verdict: verdict.to_string(),
     }))
 }
 
 // Query parameters for assist zone matchup
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AssistZoneMatchupQuery {
     opponent_id: i64,
 }
 
 // GET /api/players/:id/assist-zone-matchup?opponent_id=123 - Get player's assist zone matchup vs opponent
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/assist-zone-matchup",
+    tag = "players",
+    summary = "Get player's assist zone matchup vs opponent",
+    params(("id" = i64, Path, description = "Player id"), AssistZoneMatchupQuery),
+    responses(
+        (status = 200, body = crate::models::AssistZoneMatchupResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_assist_zone_matchup(
     State(pool): State<SqlitePool>,
     Path(player_id): Path<i64>,
     Query(params): Query<AssistZoneMatchupQuery>,
-) -> Result<Json<crate::models::AssistZoneMatchupResponse>, StatusCode> {
+) -> Result<Json<crate::models::AssistZoneMatchupResponse>, ApiError> {
     let matchup = db::get_assist_zones_with_team_defense(&pool, player_id, params.opponent_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     Ok(Json(matchup))
 }
 
+/// Stat types the upcoming matchup context is built for
+const UPCOMING_STAT_TYPES: [&str; 3] = ["points", "assists", "rebounds"];
+
 // Query parameters for upcoming matchup context
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct UpcomingMatchupQuery {
     opponent_id: i64,
     stat_type: String, // "points", "assists", "rebounds"
+    /// Skip the response cache (the fresh result still replaces the cached one)
+    #[serde(default)]
+    no_cache: bool,
 }
 
 // GET /api/players/:id/upcoming-matchup?opponent_id=123&stat_type=points
 // Get aggregated defensive context for upcoming game tooltip
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/upcoming-matchup",
+    tag = "players",
+    summary = "Get aggregated defensive context for upcoming game tooltip",
+    params(("id" = i64, Path, description = "Player id"), UpcomingMatchupQuery),
+    responses(
+        (status = 200, body = UpcomingMatchupResponse),
+        (status = 400, description = "Invalid stat_type", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_upcoming_matchup_context(
     State(pool): State<SqlitePool>,
+    State(cache): State<Arc<MatchupCache>>,
     Path(player_id): Path<i64>,
     Query(params): Query<UpcomingMatchupQuery>,
-) -> Result<Json<UpcomingMatchupResponse>, StatusCode> {
+) -> Result<([(&'static str, &'static str); 1], Json<UpcomingMatchupResponse>), ApiError> {
+    // Checked before the cache so unknown stat types can't fill it with empty contexts
+    if !UPCOMING_STAT_TYPES.contains(&params.stat_type.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid stat_type '{}'. Valid stat types: {}",
+            params.stat_type,
+            UPCOMING_STAT_TYPES.join(", ")
+        )));
+    }
+
+    let key = (player_id, params.opponent_id, params.stat_type.clone());
+    if !params.no_cache
+        && let Some(cached) = cache.upcoming.get(&key)
+    {
+        return Ok(([(X_CACHE, "HIT")], Json(cached)));
+    }
+
+    let response = build_upcoming_matchup_context(&pool, player_id, &params).await?;
+    cache.upcoming.insert(key, response.clone());
+
+    Ok(([(X_CACHE, "MISS")], Json(response)))
+}
+
+// Query parameters for the combined upcoming matchup context
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UpcomingMatchupAllQuery {
+    opponent_id: i64,
+    /// Skip the response cache (the fresh results still replace the cached ones)
+    #[serde(default)]
+    no_cache: bool,
+}
+
+// GET /api/players/:id/upcoming-matchup/all?opponent_id=123
+// Get the points, assists and rebounds upcoming matchup contexts in one call
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/upcoming-matchup/all",
+    tag = "players",
+    summary = "Get points, assists and rebounds upcoming matchup context in one call",
+    params(("id" = i64, Path, description = "Player id"), UpcomingMatchupAllQuery),
+    responses(
+        (status = 200, body = UpcomingMatchupAllResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_upcoming_matchup_context_all(
+    State(pool): State<SqlitePool>,
+    State(cache): State<Arc<MatchupCache>>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<UpcomingMatchupAllQuery>,
+) -> Result<Json<UpcomingMatchupAllResponse>, ApiError> {
+    let points = cached_upcoming_context(&pool, &cache, player_id, params.opponent_id, "points", params.no_cache).await?;
+    let assists = cached_upcoming_context(&pool, &cache, player_id, params.opponent_id, "assists", params.no_cache).await?;
+    let rebounds = cached_upcoming_context(&pool, &cache, player_id, params.opponent_id, "rebounds", params.no_cache).await?;
+
+    Ok(Json(UpcomingMatchupAllResponse {
+        opponent_name: points.opponent_name.clone(),
+        points,
+        assists,
+        rebounds,
+    }))
+}
+
+/// One stat's upcoming matchup context through the same cache entries as the
+/// single-stat endpoint, so the two endpoints warm each other
+async fn cached_upcoming_context(
+    pool: &SqlitePool,
+    cache: &MatchupCache,
+    player_id: i64,
+    opponent_id: i64,
+    stat_type: &str,
+    no_cache: bool,
+) -> Result<UpcomingMatchupResponse, ApiError> {
+    let key = (player_id, opponent_id, stat_type.to_string());
+    if !no_cache
+        && let Some(cached) = cache.upcoming.get(&key)
+    {
+        return Ok(cached);
+    }
+
+    let response = build_upcoming_matchup_context(pool, player_id, &UpcomingMatchupQuery {
+        opponent_id,
+        stat_type: stat_type.to_string(),
+        no_cache,
+    })
+    .await?;
+    cache.upcoming.insert(key, response.clone());
+
+    Ok(response)
+}
+
+async fn build_upcoming_matchup_context(
+    pool: &SqlitePool,
+    player_id: i64,
+    params: &UpcomingMatchupQuery,
+) -> Result<UpcomingMatchupResponse, ApiError> {
     // Get opponent team name
-    let opponent = db::get_team_by_id(&pool, params.opponent_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    let opponent = db::get_team_by_id(pool, params.opponent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", params.opponent_id)))?;
 
     // Get team stats (DefRtg, Pace)
-    let team_stats = db::get_team_stats(&pool, params.opponent_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let team_stats = db::get_team_stats(pool, params.opponent_id, &db::CURRENT_SEASON)
+        .await?;
 
     let def_rtg = team_stats.as_ref().and_then(|s| s.def_rating);
     let pace = team_stats.as_ref().and_then(|s| s.pace);
@@ -355,12 +1896,15 @@ pub async fn get_upcoming_matchup_context(
         rebounds_allowed: None,
         oreb_allowed: None,
         dreb_allowed: None,
+        total_reb_rank: None,
+        oreb_rank: None,
+        dreb_rank: None,
     };
 
     match params.stat_type.as_str() {
         "points" => {
             // Get shooting zone matchup data
-            if let Ok(zone_matchup) = db::get_shooting_zone_matchup(&pool, player_id, params.opponent_id).await {
+            if let Ok(zone_matchup) = db::get_shooting_zone_matchup(pool, player_id, params.opponent_id).await {
                 // Sort zones by FGA (volume) to find dominant zones
                 let mut zones_by_volume: Vec<_> = zone_matchup.zones.iter()
                     .filter(|z| z.has_data && z.player_fga > 0.0)
@@ -369,22 +1913,22 @@ pub async fn get_upcoming_matchup_context(
 
                 if let Some(dsz) = zones_by_volume.first() {
                     response.dsz_name = Some(dsz.zone_name.clone());
-                    response.dsz_rank = Some(dsz.opp_rank);
+                    response.dsz_rank = dsz.opp_rank;
                 }
                 if let Some(dsz2) = zones_by_volume.get(1) {
                     response.dsz2_name = Some(dsz2.zone_name.clone());
-                    response.dsz2_rank = Some(dsz2.opp_rank);
+                    response.dsz2_rank = dsz2.opp_rank;
                 }
             }
 
             // Get play type matchup data
-            let player_play_types = db::get_player_playtypes(&pool, player_id)
+            let player_play_types = db::get_player_playtypes(pool, player_id)
                 .await
                 .unwrap_or_default();
-            let opp_defense = db::get_defensive_play_types(&pool, params.opponent_id)
+            let opp_defense = db::get_defensive_play_types(pool, params.opponent_id)
                 .await
                 .unwrap_or_default();
-            let ranks = db::get_team_defensive_play_type_ranks(&pool)
+            let ranks = db::get_team_defensive_play_type_ranks(pool)
                 .await
                 .unwrap_or_default();
 
@@ -405,15 +1949,15 @@ pub async fn get_upcoming_matchup_context(
         },
         "assists" => {
             // Get assist zone matchup data
-            if let Ok(assist_matchup) = db::get_assist_zones_with_team_defense(&pool, player_id, params.opponent_id).await {
+            if let Ok(assist_matchup) = db::get_assist_zones_with_team_defense(pool, player_id, params.opponent_id).await {
                 // Zones are already sorted by assists DESC
                 if let Some(daz) = assist_matchup.zones.first() {
                     response.daz_name = Some(daz.zone_name.clone());
-                    response.daz_rank = Some(daz.opp_def_rank);
+                    response.daz_rank = daz.opp_def_rank;
                 }
                 if let Some(daz2) = assist_matchup.zones.get(1) {
                     response.daz2_name = Some(daz2.zone_name.clone());
-                    response.daz2_rank = Some(daz2.opp_def_rank);
+                    response.daz2_rank = daz2.opp_def_rank;
                 }
             }
 
@@ -428,7 +1972,7 @@ pub async fn get_upcoming_matchup_context(
             .bind(params.opponent_id)
             .bind(params.opponent_id)
             .bind(params.opponent_id)
-            .fetch_optional(&pool)
+            .fetch_optional(pool)
             .await
             .ok()
             .flatten();
@@ -438,41 +1982,9 @@ pub async fn get_upcoming_matchup_context(
         "rebounds" => {
             // Calculate team rebounding allowed per game for all teams
             // Then rank the opponent team
-            #[derive(sqlx::FromRow)]
-            struct TeamRebStats {
-                team_id: i64,
-                reb_allowed: f32,
-                oreb_allowed: f32,
-                dreb_allowed: f32,
-            }
-
-            // Get average rebounds allowed per game for each team
-            // This sums player rebounds by game (for opposing team), then averages across games
-            let all_team_reb_stats: Vec<TeamRebStats> = sqlx::query_as(
-                r#"WITH game_rebounds AS (
-                    SELECT
-                        s.game_id,
-                        CASE WHEN pgl.team_id = s.home_team_id THEN s.away_team_id ELSE s.home_team_id END as defending_team_id,
-                        SUM(pgl.reb) as total_reb,
-                        SUM(pgl.oreb) as total_oreb,
-                        SUM(pgl.dreb) as total_dreb
-                    FROM player_game_logs pgl
-                    JOIN schedule s ON pgl.game_id = s.game_id
-                    WHERE pgl.reb IS NOT NULL
-                    GROUP BY s.game_id, defending_team_id
-                )
-                SELECT
-                    defending_team_id as team_id,
-                    CAST(AVG(total_reb) AS REAL) as reb_allowed,
-                    CAST(AVG(total_oreb) AS REAL) as oreb_allowed,
-                    CAST(AVG(total_dreb) AS REAL) as dreb_allowed
-                FROM game_rebounds
-                GROUP BY defending_team_id
-                ORDER BY reb_allowed ASC"#
-            )
-            .fetch_all(&pool)
-            .await
-            .unwrap_or_default();
+            let all_team_reb_stats = db::get_team_rebounds_allowed(pool, &db::CURRENT_SEASON)
+                .await
+                .unwrap_or_default();
 
             // Find opponent's stats and rank
             if let Some(pos) = all_team_reb_stats.iter().position(|t| t.team_id == params.opponent_id) {
@@ -482,30 +1994,730 @@ pub async fn get_upcoming_matchup_context(
                 response.dreb_allowed = Some(opp_stats.dreb_allowed);
 
                 // Calculate ranks (1 = allows fewest rebounds = best defense)
-                // Sort by each stat to get individual ranks
-                let mut reb_sorted: Vec<_> = all_team_reb_stats.iter().collect();
-                reb_sorted.sort_by(|a, b| a.reb_allowed.partial_cmp(&b.reb_allowed).unwrap_or(std::cmp::Ordering::Equal));
-                let reb_rank = reb_sorted.iter().position(|t| t.team_id == params.opponent_id).map(|p| (p + 1) as i32);
-
-                let mut oreb_sorted: Vec<_> = all_team_reb_stats.iter().collect();
-                oreb_sorted.sort_by(|a, b| a.oreb_allowed.partial_cmp(&b.oreb_allowed).unwrap_or(std::cmp::Ordering::Equal));
-                let oreb_rank = oreb_sorted.iter().position(|t| t.team_id == params.opponent_id).map(|p| (p + 1) as i32);
-
-                let mut dreb_sorted: Vec<_> = all_team_reb_stats.iter().collect();
-                dreb_sorted.sort_by(|a, b| a.dreb_allowed.partial_cmp(&b.dreb_allowed).unwrap_or(std::cmp::Ordering::Equal));
-                let dreb_rank = dreb_sorted.iter().position(|t| t.team_id == params.opponent_id).map(|p| (p + 1) as i32);
-
-                // Store ranks in the zone name fields (repurposing for rebounds)
-                response.dsz_name = Some("Total Reb".to_string());
-                response.dsz_rank = reb_rank;
-                response.dsz2_name = Some("OREB".to_string());
-                response.dsz2_rank = oreb_rank;
-                response.dpt_name = Some("DREB".to_string());
-                response.dpt_rank = dreb_rank;
+                let team_id = |t: &crate::models::TeamReboundsAllowed| t.team_id;
+                response.total_reb_rank = rank_ascending(&all_team_reb_stats, params.opponent_id, team_id, |t| t.reb_allowed);
+                response.oreb_rank = rank_ascending(&all_team_reb_stats, params.opponent_id, team_id, |t| t.oreb_allowed);
+                response.dreb_rank = rank_ascending(&all_team_reb_stats, params.opponent_id, team_id, |t| t.dreb_allowed);
             }
         },
         _ => {}
     }
 
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// Largest swing (either way) the matchup ranks can apply to a projection
+const MAX_MATCHUP_ADJUSTMENT: f32 = 0.05;
+
+/// Projection multiplier from matchup ranks, each given as (rank, teams ranked among):
+/// the ranks' average position from the best defense (-MAX_MATCHUP_ADJUSTMENT) to the
+/// worst (+MAX_MATCHUP_ADJUSTMENT). A ranking of fewer than two teams says nothing, so
+/// it's skipped; with no usable ranks the factor is neutral.
+fn matchup_factor(ranked: &[(i32, i32)]) -> f32 {
+    let positions: Vec<f32> = ranked
+        .iter()
+        .filter(|&&(_, among)| among >= 2)
+        .map(|&(rank, among)| ((rank - 1) as f32 / (among - 1) as f32).clamp(0.0, 1.0))
+        .collect();
+    if positions.is_empty() {
+        return 1.0;
+    }
+    let position = positions.iter().sum::<f32>() / positions.len() as f32;
+    1.0 + MAX_MATCHUP_ADJUSTMENT * (2.0 * position - 1.0)
+}
+
+// Query parameters for player projection
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectionQuery {
+    opponent_id: i64,
+    stat_type: String, // "points", "assists", "rebounds"
+}
+
+// GET /api/players/:id/projection?opponent_id=123&stat_type=points
+// Project a stat from the season average and the upcoming matchup context
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/projection",
+    tag = "players",
+    summary = "Project a stat from the season average and the upcoming matchup context",
+    params(("id" = i64, Path, description = "Player id"), ProjectionQuery),
+    responses(
+        (status = 200, body = Projection),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_projection(
+    State(pool): State<SqlitePool>,
+    Path(player_id): Path<i64>,
+    Query(params): Query<ProjectionQuery>,
+) -> Result<Json<Projection>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let base = match params.stat_type.as_str() {
+        "points" => player.points,
+        "assists" => player.assists,
+        "rebounds" => player.rebounds,
+        other => return Err(ApiError::BadRequest(format!("Unknown stat_type: {}", other))),
+    };
+
+    let context = build_upcoming_matchup_context(&pool, player_id, &UpcomingMatchupQuery {
+        opponent_id: params.opponent_id,
+        stat_type: params.stat_type.clone(),
+        no_cache: false,
+    })
+    .await?;
+
+    // Opponent relative to the league: faster pace means more possessions, a higher
+    // DefRtg means a weaker defense. Missing data leaves the factor neutral.
+    let league = db::get_league_team_averages(&pool, &db::CURRENT_SEASON).await?;
+    let ratio = |opp: Option<f32>, avg: Option<f32>| match (opp, avg) {
+        (Some(opp), Some(avg)) if avg > 0.0 => opp / avg,
+        _ => 1.0,
+    };
+    let pace_factor = ratio(context.pace, league.pace);
+    let defense_factor = ratio(context.def_rtg, league.def_rating);
+
+    // Dominant zone / play type ranks for the stat (overall rebounds-allowed rank for
+    // rebounds), each with the number of teams it was ranked among: teams without data
+    // for a zone, play type or rebounding aren't in that ranking
+    let with_pool = |rank: Option<i32>, name: &Option<String>, ranks: &HashMap<(i64, String), i32>| {
+        let name = name.as_deref()?;
+        Some((rank?, db::teams_ranked(ranks, name)))
+    };
+    let ranked: Vec<(i32, i32)> = match params.stat_type.as_str() {
+        "points" => {
+            let zone_ranks = db::compute_zone_ranks(&pool).await?;
+            let play_type_ranks = db::get_team_defensive_play_type_ranks(&pool).await?;
+            vec![
+                with_pool(context.dsz_rank, &context.dsz_name, &zone_ranks),
+                with_pool(context.dsz2_rank, &context.dsz2_name, &zone_ranks),
+                with_pool(context.dpt_rank, &context.dpt_name, &play_type_ranks),
+                with_pool(context.dpt2_rank, &context.dpt2_name, &play_type_ranks),
+            ]
+        }
+        "assists" => {
+            let zone_ranks = db::compute_zone_ranks(&pool).await?;
+            vec![
+                with_pool(context.daz_rank, &context.daz_name, &zone_ranks),
+                with_pool(context.daz2_rank, &context.daz2_name, &zone_ranks),
+            ]
+        }
+        _ => {
+            let rebounding = db::get_team_rebounds_allowed(&pool, &db::CURRENT_SEASON).await?;
+            vec![context.total_reb_rank.map(|rank| (rank, rebounding.len() as i32))]
+        }
+    }
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let matchup_ranks = ranked.iter().map(|&(rank, _)| rank).collect();
+    let matchup_factor = matchup_factor(&ranked);
+
+    let projected = base * pace_factor * defense_factor * matchup_factor;
+
+    Ok(Json(Projection {
+        player_name: player.player_name,
+        opponent_name: context.opponent_name,
+        stat_type: params.stat_type,
+        base,
+        pace_factor,
+        defense_factor,
+        matchup_factor,
+        matchup_ranks,
+        projected,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::*;
+    use axum::http::{StatusCode, Uri};
+
+    fn query<T: serde::de::DeserializeOwned>(uri: &str) -> Query<T> {
+        Query::try_from_uri(&uri.parse::<Uri>().unwrap()).unwrap()
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn matchup_verdict_splits_the_league_in_thirds() {
+        assert_eq!(matchup_verdict(1, 30), "avoid");
+        assert_eq!(matchup_verdict(10, 30), "avoid");
+        assert_eq!(matchup_verdict(11, 30), "neutral");
+        assert_eq!(matchup_verdict(20, 30), "neutral");
+        assert_eq!(matchup_verdict(21, 30), "attack");
+        assert_eq!(matchup_verdict(30, 30), "attack");
+    }
+
+    #[test]
+    fn matchup_verdict_scales_with_league_size() {
+        assert_eq!(matchup_verdict(2, 6), "avoid");
+        assert_eq!(matchup_verdict(3, 6), "neutral");
+        assert_eq!(matchup_verdict(4, 6), "neutral");
+        assert_eq!(matchup_verdict(5, 6), "attack");
+    }
+
+    #[test]
+    fn boom_bust_counts_games_a_margin_past_the_line() {
+        // Line 24.5, margin 5: 29.5+ is a boom, 19.5- a bust
+        let values = [35.0, 29.5, 25.0, 24.0, 20.0, 19.5, 10.0, 30.0];
+        let result = classify_boom_bust("points".to_string(), 24.5, 5.0, &values);
+
+        assert_eq!(result.games, 8);
+        assert_eq!(result.booms, 3);
+        assert_eq!(result.busts, 2);
+        assert_eq!(result.boom_pct, 37.5);
+        assert_eq!(result.bust_pct, 25.0);
+        assert_eq!(result.neutral_pct, 37.5);
+    }
+
+    #[tokio::test]
+    async fn unknown_upcoming_stat_type_is_rejected_before_the_cache() {
+        let pool = test_pool().await;
+        let cache = Arc::new(MatchupCache::new(std::time::Duration::from_secs(60)));
+
+        let result = get_upcoming_matchup_context(
+            State(pool),
+            State(cache.clone()),
+            Path(1),
+            query("/api/players/1/upcoming-matchup?opponent_id=2&stat_type=steals"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+        assert!(cache.upcoming.get(&(1, 2, "steals".to_string())).is_none());
+    }
+
+    #[test]
+    fn consistency_score_falls_with_spread() {
+        assert_eq!(consistency_score(20.0, 0.0), 100.0);
+        assert_eq!(consistency_score(20.0, 5.0), 75.0);
+        // Spread beyond the mean floors at 0, as does a zero mean
+        assert_eq!(consistency_score(20.0, 30.0), 0.0);
+        assert_eq!(consistency_score(0.0, 0.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn fantasy_consistency_scores_seeded_games_under_dk() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_team(&pool, 2, "AWY").await;
+        insert_player(&pool, 10, "Fantasy Player", 1, "F").await;
+        for (game_id, date, pts, reb) in [("f1", "2026-01-01", 20, 5), ("f2", "2026-01-03", 30, 10), ("f3", "2026-01-05", 10, 5)] {
+            insert_game(&pool, game_id, date, 1, 2).await;
+            insert_log(&pool, 10, 1, game_id, date, SEASON, Line::new(30.0, pts, reb, 5)).await;
+        }
+        // Last season's game is outside the window rated by this season's defenses
+        insert_game(&pool, "f0", "2025-04-01", 1, 2).await;
+        insert_log(&pool, 10, 1, "f0", "2025-04-01", "2024-25", Line::new(30.0, 50, 10, 5)).await;
+        sqlx::query("UPDATE player_game_logs SET stl = 1, blk = 1, tov = 2, fg3m = 2")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let Json(result) = get_player_fantasy_consistency(State(pool), Path(10), query("/?scoring=dk"))
+            .await
+            .unwrap();
+
+        // Newest first; the 30-point, 10-rebound game earns the double-double bonus
+        let points: Vec<f32> = result.game_log.iter().map(|g| g.fantasy_points).collect();
+        assert_eq!(points, [27.75, 55.5, 37.75]);
+        assert_eq!(result.scoring, "dk");
+        // No team_pace data, so every opponent is neutral
+        assert!(result.game_log.iter().all(|g| g.opp_factor == 1.0));
+        assert!((result.mean - 40.333).abs() < 0.01);
+        assert!((result.consistency - consistency_score(result.adjusted_mean, result.adjusted_std_dev)).abs() < 1e-4);
+        assert!((result.consistency - 71.55).abs() < 0.05, "{}", result.consistency);
+    }
+
+    #[test]
+    fn def_rating_factor_is_neutral_without_both_ratings() {
+        assert_eq!(def_rating_factor(Some(121.0), Some(110.0)), 1.1);
+        assert_eq!(def_rating_factor(Some(99.0), Some(110.0)), 0.9);
+        assert_eq!(def_rating_factor(None, Some(110.0)), 1.0);
+        assert_eq!(def_rating_factor(Some(110.0), None), 1.0);
+        assert_eq!(def_rating_factor(Some(0.0), Some(110.0)), 1.0);
+    }
+
+    #[tokio::test]
+    async fn adjusted_points_credit_strong_defenses_and_discount_weak_ones() {
+        let pool = test_pool().await;
+        for (id, abbr) in [(1, "HOM"), (2, "STR"), (3, "WEK"), (4, "UNR")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        insert_player(&pool, 10, "Adjusted Player", 1, "G").await;
+        // League average DefRtg 110: team 2 is strong, team 3 weak, team 4 unrated
+        for (team, def_rating) in [(1, 110.0), (2, 99.0), (3, 121.0)] {
+            insert_team_pace(&pool, team, 100.0, def_rating).await;
+        }
+        for (game_id, date, opponent, pts) in [("d1", "2026-01-01", 2, 18), ("d2", "2026-01-03", 3, 33), ("d3", "2026-01-05", 4, 24)] {
+            insert_game(&pool, game_id, date, 1, opponent).await;
+            insert_log(&pool, 10, 1, game_id, date, SEASON, Line::new(30.0, pts, 0, 0)).await;
+        }
+
+        let Json(adjusted) = get_player_adjusted_stats(State(pool.clone()), Path(10)).await.unwrap();
+        assert_eq!(adjusted.games, 3);
+        assert_eq!(adjusted.games_with_rating, 2);
+        assert_eq!(adjusted.raw_points, 25.0);
+        // 18 / 0.9 = 20 against the strong defense, 33 / 1.1 = 30 against the weak one
+        assert!((adjusted.adjusted_points - (20.0 + 30.0 + 24.0) / 3.0).abs() < 1e-3, "{}", adjusted.adjusted_points);
+        assert_eq!(adjusted.avg_opp_def_rating, Some(110.0));
+        assert_eq!(adjusted.league_avg_def_rating, Some(110.0));
+
+        // Fantasy consistency adjusts each game by the same factor
+        sqlx::query("UPDATE player_game_logs SET stl = 0, blk = 0, tov = 0, fg3m = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let Json(fantasy) = get_player_fantasy_consistency(State(pool), Path(10), query("/?scoring=dk"))
+            .await
+            .unwrap();
+        let factors: Vec<f32> = fantasy.game_log.iter().map(|g| g.opp_factor).collect();
+        assert_eq!(factors, [1.0, 1.1, 0.9]);
+        let strong = &fantasy.game_log[2];
+        assert!((strong.adjusted_points - strong.fantasy_points / 0.9).abs() < 1e-3);
+    }
+
+    #[tokio::test]
+    async fn data_coverage_reports_partial_data() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_team(&pool, 2, "AWY").await;
+        insert_player(&pool, 10, "Partial Player", 1, "G").await;
+        for zone_name in ["Restricted Area", "Mid-Range"] {
+            sqlx::query("INSERT INTO player_shooting_zones (player_id, season, zone_name, fgm, fga) VALUES (10, ?, ?, 2.0, 4.0)")
+                .bind(SEASON)
+                .bind(zone_name)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        for (game_id, date, min) in [("c1", "2026-01-01", 30.0), ("c2", "2026-01-03", 0.0), ("c3", "2026-01-05", 28.0)] {
+            insert_game(&pool, game_id, date, 1, 2).await;
+            insert_log(&pool, 10, 1, game_id, date, SEASON, Line::new(min, 10, 4, 2)).await;
+        }
+        let today = Today::fixed("2026-01-10");
+
+        let Json(coverage) = get_player_data_coverage(State(pool.clone()), today, Path(10)).await.unwrap();
+
+        assert_eq!(coverage.player_name.as_deref(), Some("Partial Player"));
+        assert!(coverage.season_stats);
+        assert_eq!(coverage.shooting_zones, 2);
+        assert_eq!(coverage.assist_zones, 0);
+        assert_eq!(coverage.play_types, 0);
+        assert_eq!(coverage.game_logs, 3);
+        assert_eq!(coverage.played_games, 2);
+        assert!(!coverage.has_props);
+
+        let missing = get_player_data_coverage(State(pool), today, Path(99)).await;
+        assert!(matches!(missing, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn player_list_offset_past_the_end_is_empty() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_player(&pool, 1, "First Player", 1, "G").await;
+        insert_player(&pool, 2, "Second Player", 1, "F").await;
+
+        for uri in ["/api/players?offset=99999", "/api/players?limit=10&offset=99999"] {
+            let response = get_players(State(pool.clone()), query(uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(body_json(response).await, serde_json::json!([]));
+        }
+    }
+
+    #[tokio::test]
+    async fn attack_point_ranks_the_opponent_on_the_top_play_type() {
+        let pool = test_pool().await;
+        for (id, abbr) in [(1, "AAA"), (2, "BBB"), (3, "CCC")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        insert_player(&pool, 10, "Iso Scorer", 1, "G").await;
+        insert_player(&pool, 11, "Post Scorer", 1, "C").await;
+        // Spot-up earns more per game, but isolation is the larger share of points
+        insert_player_play_type(&pool, 10, "Isolation", 8.0, 40.0).await;
+        insert_player_play_type(&pool, 10, "Spotup", 9.0, 30.0).await;
+        insert_player_play_type(&pool, 11, "Postup", 10.0, 50.0).await;
+        insert_player_play_type(&pool, 11, "Isolation", 4.0, 20.0).await;
+        for (team, ppp) in [(1, 0.75), (2, 1.0), (3, 1.25)] {
+            insert_defensive_play_type(&pool, team, "Isolation", ppp).await;
+            insert_defensive_play_type(&pool, team, "Spotup", 2.0 - ppp).await;
+        }
+        // Team 2 has no post-up data
+        insert_defensive_play_type(&pool, 1, "Postup", 0.8).await;
+        insert_defensive_play_type(&pool, 3, "Postup", 1.2).await;
+
+        let Json(iso) = get_player_attack_point(State(pool.clone()), Path(10), query("/?opponent_id=3"))
+            .await
+            .unwrap();
+        assert_eq!(iso.play_type, "Isolation");
+        assert_eq!(iso.pct_of_total, 40.0);
+        assert_eq!(iso.opp_ppp, Some(1.25));
+        assert_eq!(iso.opp_rank, Some(3));
+        assert_eq!(iso.teams_ranked, 3);
+        assert_eq!(iso.league_avg_ppp, Some(1.0));
+        assert_eq!(iso.verdict, "attack");
+
+        // Without opponent data there's no rank to judge
+        let Json(post) = get_player_attack_point(State(pool.clone()), Path(11), query("/?opponent_id=2"))
+            .await
+            .unwrap();
+        assert_eq!(post.play_type, "Postup");
+        assert_eq!(post.opp_ppp, None);
+        assert_eq!(post.opp_rank, None);
+        assert_eq!(post.teams_ranked, 2);
+        assert_eq!(post.league_avg_ppp, Some(1.0));
+        assert_eq!(post.verdict, "no_data");
+
+        // Teams with no play-type data don't stretch the thirds: still the worst of 3
+        for (id, abbr) in [(4, "DDD"), (5, "EEE"), (6, "FFF")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        let Json(iso) = get_player_attack_point(State(pool), Path(10), query("/?opponent_id=3"))
+            .await
+            .unwrap();
+        assert_eq!((iso.opp_rank, iso.teams_ranked), (Some(3), 3));
+        assert_eq!(iso.verdict, "attack");
+    }
+
+    #[test]
+    fn percentile_counts_ties_as_half() {
+        let pool = [10.0, 20.0, 20.0, 30.0];
+        assert_eq!(percentile_of(&pool, 20.0), Some(50.0));
+        assert_eq!(percentile_of(&pool, 25.0), Some(75.0));
+        // The minimum only beats half of itself; anything past the max is 100
+        assert_eq!(percentile_of(&pool, 10.0), Some(12.5));
+        assert_eq!(percentile_of(&pool, 5.0), Some(0.0));
+        assert_eq!(percentile_of(&pool, 30.0), Some(87.5));
+        assert_eq!(percentile_of(&pool, 35.0), Some(100.0));
+        assert_eq!(percentile_of(&[20.0, 20.0, 20.0], 20.0), Some(50.0));
+        assert_eq!(percentile_of(&[10.0, 20.0, 30.0], 10.0), Some(16.7));
+        assert_eq!(percentile_of(&[], 20.0), None);
+    }
+
+    #[tokio::test]
+    async fn percentiles_leave_players_under_the_games_floor_out_of_the_pool() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "AAA").await;
+        for (id, name, points, games) in [(10, "Low", 10.0, 20), (11, "Mid", 20.0, 20), (12, "Top", 30.0, 20), (13, "Cameo", 40.0, 3)] {
+            insert_player(&pool, id, name, 1, "G").await;
+            // A load time no other test uses, so the shared distribution cache starts empty
+            sqlx::query(r#"UPDATE player_stats SET points = ?, games_played = ?, last_updated = '2031-02-03 06:00:00' WHERE player_id = ?"#)
+                .bind(points)
+                .bind(games)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        let points = |result: &PlayerPercentiles| {
+            result.percentiles.iter().find(|p| p.stat == "points").unwrap().percentile
+        };
+
+        let Json(cameo) = get_player_percentiles(State(pool.clone()), Path(13)).await.unwrap();
+        assert!(!cameo.qualified);
+        assert_eq!(cameo.pool_size, 3);
+        assert_eq!(points(&cameo), Some(100.0));
+
+        // The 40-point cameo doesn't push the qualified scorers down
+        let Json(top) = get_player_percentiles(State(pool.clone()), Path(12)).await.unwrap();
+        assert!(top.qualified);
+        assert_eq!(points(&top), Some(83.3));
+        let Json(low) = get_player_percentiles(State(pool), Path(10)).await.unwrap();
+        assert_eq!(points(&low), Some(16.7));
+    }
+
+    #[test]
+    fn rank_edge_maps_the_league_onto_minus_one_to_one() {
+        assert_eq!(rank_edge(1, 30), -1.0);
+        assert_eq!(rank_edge(30, 30), 1.0);
+        assert_eq!(rank_edge(2, 3), 0.0);
+        assert!((rank_edge(10, 30) - -0.3793).abs() < 1e-4);
+        // A one-team league has nothing to rank against
+        assert_eq!(rank_edge(1, 1), 0.0);
+    }
+
+    #[test]
+    fn letter_grade_cutoffs() {
+        assert_eq!(edge_score(1.0), 100.0);
+        assert_eq!(edge_score(-2.0), 0.0);
+        for (score, grade) in [
+            (100.0, "A+"), (80.0, "A+"), (79.9, "A"), (70.0, "A"), (62.0, "B+"), (61.9, "B"),
+            (55.0, "B"), (50.0, "C+"), (49.9, "C"), (45.0, "C"), (35.0, "D"), (34.9, "F"), (0.0, "F"),
+        ] {
+            assert_eq!(letter_grade(score), grade, "score {}", score);
+        }
+    }
+
+    #[test]
+    fn weighted_edge_is_none_without_weight() {
+        assert_eq!(weighted_edge(&[]), None);
+    }
+
+    /// Three teams; team 3 defends the rim and isolation worst in the league, mid-range and
+    /// spot-ups best, and post-ups in the middle
+    async fn seed_grade_league(pool: &SqlitePool) {
+        for (id, abbr) in [(1, "AAA"), (2, "BBB"), (3, "CCC")] {
+            insert_team(pool, id, abbr).await;
+        }
+        for (team, rim, mid) in [(1, 5.0, 4.0), (2, 5.5, 4.5), (3, 6.5, 3.0)] {
+            insert_zone_defense(pool, team, "Restricted Area", rim, 10.0).await;
+            insert_zone_defense(pool, team, "Mid-Range", mid, 10.0).await;
+        }
+        for (team, iso, spot, post) in [(1, 0.8, 1.0, 0.8), (2, 0.9, 1.1, 1.2), (3, 1.1, 0.9, 1.0)] {
+            insert_defensive_play_type(pool, team, "Isolation", iso).await;
+            insert_defensive_play_type(pool, team, "Spotup", spot).await;
+            insert_defensive_play_type(pool, team, "Postup", post).await;
+        }
+    }
+
+    fn factor_names(factors: &[MatchupFactor]) -> Vec<&str> {
+        factors.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn matchup_grade_blends_zones_and_play_types() {
+        let pool = test_pool().await;
+        seed_grade_league(&pool).await;
+        insert_player(&pool, 10, "Two Way Scorer", 1, "G").await;
+        // 60% of attempts at the rim, 40% from mid-range
+        insert_shooting_zone(&pool, 10, "Restricted Area", 3.6, 6.0).await;
+        insert_shooting_zone(&pool, 10, "Mid-Range", 1.6, 4.0).await;
+        // Shares of points are stored as percentages, like the collector writes them
+        insert_player_play_type(&pool, 10, "Isolation", 8.0, 40.0).await;
+        insert_player_play_type(&pool, 10, "Spotup", 6.0, 30.0).await;
+        insert_player_play_type(&pool, 10, "Postup", 4.0, 20.0).await;
+
+        let Json(grade) = get_player_matchup_grade(State(pool), Path(10), query("/?opponent_id=3"))
+            .await
+            .unwrap();
+
+        // Zones: 0.6 * +1 + 0.4 * -1 = 0.2. Play types: (0.4 - 0.3 + 0) / 0.9 = 0.111
+        assert!((grade.zone_score.unwrap() - 60.0).abs() < 1e-3);
+        assert!((grade.play_type_score.unwrap() - 55.556).abs() < 1e-3);
+        assert!((grade.score - 57.778).abs() < 1e-3, "{}", grade.score);
+        assert_eq!(grade.grade, "B");
+        // Post-ups against a mid-league defense have no impact either way
+        assert_eq!(factor_names(&grade.favorable), ["Restricted Area", "Isolation"]);
+        assert_eq!(factor_names(&grade.unfavorable), ["Mid-Range", "Spotup"]);
+        assert!((grade.favorable[0].impact - 0.6).abs() < 1e-4);
+        assert!((grade.unfavorable[1].impact - -0.3).abs() < 1e-4);
+    }
+
+    #[tokio::test]
+    async fn matchup_grade_with_only_zones() {
+        let pool = test_pool().await;
+        seed_grade_league(&pool).await;
+        insert_player(&pool, 11, "Zone Shooter", 1, "G").await;
+        insert_shooting_zone(&pool, 11, "Restricted Area", 3.6, 6.0).await;
+        insert_shooting_zone(&pool, 11, "Mid-Range", 1.6, 4.0).await;
+
+        let Json(grade) = get_player_matchup_grade(State(pool), Path(11), query("/?opponent_id=3"))
+            .await
+            .unwrap();
+
+        assert_eq!(grade.play_type_score, None);
+        assert!((grade.score - 60.0).abs() < 1e-3);
+        assert_eq!(grade.grade, "B");
+        assert_eq!(factor_names(&grade.favorable), ["Restricted Area"]);
+        assert_eq!(factor_names(&grade.unfavorable), ["Mid-Range"]);
+    }
+
+    #[tokio::test]
+    async fn matchup_grade_with_only_play_types() {
+        let pool = test_pool().await;
+        seed_grade_league(&pool).await;
+        insert_player(&pool, 12, "Post Hub", 1, "C").await;
+        insert_player_play_type(&pool, 12, "Isolation", 10.0, 50.0).await;
+        insert_player_play_type(&pool, 12, "Postup", 10.0, 50.0).await;
+
+        let Json(grade) = get_player_matchup_grade(State(pool.clone()), Path(12), query("/?opponent_id=3"))
+            .await
+            .unwrap();
+
+        assert_eq!(grade.zone_score, None);
+        assert!((grade.score - 75.0).abs() < 1e-3);
+        assert_eq!(grade.grade, "A");
+        assert_eq!(factor_names(&grade.favorable), ["Isolation"]);
+        assert!(grade.unfavorable.is_empty());
+
+        // Team 1 defends both best, the floor of the scale
+        let Json(grade) = get_player_matchup_grade(State(pool), Path(12), query("/?opponent_id=1"))
+            .await
+            .unwrap();
+        assert_eq!(grade.score, 0.0);
+        assert_eq!(grade.grade, "F");
+        assert!(grade.favorable.is_empty());
+        assert_eq!(grade.unfavorable.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn matchup_grade_scales_ranks_by_the_teams_with_data() {
+        let pool = test_pool().await;
+        seed_grade_league(&pool).await;
+        // Three more teams in the league with no zone or play-type data
+        for (id, abbr) in [(4, "DDD"), (5, "EEE"), (6, "FFF")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        insert_player(&pool, 12, "Post Hub", 1, "C").await;
+        insert_player_play_type(&pool, 12, "Isolation", 10.0, 50.0).await;
+        insert_player_play_type(&pool, 12, "Postup", 10.0, 50.0).await;
+
+        let Json(grade) = get_player_matchup_grade(State(pool), Path(12), query("/?opponent_id=3"))
+            .await
+            .unwrap();
+
+        // Worst of the 3 teams ranked on isolation is as bad as it gets, not mid-table of 6
+        let iso = &grade.favorable[0];
+        assert_eq!((iso.opp_rank, iso.teams_ranked), (3, 3));
+        assert_eq!(iso.edge, 1.0);
+        assert!((grade.score - 75.0).abs() < 1e-3);
+        assert_eq!(grade.grade, "A");
+    }
+
+    /// A played (or, with 0 minutes, DNP) game carrying only pts/reb/ast
+    fn game(min: f32, pts: i32, reb: i32, ast: i32) -> crate::models::PlayerGameLog {
+        crate::models::PlayerGameLog {
+            game_id: "g".to_string(),
+            player_id: "10".to_string(),
+            team_id: None,
+            season: None,
+            game_date: None,
+            matchup: None,
+            wl: None,
+            min: Some(min),
+            pts: Some(pts),
+            reb: Some(reb),
+            ast: Some(ast),
+            stl: None,
+            blk: None,
+            fgm: None,
+            fga: None,
+            fg3m: None,
+            fg3a: None,
+            ftm: None,
+            fta: None,
+            tov: None,
+            game_margin: None,
+            oreb: None,
+            dreb: None,
+        }
+    }
+
+    fn streak_summary(logs: &[crate::models::PlayerGameLog]) -> Vec<(String, i32, i64)> {
+        active_streaks(logs).into_iter().map(|s| (s.stat, s.threshold, s.games)).collect()
+    }
+
+    #[test]
+    fn streak_stops_at_the_most_recent_miss() {
+        // Newest first: 3 straight 20+ games, then a 15, then two more 20+ before it
+        let logs = [game(30.0, 22, 4, 3), game(30.0, 25, 4, 3), game(30.0, 21, 4, 3), game(30.0, 15, 4, 3), game(30.0, 30, 4, 3), game(30.0, 28, 4, 3)];
+
+        let streaks = streak_summary(&logs);
+
+        assert!(streaks.contains(&("points".to_string(), 20, 3)));
+        // Every game cleared 10, so that run covers all six
+        assert_eq!(streaks[0], ("points".to_string(), 10, 6));
+        assert!(!streaks.iter().any(|s| s.1 == 30));
+    }
+
+    #[test]
+    fn dnp_games_neither_extend_nor_break_a_streak() {
+        let logs = [game(30.0, 12, 10, 2), game(0.0, 0, 0, 0), game(28.0, 11, 11, 1), game(30.0, 14, 12, 0)];
+
+        let streaks = streak_summary(&logs);
+
+        assert!(streaks.contains(&("rebounds".to_string(), 10, 3)));
+        assert!(streaks.contains(&("points".to_string(), 10, 3)));
+    }
+
+    #[test]
+    fn streaks_shorter_than_the_minimum_are_dropped() {
+        // One 10-assist game after a miss isn't a streak yet
+        let logs = [game(30.0, 8, 2, 11), game(30.0, 8, 2, 4)];
+        assert!(streak_summary(&logs).is_empty());
+
+        let logs = [game(30.0, 8, 2, 11), game(30.0, 8, 2, 10)];
+        assert_eq!(streak_summary(&logs), [("assists".to_string(), 10, MIN_STREAK_GAMES)]);
+    }
+
+    #[test]
+    fn streaks_are_ordered_longest_first() {
+        let logs = [
+            game(30.0, 32, 10, 4),
+            game(30.0, 31, 11, 4),
+            game(30.0, 24, 10, 4),
+            game(30.0, 12, 3, 4),
+        ];
+
+        let games: Vec<i64> = active_streaks(&logs).iter().map(|s| s.games).collect();
+
+        // 10+ points over 4, 20+ points and 10+ rebounds over 3, 30+ points and 40+ PRA over 2
+        assert_eq!(games, [4, 3, 3, 2, 2]);
+        assert!(games.is_sorted_by(|a, b| a >= b));
+    }
+
+    #[test]
+    fn matchup_factor_scales_each_rank_by_its_own_pool() {
+        assert_eq!(matchup_factor(&[(1, 30)]), 1.0 - MAX_MATCHUP_ADJUSTMENT);
+        assert_eq!(matchup_factor(&[(30, 30)]), 1.0 + MAX_MATCHUP_ADJUSTMENT);
+        // Worst of the 20 teams with data is as bad as it gets, not two-thirds of the way
+        assert_eq!(matchup_factor(&[(20, 20)]), 1.0 + MAX_MATCHUP_ADJUSTMENT);
+        // Best of 3 and worst of 30 cancel out
+        assert_eq!(matchup_factor(&[(1, 3), (30, 30)]), 1.0);
+    }
+
+    #[test]
+    fn matchup_factor_stays_within_the_max_adjustment() {
+        // A rank outside its pool can't push past the bound
+        assert_eq!(matchup_factor(&[(5, 3)]), 1.0 + MAX_MATCHUP_ADJUSTMENT);
+        assert_eq!(matchup_factor(&[(0, 3)]), 1.0 - MAX_MATCHUP_ADJUSTMENT);
+        // Nothing to rank against
+        assert_eq!(matchup_factor(&[]), 1.0);
+        assert_eq!(matchup_factor(&[(1, 1)]), 1.0);
+    }
+
+    #[tokio::test]
+    async fn projection_combines_pace_defense_and_matchup() {
+        let pool = test_pool().await;
+        for (id, abbr) in [(1, "AAA"), (2, "BBB"), (3, "CCC"), (4, "DDD")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        // League averages: pace 100, DefRtg 110; team 3 is 5% above both
+        for (team, pace, def_rating) in [(1, 95.0, 104.5), (2, 100.0, 110.0), (3, 105.0, 115.5), (4, 100.0, 110.0)] {
+            insert_team_pace(&pool, team, pace, def_rating).await;
+        }
+        insert_player(&pool, 10, "Glass Cleaner", 1, "C").await;
+        insert_player(&pool, 20, "Other Big", 2, "C").await;
+        // Team 1 allows 8 rebounds, team 2 10 and team 3 12; team 4 has no games
+        insert_game(&pool, "r1", "2026-01-01", 1, 2).await;
+        insert_log(&pool, 10, 1, "r1", "2026-01-01", SEASON, Line::new(30.0, 10, 10, 1)).await;
+        insert_log(&pool, 20, 2, "r1", "2026-01-01", SEASON, Line::new(30.0, 10, 8, 1)).await;
+        insert_game(&pool, "r2", "2026-01-03", 1, 3).await;
+        insert_log(&pool, 10, 1, "r2", "2026-01-03", SEASON, Line::new(30.0, 10, 12, 1)).await;
+
+        let Json(projection) = get_player_projection(State(pool), Path(10), query("/?opponent_id=3&stat_type=rebounds"))
+            .await
+            .unwrap();
+
+        assert!((projection.pace_factor - 1.05).abs() < 1e-5);
+        assert!((projection.defense_factor - 1.05).abs() < 1e-5);
+        // Worst of the three rebounding teams, not 3rd of the 4-team league
+        assert_eq!(projection.matchup_ranks, [3]);
+        assert!((projection.matchup_factor - (1.0 + MAX_MATCHUP_ADJUSTMENT)).abs() < 1e-6);
+        assert_eq!(projection.base, 5.0);
+        assert!((projection.projected - 5.0 * 1.05 * 1.05 * 1.05).abs() < 1e-4);
+    }
 }
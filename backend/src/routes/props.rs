@@ -1,89 +1,596 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
 };
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 use sqlx::sqlite::SqlitePool;
 use std::collections::HashMap;
-use crate::models::{PlayerPropsResponse, PropLine};
+use crate::models::{PlayerPropsResponse, PropLine, PropLineSnapshot, PropPlayer, UnderdogProp};
 use crate::db;
+use crate::error::{ApiError, ErrorResponse};
+use crate::util::game_time::has_game_started;
+use crate::util::today::Today;
 
-/// GET /api/players/:id/props - Get underdog props for a player
+/// Underdog stat names mapped to the internal (player_stats column) names, in display
+/// order from most to least important
+pub const UNDERDOG_STATS: [(&str, &str); 13] = [
+    ("points", "points"),
+    ("rebounds", "rebounds"),
+    ("assists", "assists"),
+    ("pts_rebs_asts", "pts_plus_ast_plus_reb"),
+    ("pts_asts", "pts_plus_ast"),
+    ("pts_rebs", "pts_plus_reb"),
+    ("rebs_asts", "ast_plus_reb"),
+    ("three_points_made", "threes_made"),
+    ("blks_stls", "steals_plus_blocks"),
+    ("steals", "steals"),
+    ("blocks", "blocks"),
+    ("turnovers", "turnovers"),
+    ("free_throws_made", "free_throws_made"),
+];
+
+/// First-quarter and first-half Underdog stats mapped to their `player_stats` season
+/// average columns. Game logs have no per-quarter splits, so these get no hit rates.
+pub const PERIOD_STATS: [(&str, &str); 4] = [
+    ("period_1_points", "q1_points"),
+    ("period_1_rebounds", "q1_rebounds"),
+    ("period_1_assists", "q1_assists"),
+    ("period_1_2_points", "first_half_points"),
+];
+
+/// Position of an Underdog stat in `UNDERDOG_STATS` (then `PERIOD_STATS`), used to sort
+/// stats by importance
+fn underdog_stat_rank(stat_name: &str) -> usize {
+    UNDERDOG_STATS
+        .iter()
+        .chain(PERIOD_STATS.iter())
+        .position(|&(underdog, _)| underdog == stat_name)
+        .unwrap_or(99)
+}
+
+/// `player_stats` season average column for an Underdog stat, full-game or period
+fn season_avg_column(stat_name: &str) -> Option<&'static str> {
+    internal_stat_name(stat_name).or_else(|| {
+        PERIOD_STATS
+            .iter()
+            .find(|&&(underdog, _)| underdog == stat_name)
+            .map(|&(_, column)| column)
+    })
+}
+
+/// Internal stat name for an Underdog stat, or None if it isn't in `UNDERDOG_STATS`
+pub fn internal_stat_name(stat_name: &str) -> Option<&'static str> {
+    UNDERDOG_STATS
+        .iter()
+        .find(|&&(underdog, _)| underdog == stat_name)
+        .map(|&(_, internal)| internal)
+}
+
+/// How a player's prop lines are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PropOrder {
+    /// Fixed `UNDERDOG_STATS` importance order
+    Default,
+    /// Stat name A-Z
+    Alpha,
+    /// Player's season average for the stat, highest first
+    Volume,
+}
+
+impl PropOrder {
+    fn parse(value: Option<&str>) -> Result<Self, ApiError> {
+        match value {
+            None | Some("default") => Ok(Self::Default),
+            Some("alpha") => Ok(Self::Alpha),
+            Some("volume") => Ok(Self::Volume),
+            Some(other) => Err(ApiError::BadRequest(format!(
+                "Invalid order '{}'. Use 'default', 'alpha' or 'volume'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Re-sort lines from `group_prop_lines` (already in default order) by `order`. Lines
+/// of the same stat stay by line ascending; stats with no season average sort last
+/// under "volume".
+fn sort_prop_lines(prop_lines: &mut [PropLine], order: PropOrder, season: Option<&crate::models::PlayerStats>) {
+    let line_cmp = |a: &PropLine, b: &PropLine| a.line.partial_cmp(&b.line).unwrap_or(std::cmp::Ordering::Equal);
+
+    match order {
+        PropOrder::Default => {}
+        PropOrder::Alpha => {
+            prop_lines.sort_by(|a, b| a.stat_name.cmp(&b.stat_name).then_with(|| line_cmp(a, b)));
+        }
+        PropOrder::Volume => {
+            let volume = |line: &PropLine| {
+                season_avg_column(&line.stat_name)
+                    .and_then(|stat| season?.season_avg(stat))
+                    .unwrap_or(f32::NEG_INFINITY)
+            };
+            prop_lines.sort_by(|a, b| {
+                volume(b)
+                    .partial_cmp(&volume(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.stat_name.cmp(&b.stat_name))
+                    .then_with(|| line_cmp(a, b))
+            });
+        }
+    }
+}
+
+/// Group raw props by (stat_name, line) and combine over/under, so alternate lines for
+/// the same stat stay separate entries. Sorted by stat importance, then line ascending.
+pub fn group_prop_lines<'a>(props: impl IntoIterator<Item = &'a UnderdogProp>) -> Vec<PropLine> {
+    let mut grouped: HashMap<(String, u64), PropLine> = HashMap::new();
+
+    for prop in props {
+        let key = (prop.stat_name.clone(), prop.stat_value.to_bits());
+        let entry = grouped.entry(key).or_insert(PropLine {
+            stat_name: prop.stat_name.clone(),
+            line: prop.stat_value,
+            over_odds: None,
+            under_odds: None,
+            opponent: prop.opponent_name.clone(),
+            scheduled_at: prop.scheduled_at.clone(),
+            hit_rate_last_10: None,
+            hit_rate_last_20: None,
+            season_avg: None,
+            line_vs_avg_pct: None,
+        });
+
+        match prop.choice.as_str() {
+            "over" => entry.over_odds = prop.american_price,
+            "under" => entry.under_odds = prop.american_price,
+            _ => {}
+        }
+    }
+
+    // Convert to vec and sort by stat importance, then by line ascending
+    let mut prop_lines: Vec<PropLine> = grouped.into_values().collect();
+    prop_lines.sort_by(|a, b| {
+        underdog_stat_rank(&a.stat_name)
+            .cmp(&underdog_stat_rank(&b.stat_name))
+            .then_with(|| a.line.partial_cmp(&b.line).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    prop_lines
+}
+
+/// Whether the game a prop is for has started as of `today`. `scheduled_at` is a UTC timestamp, so it's
+/// converted to the ET date and "7:30 PM"-style time that `has_game_started` works with.
+/// Props without a parseable timestamp are treated as upcoming.
+pub fn prop_game_started(prop: &UnderdogProp, today: &Today) -> bool {
+    let Some(scheduled_at) = prop.scheduled_at.as_deref() else {
+        return false;
+    };
+    let Ok(start) = chrono::DateTime::parse_from_rfc3339(scheduled_at) else {
+        return false;
+    };
+    let start_et = start.with_timezone(&chrono_tz::America::New_York);
+    has_game_started(
+        &start_et.format("%Y-%m-%d").to_string(),
+        &Some(start_et.format("%-I:%M %p").to_string()),
+        today,
+    )
+}
+
+/// Query parameters for player props
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PlayerPropsQuery {
+    /// Stat ordering: "default" (fixed importance), "alpha" or "volume" (season average)
+    #[serde(default)]
+    pub order: Option<String>,
+    /// Return no props when the player is ruled Out (default: false)
+    #[serde(default)]
+    pub exclude_injured: bool,
+}
+
+/// GET /api/players/:id/props?order=default&exclude_injured=false - Get underdog props for a player
+///
+/// The player's latest injury status is included so the UI can warn on Questionable
+/// players; with `exclude_injured=true` an Out player's props are suppressed.
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/props",
+    tag = "players",
+    params(("id" = i64, Path, description = "Player id"), PlayerPropsQuery),
+    responses(
+        (status = 200, body = PlayerPropsResponse),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_player_props(
     State(pool): State<SqlitePool>,
+    today: Today,
     Path(player_id): Path<i64>,
-) -> Result<Json<PlayerPropsResponse>, StatusCode> {
-    // Get raw props from database
-    let props = db::get_player_props_by_id(&pool, player_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Query(params): Query<PlayerPropsQuery>,
+) -> Result<Json<PlayerPropsResponse>, ApiError> {
+    let order = PropOrder::parse(params.order.as_deref())?;
+
+    let (injury_status, injury_description) = db::get_player_injury(&pool, player_id)
+        .await?
+        .unzip();
+    let injury_description = injury_description.flatten();
+    let ruled_out = injury_status.as_deref().is_some_and(|status| status.eq_ignore_ascii_case("out"));
+
+    // Get raw props from database, dropping any for a game that's already tipped off
+    let mut props = db::get_player_props_by_id(&pool, player_id, &today)
+        .await?;
+    props.retain(|prop| !prop_game_started(prop, &today));
+    if params.exclude_injured && ruled_out {
+        props.clear();
+    }
 
     if props.is_empty() {
         // Return empty response with player name if we can get it
         let player = db::get_player_by_id(&pool, player_id)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .await?;
 
         return Ok(Json(PlayerPropsResponse {
             player_name: player.map(|p| p.player_name).unwrap_or_default(),
             opponent_id: None,
             opponent_name: None,
+            injury_status,
+            injury_description,
             props: vec![],
         }));
     }
 
-    // Group props by stat_name and combine over/under
-    let mut grouped: HashMap<String, PropLine> = HashMap::new();
     let player_name = props.first().map(|p| p.full_name.clone()).unwrap_or_default();
     let opponent_name = props.first().and_then(|p| p.opponent_name.clone());
-    let scheduled_at = props.first().and_then(|p| p.scheduled_at.clone());
 
     // Look up opponent team ID from name
     let opponent_id = if let Some(ref opp_name) = opponent_name {
         // Get all teams and find the matching one
         let teams = db::get_all_teams(&pool)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .await?;
         teams.iter().find(|t| &t.full_name == opp_name).map(|t| t.team_id)
     } else {
         None
     };
 
-    for prop in props {
-        let entry = grouped.entry(prop.stat_name.clone()).or_insert(PropLine {
-            stat_name: prop.stat_name.clone(),
-            line: prop.stat_value,
-            over_odds: None,
-            under_odds: None,
-            opponent: opponent_name.clone(),
-            scheduled_at: scheduled_at.clone(),
-        });
+    let mut prop_lines = group_prop_lines(&props);
+    let season = db::get_player_by_id(&pool, player_id).await?;
+    sort_prop_lines(&mut prop_lines, order, season.as_ref());
 
-        match prop.choice.as_str() {
-            "over" => entry.over_odds = prop.american_price,
-            "under" => entry.under_odds = prop.american_price,
-            _ => {}
+    // Attach how often the player cleared each line recently, and how the line compares
+    // to the season average (combo stats use the combined average columns, quarter and
+    // half stats the q1_* / first_half_points columns)
+    for prop_line in prop_lines.iter_mut() {
+        prop_line.season_avg = season_avg_column(&prop_line.stat_name)
+            .and_then(|stat| season.as_ref()?.season_avg(stat));
+        prop_line.line_vs_avg_pct = prop_line
+            .season_avg
+            .filter(|&avg| avg > 0.0)
+            .map(|avg| ((prop_line.line as f32 - avg) / avg * 1000.0).round() / 10.0);
+        if internal_stat_name(&prop_line.stat_name).is_none() {
+            continue;
         }
+        prop_line.hit_rate_last_10 = db::get_prop_hit_rate(&pool, player_id, &prop_line.stat_name, prop_line.line, 10)
+            .await?;
+        prop_line.hit_rate_last_20 = db::get_prop_hit_rate(&pool, player_id, &prop_line.stat_name, prop_line.line, 20)
+            .await?;
     }
 
-    // Convert to vec and sort by stat importance
-    let stat_order = [
-        "points", "rebounds", "assists", "pts_rebs_asts", "pts_asts",
-        "pts_rebs", "rebs_asts", "three_points_made", "blks_stls",
-        "steals", "blocks", "turnovers", "free_throws_made",
-    ];
-
-    let mut prop_lines: Vec<PropLine> = grouped.into_values().collect();
-    prop_lines.sort_by(|a, b| {
-        let a_idx = stat_order.iter().position(|&s| s == a.stat_name).unwrap_or(99);
-        let b_idx = stat_order.iter().position(|&s| s == b.stat_name).unwrap_or(99);
-        a_idx.cmp(&b_idx)
-    });
-
     Ok(Json(PlayerPropsResponse {
         player_name,
         opponent_id,
         opponent_name,
+        injury_status,
+        injury_description,
         props: prop_lines,
     }))
 }
+
+/// Query parameters for prop line history
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PropHistoryQuery {
+    /// Underdog stat name (points, rebounds, pts_rebs_asts, ...)
+    pub stat_name: String,
+}
+
+/// GET /api/players/:id/props/history?stat_name=points - Line movement for one stat
+///
+/// Every recorded (updated_at, line, price, side) for the player's upcoming game,
+/// oldest first. Empty when the player has no props for the stat.
+#[utoipa::path(
+    get,
+    path = "/api/players/{id}/props/history",
+    tag = "players",
+    params(("id" = i64, Path, description = "Player id"), PropHistoryQuery),
+    responses(
+        (status = 200, body = Vec<PropLineSnapshot>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_player_prop_history(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Path(player_id): Path<i64>,
+    Query(params): Query<PropHistoryQuery>,
+) -> Result<Json<Vec<PropLineSnapshot>>, ApiError> {
+    let player = db::get_player_by_id(&pool, player_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("player {} not found", player_id)))?;
+
+    let history = db::get_player_prop_history(&pool, &player.player_name, &params.stat_name, &today)
+        .await?;
+
+    Ok(Json(history))
+}
+
+/// Maximum number of players accepted by the batch props endpoint
+const MAX_BATCH_PLAYERS: usize = 50;
+
+/// Request body for batch props
+#[derive(Deserialize, ToSchema)]
+pub struct BatchPropsRequest {
+    pub player_ids: Vec<i64>,
+    /// Game date (YYYY-MM-DD); defaults to the upcoming today/tomorrow window
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+/// POST /api/props/batch - Get underdog props for many players in one request
+///
+/// Returns a map of player_id -> prop lines. Players without upcoming props are omitted.
+/// Rejects more than `MAX_BATCH_PLAYERS` ids with 400.
+#[utoipa::path(
+    post,
+    path = "/api/props/batch",
+    tag = "props",
+    request_body = BatchPropsRequest,
+    responses(
+        (status = 200, body = HashMap<i64, Vec<PropLine>>),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_batch_props(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Json(body): Json<BatchPropsRequest>,
+) -> Result<Json<HashMap<i64, Vec<PropLine>>>, ApiError> {
+    if body.player_ids.len() > MAX_BATCH_PLAYERS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} player_ids per request",
+            MAX_BATCH_PLAYERS
+        )));
+    }
+
+    let props_by_player = db::get_batch_player_props(&pool, &body.player_ids, body.date.as_deref(), &today)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get batch props: {}", e);
+            ApiError::from(e)
+        })?;
+
+    let prop_lines = props_by_player
+        .iter()
+        .filter_map(|(player_id, props)| {
+            let lines = group_prop_lines(props.iter().filter(|prop| !prop_game_started(prop, &today)));
+            (!lines.is_empty()).then_some((*player_id, lines))
+        })
+        .collect();
+
+    Ok(Json(prop_lines))
+}
+
+/// GET /api/props/players - Every player with props for today's or tomorrow's games
+///
+/// The inverse of `/api/players/{id}/props`: prop names are matched to players the same
+/// way, so the two agree on who has lines. Sorted by prop count, most first.
+#[utoipa::path(
+    get,
+    path = "/api/props/players",
+    tag = "props",
+    responses(
+        (status = 200, body = Vec<PropPlayer>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_prop_players(
+    State(pool): State<SqlitePool>,
+    today: Today,
+) -> Result<Json<Vec<PropPlayer>>, ApiError> {
+    let players = db::get_players_with_props(&pool, &today)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get players with props: {}", e);
+            ApiError::from(e)
+        })?;
+
+    Ok(Json(players))
+}
+
+/// Query parameters for stat types
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StatTypesQuery {
+    /// Slate date (YYYY-MM-DD, default: today)
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+/// GET /api/props/stat-types?date= - List the stat types offered on the slate
+///
+/// Distinct Underdog stat names for the date, mapped to internal stat names (period stats
+/// to their `q1_*` / `first_half_points` columns) and sorted by importance. Stats in
+/// neither `UNDERDOG_STATS` nor `PERIOD_STATS` are dropped.
+#[utoipa::path(
+    get,
+    path = "/api/props/stat-types",
+    tag = "props",
+    params(StatTypesQuery),
+    responses(
+        (status = 200, body = Vec<String>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_stat_types(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<StatTypesQuery>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    let date = params.date.unwrap_or_else(|| today.ymd());
+
+    let mut stat_names = db::get_slate_stat_names(&pool, &date).await?;
+    stat_names.sort_by_key(|stat_name| underdog_stat_rank(stat_name));
+
+    let stat_types = stat_names
+        .iter()
+        .filter_map(|stat_name| season_avg_column(stat_name).map(str::to_string))
+        .collect();
+
+    Ok(Json(stat_types))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_player, insert_prop, insert_team, test_pool, Prop};
+
+    fn prop_at(scheduled_at: Option<&str>) -> UnderdogProp {
+        UnderdogProp {
+            id: 1,
+            full_name: "Test Player".to_string(),
+            team_name: None,
+            opponent_name: None,
+            stat_name: "points".to_string(),
+            stat_value: 20.5,
+            choice: "over".to_string(),
+            american_price: Some(-110),
+            decimal_price: None,
+            scheduled_at: scheduled_at.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn prop_game_started_compares_the_et_tip_off_with_today() {
+        // 8:00 PM ET on Jan 15
+        let today = Today::fixed("2026-01-15T20:00");
+
+        // 7:30 PM ET tonight has tipped off; 9:00 PM ET tonight and tomorrow haven't
+        assert!(prop_game_started(&prop_at(Some("2026-01-16T00:30:00Z")), &today));
+        assert!(!prop_game_started(&prop_at(Some("2026-01-16T02:00:00Z")), &today));
+        assert!(!prop_game_started(&prop_at(Some("2026-01-17T00:30:00Z")), &today));
+    }
+
+    #[test]
+    fn prop_game_started_treats_missing_or_bad_timestamps_as_upcoming() {
+        let today = Today::fixed("2026-01-15T20:00");
+        assert!(!prop_game_started(&prop_at(None), &today));
+        assert!(!prop_game_started(&prop_at(Some("tonight")), &today));
+    }
+
+    #[tokio::test]
+    async fn stat_types_are_the_slates_known_stats_by_importance() {
+        let pool = test_pool().await;
+        let on = |stat: &'static str, scheduled_at: &'static str| Prop {
+            player: "Stat Player",
+            team: "HOM Team",
+            opponent: "AWY Team",
+            stat,
+            line: 10.5,
+            choice: "over",
+            price: -110,
+            scheduled_at,
+            updated_at: "2026-01-10 12:00:00",
+        };
+        for stat in ["assists", "pts_rebs", "points", "period_1_points", "fantasy_points"] {
+            insert_prop(&pool, on(stat, "2026-01-11T00:30:00Z")).await;
+        }
+        // Another player's line on the same stat is listed once
+        insert_prop(&pool, Prop { player: "Other Player", ..on("points", "2026-01-10T23:00:00Z") }).await;
+        // Next week's slate
+        insert_prop(&pool, on("steals", "2026-01-17T00:30:00Z")).await;
+
+        let today = Today::fixed("2026-01-10");
+        let Json(stat_types) = get_stat_types(State(pool), today, Query(StatTypesQuery { date: None }))
+            .await
+            .unwrap();
+
+        // Period stats follow the full-game ones; unknown stats have no internal name
+        assert_eq!(stat_types, ["points", "assists", "pts_plus_reb", "q1_points"]);
+    }
+
+    #[tokio::test]
+    async fn period_props_compare_with_quarter_and_half_averages() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_player(&pool, 10, "Period Player", 1, "G").await;
+        sqlx::query("UPDATE player_stats SET q1_points = 6.0, first_half_points = 12.5 WHERE player_id = 10")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (stat, line) in [("points", 24.5), ("period_1_points", 6.5), ("period_1_2_points", 12.5), ("period_1_assists", 1.5)] {
+            insert_prop(&pool, Prop {
+                player: "Period Player",
+                team: "HOM Team",
+                opponent: "AWY Team",
+                stat,
+                line,
+                choice: "over",
+                price: -110,
+                scheduled_at: "2026-01-11T00:30:00Z",
+                updated_at: "2026-01-10 12:00:00",
+            })
+            .await;
+        }
+
+        let today = Today::fixed("2026-01-10T12:00");
+        let Json(response) = get_player_props(State(pool), today, Path(10), Query(PlayerPropsQuery { order: None, exclude_injured: false }))
+            .await
+            .unwrap();
+
+        let line = |stat: &str| response.props.iter().find(|line| line.stat_name == stat).unwrap();
+        assert_eq!(line("period_1_points").season_avg, Some(6.0));
+        assert_eq!(line("period_1_points").line_vs_avg_pct, Some(8.3));
+        assert_eq!(line("period_1_2_points").season_avg, Some(12.5));
+        assert_eq!(line("period_1_2_points").line_vs_avg_pct, Some(0.0));
+        // No q1_assists average seeded
+        assert_eq!(line("period_1_assists").season_avg, None);
+        assert_eq!(line("period_1_assists").line_vs_avg_pct, None);
+        // Game logs have no quarter splits, so only full-game stats get hit rates
+        assert!(line("period_1_points").hit_rate_last_10.is_none());
+        assert_eq!(line("points").season_avg, Some(20.0));
+    }
+
+    #[test]
+    fn alpha_order_sorts_by_stat_name_then_line() {
+        let props: Vec<UnderdogProp> = [("points", 24.5), ("rebounds", 8.5), ("assists", 6.5), ("points", 22.5), ("blks_stls", 2.5)]
+            .into_iter()
+            .map(|(stat, line)| UnderdogProp { stat_name: stat.to_string(), stat_value: line, ..prop_at(None) })
+            .collect();
+        let mut lines = group_prop_lines(&props);
+        let order = |lines: &[PropLine]| -> Vec<(String, f64)> {
+            lines.iter().map(|l| (l.stat_name.clone(), l.line)).collect()
+        };
+        let expected = |pairs: &[(&str, f64)]| -> Vec<(String, f64)> {
+            pairs.iter().map(|&(stat, line)| (stat.to_string(), line)).collect()
+        };
+        assert_eq!(
+            order(&lines),
+            expected(&[("points", 22.5), ("points", 24.5), ("rebounds", 8.5), ("assists", 6.5), ("blks_stls", 2.5)])
+        );
+
+        sort_prop_lines(&mut lines, PropOrder::parse(Some("alpha")).unwrap(), None);
+
+        assert_eq!(
+            order(&lines),
+            expected(&[("assists", 6.5), ("blks_stls", 2.5), ("points", 22.5), ("points", 24.5), ("rebounds", 8.5)])
+        );
+    }
+
+    #[test]
+    fn unknown_order_is_rejected() {
+        assert!(matches!(PropOrder::parse(Some("random")), Err(ApiError::BadRequest(_))));
+        assert_eq!(PropOrder::parse(None).unwrap(), PropOrder::Default);
+    }
+}
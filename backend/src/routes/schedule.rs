@@ -1,17 +1,26 @@
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
 };
-use chrono::Timelike;
-use chrono_tz::America::New_York;
 use serde::Deserialize;
+use utoipa::IntoParams;
 use sqlx::sqlite::SqlitePool;
 use crate::db;
-use crate::models::{ScheduleResponse, ScheduleGame, RosterResponse, GameWithRosters, TeamInfo};
+use crate::error::{ApiError, ErrorResponse};
+use crate::util::freshness::data_as_of;
+use crate::util::game_status::GameStatus;
+use crate::util::game_time::has_game_started;
+use crate::util::today::Today;
+use crate::models::{
+    ScheduleResponse, ScheduleGame, ScheduleGameContext, RosterResponse, GameWithRosters, TeamInfo,
+    TeamScheduleContext, GameContext, ScheduleRow,
+};
+use crate::routes::teams::project_team_points;
+use std::collections::HashMap;
 
 /// Query parameters for filtering schedule
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ScheduleQuery {
     /// Filter by date (format: YYYY-MM-DD)
     #[serde(default)]
@@ -19,152 +28,299 @@ pub struct ScheduleQuery {
     /// Filter by team abbreviation (e.g., "LAL", "BOS")
     #[serde(default)]
     pub team: Option<String>,
+    /// Start of a date range (YYYY-MM-DD, inclusive); requires end_date, not allowed with date
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// End of a date range (YYYY-MM-DD, inclusive); requires start_date, not allowed with date
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Only games in this state: scheduled, live or final
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Query parameters for the today/upcoming schedule endpoints
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StatusQuery {
+    /// Only games in this state: scheduled, live or final
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Validate a `status` filter
+fn parse_status(status: Option<&str>) -> Result<Option<GameStatus>, ApiError> {
+    status
+        .map(|value| {
+            GameStatus::parse_filter(value).ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "Invalid status '{}'. Use 'scheduled', 'live' or 'final'",
+                    value
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Schedule response for `rows`, keeping only games in `status` when given
+fn schedule_response(rows: &[ScheduleRow], status: Option<GameStatus>) -> ScheduleResponse {
+    let rows: Vec<&ScheduleRow> = rows
+        .iter()
+        .filter(|r| status.is_none_or(|s| GameStatus::from_raw(r.game_status.as_deref().unwrap_or_default()) == s))
+        .collect();
+    let games: Vec<ScheduleGame> = rows.iter().map(|r| r.to_schedule_game()).collect();
+    let count = games.len();
+    let data_as_of = data_as_of(rows.iter().filter_map(|r| r.last_updated.as_deref()));
+    ScheduleResponse { games, count, data_as_of }
 }
 
 /// GET /api/schedule - Get NBA game schedule
 ///
 /// Query params:
 /// - date: Filter games by date (YYYY-MM-DD format)
-/// - team: Filter games by team abbreviation
+/// - start_date/end_date: Filter games to an inclusive date range; not allowed with date
+/// - team: Filter games by team abbreviation, alone or within the date or range
+/// - status: Filter games to scheduled, live or final
+#[utoipa::path(
+    get,
+    path = "/api/schedule",
+    tag = "schedule",
+    params(ScheduleQuery),
+    responses(
+        (status = 200, body = ScheduleResponse),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_schedule(
     State(pool): State<SqlitePool>,
+    today: Today,
     Query(params): Query<ScheduleQuery>,
-) -> Result<Json<ScheduleResponse>, StatusCode> {
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    let status = parse_status(params.status.as_deref())?;
+    if params.date.is_some() && (params.start_date.is_some() || params.end_date.is_some()) {
+        return Err(ApiError::BadRequest(
+            "date can't be combined with start_date/end_date".to_string(),
+        ));
+    }
+    let range = match (&params.start_date, &params.end_date) {
+        (Some(start), Some(end)) => {
+            let parse = |date: &str| {
+                chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                    ApiError::BadRequest(format!("Invalid date '{}'. Use YYYY-MM-DD", date))
+                })
+            };
+            if parse(start)? > parse(end)? {
+                return Err(ApiError::BadRequest(format!(
+                    "start_date {} is after end_date {}",
+                    start, end
+                )));
+            }
+            Some((start, end))
+        }
+        (None, None) => None,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "start_date and end_date must be given together".to_string(),
+            ));
+        }
+    };
+
     let db_result = if let Some(date) = &params.date {
         db::get_schedule_by_date(&pool, date).await
+    } else if let Some((start, end)) = range {
+        db::get_schedule_by_range(&pool, start, end).await
     } else if let Some(team) = &params.team {
         db::get_schedule_by_team(&pool, team).await
     } else {
-        db::get_todays_schedule(&pool).await
+        db::get_todays_schedule(&pool, &today).await
+    };
+
+    // A team alongside a date or range narrows those games rather than being ignored
+    let plays = |row: &ScheduleRow| {
+        params.team.as_deref().is_none_or(|team| {
+            [&row.home_team_abbreviation, &row.away_team_abbreviation]
+                .iter()
+                .any(|abbreviation| abbreviation.as_deref() == Some(team))
+        })
     };
 
     match db_result {
-        Ok(rows) => {
-            let games: Vec<ScheduleGame> = rows.iter().map(|r| r.to_schedule_game()).collect();
-            let count = games.len();
-            Ok(Json(ScheduleResponse { games, count }))
+        Ok(mut rows) => {
+            rows.retain(plays);
+            Ok(Json(schedule_response(&rows, status)))
         }
         Err(e) => {
             tracing::error!("Failed to get schedule: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e.into())
         }
     }
 }
 
-/// GET /api/schedule/today - Get today's games
-pub async fn get_todays_games(
+/// GET /api/schedule/:game_id/context - Pace-based projected total and spread for a game
+///
+/// Each side is projected with the same pace and rating blend as
+/// `/api/teams/{id}/projected-points`; the total is their sum and the spread their difference.
+#[utoipa::path(
+    get,
+    path = "/api/schedule/{game_id}/context",
+    tag = "schedule",
+    params(("game_id" = String, Path, description = "Game id")),
+    responses(
+        (status = 200, body = GameContext),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_game_context(
     State(pool): State<SqlitePool>,
-) -> Result<Json<ScheduleResponse>, StatusCode> {
-    match db::get_todays_schedule(&pool).await {
-        Ok(rows) => {
-            let games: Vec<ScheduleGame> = rows.iter().map(|r| r.to_schedule_game()).collect();
-            let count = games.len();
-            Ok(Json(ScheduleResponse { games, count }))
-        }
-        Err(e) => {
-            tracing::error!("Failed to get today's schedule: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
-}
+    Path(game_id): Path<String>,
+) -> Result<Json<GameContext>, ApiError> {
+    let row = db::get_schedule_game(&pool, &game_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("game {} not found", game_id)))?;
 
-/// GET /api/schedule/upcoming - Get upcoming games for next 7 days
-pub async fn get_upcoming_games(
-    State(pool): State<SqlitePool>,
-) -> Result<Json<ScheduleResponse>, StatusCode> {
-    match db::get_upcoming_schedule(&pool, 7).await {
-        Ok(rows) => {
-            let games: Vec<ScheduleGame> = rows.iter().map(|r| r.to_schedule_game()).collect();
-            let count = games.len();
-            Ok(Json(ScheduleResponse { games, count }))
-        }
-        Err(e) => {
-            tracing::error!("Failed to get upcoming schedule: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
-}
+    let home = project_team_points(&pool, row.home_team_id, row.away_team_id).await?;
+    let away = project_team_points(&pool, row.away_team_id, row.home_team_id).await?;
 
-/// Parse game time string (e.g., "7:30 PM" or "7:30 pm ET") into hour and minute
-fn parse_game_time(time_str: &str) -> Option<(u32, u32)> {
-    // Remove timezone indicator if present
-    let clean_time = time_str
-        .trim()
-        .trim_end_matches("ET")
-        .trim_end_matches("EST")
-        .trim_end_matches("EDT")
-        .trim();
-
-    // Match pattern like "7:30 PM" or "10:00 AM"
-    let re = regex::Regex::new(r"(\d{1,2}):(\d{2})\s*(AM|PM|am|pm)").ok()?;
-    let caps = re.captures(clean_time)?;
-
-    let mut hours: u32 = caps.get(1)?.as_str().parse().ok()?;
-    let minutes: u32 = caps.get(2)?.as_str().parse().ok()?;
-    let am_pm = caps.get(3)?.as_str().to_uppercase();
-
-    // Convert to 24-hour format
-    if am_pm == "PM" && hours != 12 {
-        hours += 12;
-    } else if am_pm == "AM" && hours == 12 {
-        hours = 0;
-    }
+    Ok(Json(GameContext {
+        game: row.to_schedule_game(),
+        expected_possessions: home.expected_pace,
+        projected_total: home.projected_points + away.projected_points,
+        projected_spread: away.projected_points - home.projected_points,
+        home,
+        away,
+    }))
+}
 
-    Some((hours, minutes))
+/// Full days off between a team's previous game (in its sorted `dates`) and `date`
+fn rest_days_before(dates: &[chrono::NaiveDate], date: chrono::NaiveDate) -> Option<i64> {
+    let previous = dates.partition_point(|d| *d < date).checked_sub(1)?;
+    Some((date - dates[previous]).num_days() - 1)
 }
 
-/// Check if a game has started based on its date and time
-/// Game times are in ET (Eastern Time), so we convert current time to ET for comparison
-fn has_game_started(game_date: &str, game_time: &Option<String>) -> bool {
-    // Get current time in ET (Eastern Time) since NBA game times are in ET
-    let now_utc = chrono::Utc::now();
-    let now_et = now_utc.with_timezone(&New_York);
-
-    // Parse game date
-    let parsed_date = chrono::NaiveDate::parse_from_str(game_date, "%Y-%m-%d");
-    let game_date_parsed = match parsed_date {
-        Ok(d) => d,
-        Err(_) => return false, // Can't parse, assume not started
-    };
+/// GET /api/teams/:abbr/schedule/context - A team's games with rest days and back-to-backs
+///
+/// Rest is measured from each side's previous game in the schedule table, so the
+/// opponent's back-to-back is flagged too.
+#[utoipa::path(
+    get,
+    path = "/api/teams/{abbr}/schedule/context",
+    tag = "schedule",
+    params(("abbr" = String, Path, description = "Team abbreviation (e.g. LAL)")),
+    responses(
+        (status = 200, body = TeamScheduleContext),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_schedule_context(
+    State(pool): State<SqlitePool>,
+    Path(abbr): Path<String>,
+) -> Result<Json<TeamScheduleContext>, ApiError> {
+    let abbr = abbr.to_uppercase();
+    let rows = db::get_full_schedule(&pool).await?;
 
-    // Compare dates in ET
-    let today_et = now_et.date_naive();
-    if game_date_parsed > today_et {
-        return false; // Game is in the future
+    let mut dates_by_team: HashMap<i64, Vec<chrono::NaiveDate>> = HashMap::new();
+    for row in &rows {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(&row.game_date, "%Y-%m-%d") {
+            dates_by_team.entry(row.home_team_id).or_default().push(date);
+            dates_by_team.entry(row.away_team_id).or_default().push(date);
+        }
     }
+    let rest_days = |team_id: i64, date: Option<chrono::NaiveDate>| {
+        rest_days_before(dates_by_team.get(&team_id)?, date?)
+    };
 
-    // If game is before today (in ET), it has started (and finished)
-    if game_date_parsed < today_et {
-        return true;
-    }
+    let games: Vec<ScheduleGameContext> = rows
+        .iter()
+        .filter_map(|row| {
+            let is_home = row.home_team_abbreviation.as_deref() == Some(abbr.as_str());
+            if !is_home && row.away_team_abbreviation.as_deref() != Some(abbr.as_str()) {
+                return None;
+            }
+            let game = row.to_schedule_game();
+            let (team, opponent) = if is_home {
+                (&game.home_team, game.away_team.clone())
+            } else {
+                (&game.away_team, game.home_team.clone())
+            };
+            let date = chrono::NaiveDate::parse_from_str(&row.game_date, "%Y-%m-%d").ok();
+            let team_rest = rest_days(team.id, date);
+            let opponent_rest = rest_days(opponent.id, date);
 
-    // Game is today (in ET) - check the time
-    let time_str = match game_time {
-        Some(t) => t,
-        None => return false, // No time info, assume not started
-    };
+            Some(ScheduleGameContext {
+                is_home,
+                rest_days: team_rest,
+                back_to_back: team_rest == Some(0),
+                opponent_rest_days: opponent_rest,
+                opponent_back_to_back: opponent_rest == Some(0),
+                opponent,
+                game,
+            })
+        })
+        .collect();
 
-    // Handle "TBD", "Scheduled", or "12:00 AM" (placeholder time) - assume not started
-    if time_str == "TBD" || time_str == "Scheduled" || time_str == "12:00 AM" {
-        return false;
+    if games.is_empty() {
+        return Err(ApiError::NotFound(format!("no scheduled games for team '{}'", abbr)));
     }
 
-    let (game_hour, game_minute) = match parse_game_time(time_str) {
-        Some((h, m)) => (h, m),
-        None => return false, // Can't parse time, assume not started
-    };
-
-    // Compare current ET time with game time (both in ET now)
-    let current_hour_et = now_et.hour();
-    let current_minute_et = now_et.minute();
+    let count = games.len();
+    Ok(Json(TeamScheduleContext { team_abbreviation: abbr, games, count }))
+}
 
-    if current_hour_et > game_hour {
-        return true;
-    } else if current_hour_et == game_hour && current_minute_et >= game_minute {
-        return true;
+/// GET /api/schedule/today - Get today's games
+#[utoipa::path(
+    get,
+    path = "/api/schedule/today",
+    tag = "schedule",
+    params(StatusQuery),
+    responses(
+        (status = 200, body = ScheduleResponse),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_todays_games(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<StatusQuery>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    let status = parse_status(params.status.as_deref())?;
+    match db::get_todays_schedule(&pool, &today).await {
+        Ok(rows) => Ok(Json(schedule_response(&rows, status))),
+        Err(e) => {
+            tracing::error!("Failed to get today's schedule: {}", e);
+            Err(e.into())
+        }
     }
+}
 
-    false
+/// GET /api/schedule/upcoming - Get upcoming games for next 7 days
+#[utoipa::path(
+    get,
+    path = "/api/schedule/upcoming",
+    tag = "schedule",
+    params(StatusQuery),
+    responses(
+        (status = 200, body = ScheduleResponse),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_upcoming_games(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<StatusQuery>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    let status = parse_status(params.status.as_deref())?;
+    match db::get_upcoming_schedule(&pool, 7, &today).await {
+        Ok(rows) => Ok(Json(schedule_response(&rows, status))),
+        Err(e) => {
+            tracing::error!("Failed to get upcoming schedule: {}", e);
+            Err(e.into())
+        }
+    }
 }
 
 /// GET /api/schedule/upcoming/rosters - Get upcoming games (today + tomorrow) with full player rosters
@@ -172,48 +328,62 @@ fn has_game_started(game_date: &str, game_time: &Option<String>) -> bool {
 /// Returns today's and tomorrow's games that haven't started yet.
 /// Games are filtered out once their scheduled start time has passed.
 /// Each game includes full roster for both teams with player info and injury status.
+#[utoipa::path(
+    get,
+    path = "/api/schedule/upcoming/rosters",
+    tag = "schedule",
+    responses(
+        (status = 200, body = RosterResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_upcoming_rosters(
     State(pool): State<SqlitePool>,
-) -> Result<Json<RosterResponse>, StatusCode> {
+    today: Today,
+) -> Result<Json<RosterResponse>, ApiError> {
     // Get today + tomorrow games
-    let schedule_rows = db::get_upcoming_schedule_for_roster(&pool)
+    let schedule_rows = db::get_upcoming_schedule_for_roster(&pool, &today)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get upcoming schedule: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiError::from(e)
         })?;
 
     // Filter out games that have already started
     let upcoming_games: Vec<_> = schedule_rows
         .into_iter()
-        .filter(|game| !has_game_started(&game.game_date, &game.game_time))
+        .filter(|game| !has_game_started(&game.game_date, &game.game_time, &today))
         .collect();
 
     if upcoming_games.is_empty() {
         return Ok(Json(RosterResponse {
             games: vec![],
             count: 0,
+            data_as_of: None,
         }));
     }
 
     let mut games_with_rosters = Vec::new();
+    let mut stamps: Vec<String> = upcoming_games.iter().filter_map(|g| g.last_updated.clone()).collect();
 
     for game in &upcoming_games {
         // Get rosters for both teams
-        let home_roster = db::get_team_roster(&pool, game.home_team_id)
+        let home_roster = db::get_team_roster(&pool, game.home_team_id, &today)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to get home roster for team {}: {}", game.home_team_id, e);
-                StatusCode::INTERNAL_SERVER_ERROR
+                ApiError::from(e)
             })?;
 
-        let away_roster = db::get_team_roster(&pool, game.away_team_id)
+        let away_roster = db::get_team_roster(&pool, game.away_team_id, &today)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to get away roster for team {}: {}", game.away_team_id, e);
-                StatusCode::INTERNAL_SERVER_ERROR
+                ApiError::from(e)
             })?;
 
+        stamps.extend(home_roster.iter().chain(&away_roster).filter_map(|r| r.last_updated.clone()));
+
         games_with_rosters.push(GameWithRosters {
             game_id: game.game_id.clone(),
             game_date: game.game_date.clone(),
@@ -240,5 +410,78 @@ pub async fn get_upcoming_rosters(
     Ok(Json(RosterResponse {
         games: games_with_rosters,
         count,
+        data_as_of: data_as_of(stamps.iter().map(String::as_str)),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::*;
+    use axum::http::Uri;
+
+    fn query<T: serde::de::DeserializeOwned>(uri: &str) -> Query<T> {
+        Query::try_from_uri(&uri.parse::<Uri>().unwrap()).unwrap()
+    }
+
+    async fn seed_week(pool: &SqlitePool) {
+        for (id, abbr) in [(1, "AAA"), (2, "BBB"), (3, "CCC")] {
+            insert_team(pool, id, abbr).await;
+        }
+        for (game_id, date, home, away) in [
+            ("r0", "2026-01-01", 1, 2),
+            ("r1", "2026-01-02", 1, 2),
+            ("r2", "2026-01-03", 2, 3),
+            ("r3", "2026-01-04", 3, 1),
+            ("r4", "2026-01-05", 1, 3),
+        ] {
+            insert_game(pool, game_id, date, home, away).await;
+        }
+    }
+
+    async fn game_ids(pool: &SqlitePool, uri: &str) -> Result<Vec<String>, ApiError> {
+        let Json(response) = get_schedule(State(pool.clone()), Today::fixed("2026-01-03"), query(uri)).await?;
+        Ok(response.games.into_iter().map(|g| g.game_id).collect())
+    }
+
+    #[tokio::test]
+    async fn schedule_range_includes_both_ends() {
+        let pool = test_pool().await;
+        seed_week(&pool).await;
+
+        let ids = game_ids(&pool, "/?start_date=2026-01-02&end_date=2026-01-04").await.unwrap();
+        assert_eq!(ids, ["r1", "r2", "r3"]);
+        let ids = game_ids(&pool, "/?start_date=2026-01-03&end_date=2026-01-03").await.unwrap();
+        assert_eq!(ids, ["r2"]);
+    }
+
+    #[tokio::test]
+    async fn schedule_team_narrows_a_range_or_date() {
+        let pool = test_pool().await;
+        seed_week(&pool).await;
+
+        let ids = game_ids(&pool, "/?start_date=2026-01-02&end_date=2026-01-05&team=AAA").await.unwrap();
+        assert_eq!(ids, ["r1", "r3", "r4"]);
+        let ids = game_ids(&pool, "/?date=2026-01-03&team=AAA").await.unwrap();
+        assert!(ids.is_empty());
+        let ids = game_ids(&pool, "/?team=CCC").await.unwrap();
+        assert_eq!(ids, ["r2", "r3", "r4"]);
+    }
+
+    #[tokio::test]
+    async fn schedule_rejects_reversed_incomplete_or_conflicting_ranges() {
+        let pool = test_pool().await;
+        seed_week(&pool).await;
+
+        for uri in [
+            "/?start_date=2026-01-04&end_date=2026-01-02",
+            "/?start_date=2026-01-02",
+            "/?end_date=2026-01-04",
+            "/?start_date=2026-01-02&end_date=01/04/2026",
+            "/?date=2026-01-03&start_date=2026-01-02&end_date=2026-01-04",
+            "/?date=2026-01-03&end_date=2026-01-04",
+        ] {
+            assert!(matches!(game_ids(&pool, uri).await, Err(ApiError::BadRequest(_))), "{}", uri);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use sqlx::sqlite::SqlitePool;
+use std::collections::BTreeMap;
+use crate::cache;
+use crate::db;
+use crate::error::{ApiError, ErrorResponse};
+use crate::models::{SlateGameProps, SlatePlayerProps, SlatePropsResponse, UnderdogProp};
+use crate::routes::props::group_prop_lines;
+use crate::util::today::Today;
+
+/// Query parameters for slate endpoints
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SlateQuery {
+    /// Slate date (YYYY-MM-DD, default: today)
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+/// GET /api/slate/props-by-game?date= - Get every prop on the slate grouped by game
+///
+/// Props are matched to scheduled games by their team/opponent names. Props that
+/// don't match any game on the date are dropped. Served from the warm cache when the
+/// props data hasn't changed since it was built.
+#[utoipa::path(
+    get,
+    path = "/api/slate/props-by-game",
+    tag = "slate",
+    params(SlateQuery),
+    responses(
+        (status = 200, body = SlatePropsResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_props_by_game(
+    State(pool): State<SqlitePool>,
+    today: Today,
+    Query(params): Query<SlateQuery>,
+) -> Result<Json<SlatePropsResponse>, ApiError> {
+    let date = params.date.unwrap_or_else(|| today.ymd());
+
+    if today.is_debug() {
+        return Ok(Json(compute_props_by_game(&pool, &date).await?));
+    }
+
+    let data_version = db::get_props_data_version(&pool).await?;
+    if let Some(cached) = cache::SLATE_PROPS.get(&date, &data_version) {
+        return Ok(Json(cached));
+    }
+
+    let slate = compute_props_by_game(&pool, &date).await?;
+    cache::SLATE_PROPS.put(&date, &data_version, slate.clone());
+
+    Ok(Json(slate))
+}
+
+/// Build the slate for `date`: every scheduled game with its players' prop lines
+pub async fn compute_props_by_game(pool: &SqlitePool, date: &str) -> Result<SlatePropsResponse, sqlx::Error> {
+    let schedule = db::get_schedule_by_date(pool, date)
+        .await
+        .inspect_err(|e| tracing::error!("Failed to get schedule for slate {}: {}", date, e))?;
+
+    let props = db::get_slate_props(pool, date)
+        .await
+        .inspect_err(|e| tracing::error!("Failed to get slate props for {}: {}", date, e))?;
+
+    let mut games = Vec::new();
+
+    for row in &schedule {
+        let game = row.to_schedule_game();
+        let home = row.home_team_name.as_deref().unwrap_or_default();
+        let away = row.away_team_name.as_deref().unwrap_or_default();
+
+        // A prop belongs to this game if its team/opponent pair is this matchup, either way round
+        let mut by_player: BTreeMap<&str, Vec<&UnderdogProp>> = BTreeMap::new();
+        for prop in &props {
+            let team = prop.team_name.as_deref().unwrap_or_default();
+            let opp = prop.opponent_name.as_deref().unwrap_or_default();
+            if (team == home && opp == away) || (team == away && opp == home) {
+                by_player.entry(prop.full_name.as_str()).or_default().push(prop);
+            }
+        }
+
+        let players: Vec<SlatePlayerProps> = by_player
+            .into_iter()
+            .map(|(player_name, player_props)| SlatePlayerProps {
+                player_name: player_name.to_string(),
+                team_name: player_props[0].team_name.clone(),
+                props: group_prop_lines(player_props),
+            })
+            .collect();
+
+        games.push(SlateGameProps {
+            game_id: game.game_id,
+            game_date: game.game_date,
+            game_time: game.game_time,
+            home_team: game.home_team,
+            away_team: game.away_team,
+            players,
+        });
+    }
+
+    let count = games.len();
+    Ok(SlatePropsResponse { date: date.to_string(), games, count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::*;
+
+    fn prop<'a>(player: &'a str, team: &'a str, opponent: &'a str, stat: &'a str, choice: &'a str) -> Prop<'a> {
+        Prop { player, team, opponent, stat, line: 20.5, choice, price: -115, scheduled_at: "2026-01-11T00:30:00Z", updated_at: "2026-01-10 12:00:00" }
+    }
+
+    #[tokio::test]
+    async fn props_are_grouped_under_their_game() {
+        let pool = test_pool().await;
+        for (team_id, abbreviation) in [(1, "HOM"), (2, "AWY"), (3, "THR"), (4, "FOU")] {
+            insert_team(&pool, team_id, abbreviation).await;
+        }
+        insert_game(&pool, "g1", "2026-01-10", 1, 2).await;
+        insert_game(&pool, "g2", "2026-01-10", 3, 4).await;
+
+        insert_prop(&pool, prop("Alpha Guard", "HOM Team", "AWY Team", "points", "over")).await;
+        insert_prop(&pool, prop("Alpha Guard", "HOM Team", "AWY Team", "points", "under")).await;
+        insert_prop(&pool, prop("Bravo Forward", "AWY Team", "HOM Team", "rebounds", "over")).await;
+        insert_prop(&pool, prop("Charlie Center", "FOU Team", "THR Team", "assists", "over")).await;
+        insert_prop(&pool, Prop { scheduled_at: "2026-01-20T00:30:00Z", ..prop("Delta Wing", "HOM Team", "AWY Team", "points", "over") }).await;
+
+        let slate = compute_props_by_game(&pool, "2026-01-10").await.unwrap();
+
+        assert_eq!(slate.count, 2);
+        let players = |game: &SlateGameProps| -> Vec<String> {
+            game.players.iter().map(|p| p.player_name.clone()).collect()
+        };
+        assert_eq!(slate.games[0].game_id, "g1");
+        assert_eq!(players(&slate.games[0]), ["Alpha Guard", "Bravo Forward"]);
+        assert_eq!(slate.games[1].game_id, "g2");
+        assert_eq!(players(&slate.games[1]), ["Charlie Center"]);
+
+        // Both sides of a line collapse into one entry
+        let alpha = &slate.games[0].players[0];
+        assert_eq!(alpha.props.len(), 1);
+        assert_eq!(alpha.props[0].over_odds, Some(-115));
+        assert_eq!(alpha.props[0].under_odds, Some(-115));
+    }
+}
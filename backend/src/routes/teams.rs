@@ -1,68 +1,694 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
 };
 use serde::Deserialize;
+use utoipa::IntoParams;
 use sqlx::sqlite::SqlitePool;
-use crate::models::{Team, TeamStats};
+use crate::models::{PositionDefense, ProjectedPoints, ScoringDefense, Standing, Team, TeamPointsAllowed, TeamRanking, TeamStats, TeamWithDivision};
 use crate::db;
+use crate::error::{ApiError, ErrorResponse};
+use crate::util::image_urls::logo_url;
+use crate::util::rank::{rank_of, rank_values};
 
 // Query parameters for searching teams
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchTeamQuery {
     #[serde(default)]
     abbr: Option<String>,
 }
 
-// GET /api/teams - List all teams
+// Query parameters for listing teams
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TeamsQuery {
+    /// "east" or "west"; every team when omitted
+    #[serde(default)]
+    conference: Option<String>,
+}
+
+/// Parse a `conference` query parameter ("east"/"west", case-insensitive)
+fn parse_conference(conference: Option<&str>) -> Result<Option<&'static str>, ApiError> {
+    match conference.map(str::to_lowercase).as_deref() {
+        None => Ok(None),
+        Some("east") => Ok(Some("East")),
+        Some("west") => Ok(Some("West")),
+        Some(other) => Err(ApiError::BadRequest(format!(
+            "Invalid conference '{}'. Use 'east' or 'west'",
+            other
+        ))),
+    }
+}
+
+/// Attach the team's conference, division and logo URL
+fn with_division(team: Team) -> TeamWithDivision {
+    TeamWithDivision {
+        conference: db::team_conference(&team.abbreviation).map(str::to_string),
+        division: db::team_division(&team.abbreviation).map(str::to_string),
+        logo_url: logo_url(team.team_id),
+        team,
+    }
+}
+
+// GET /api/teams?conference=east - List all teams with conference and division
+#[utoipa::path(
+    get,
+    path = "/api/teams",
+    tag = "teams",
+    summary = "List all teams with conference and division",
+    params(TeamsQuery),
+    responses(
+        (status = 200, body = Vec<TeamWithDivision>),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_teams(
     State(pool): State<SqlitePool>,
-) -> Result<Json<Vec<Team>>, StatusCode> {
+    Query(params): Query<TeamsQuery>,
+) -> Result<Json<Vec<TeamWithDivision>>, ApiError> {
+    let conference = parse_conference(params.conference.as_deref())?;
+
     let teams = db::get_all_teams(&pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?
+        .into_iter()
+        .map(with_division)
+        .filter(|team| conference.is_none() || team.conference.as_deref() == conference)
+        .collect();
 
     Ok(Json(teams))
 }
 
 // GET /api/teams/:id - Get team by ID
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}",
+    tag = "teams",
+    summary = "Get team by ID",
+    params(("id" = i64, Path, description = "Team id")),
+    responses(
+        (status = 200, body = TeamWithDivision),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_team_by_id(
     State(pool): State<SqlitePool>,
     Path(team_id): Path<i64>,
-) -> Result<Json<Team>, StatusCode> {
+) -> Result<Json<TeamWithDivision>, ApiError> {
     let team = db::get_team_by_id(&pool, team_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", team_id)))?;
 
-    Ok(Json(team))
+    Ok(Json(with_division(team)))
 }
 
 // GET /api/teams/search?abbr=LAL - Search team by abbreviation
+#[utoipa::path(
+    get,
+    path = "/api/teams/search",
+    tag = "teams",
+    summary = "Search team by abbreviation",
+    params(SearchTeamQuery),
+    responses(
+        (status = 200, body = TeamWithDivision),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn search_team(
     State(pool): State<SqlitePool>,
     Query(params): Query<SearchTeamQuery>,
-) -> Result<Json<Team>, StatusCode> {
-    let abbr = params.abbr.ok_or(StatusCode::BAD_REQUEST)?;
+) -> Result<Json<TeamWithDivision>, ApiError> {
+    let abbr = params
+        .abbr
+        .ok_or_else(|| ApiError::BadRequest("Missing required query parameter 'abbr'".to_string()))?;
 
     let team = db::get_team_by_abbreviation(&pool, &abbr)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team '{}' not found", abbr)))?;
+
+    Ok(Json(with_division(team)))
+}
+
+/// Metrics the team leaderboard can rank by, and whether lower values rank higher
+const RANKING_METRICS: [(&str, bool); 4] = [
+    ("off_rating", false),
+    ("def_rating", true),
+    ("net_rating", false),
+    ("pace", false),
+];
+
+// Query parameters for team rankings
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TeamRankingsQuery {
+    /// One of `RANKING_METRICS` (default: net_rating)
+    #[serde(default = "default_ranking_metric")]
+    metric: String,
+}
+
+fn default_ranking_metric() -> String {
+    "net_rating".to_string()
+}
+
+// GET /api/teams/rankings?metric=def_rating - All teams ranked by a pace/rating metric
+// Rank 1 is the best team for the metric (lowest DefRtg, highest everything else)
+#[utoipa::path(
+    get,
+    path = "/api/teams/rankings",
+    tag = "teams",
+    summary = "All teams ranked by a pace/rating metric",
+    params(TeamRankingsQuery),
+    responses(
+        (status = 200, body = Vec<TeamRanking>),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_rankings(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<TeamRankingsQuery>,
+) -> Result<Json<Vec<TeamRanking>>, ApiError> {
+    let &(metric, ascending) = RANKING_METRICS
+        .iter()
+        .find(|(name, _)| *name == params.metric)
+        .ok_or_else(|| {
+            let valid: Vec<&str> = RANKING_METRICS.iter().map(|(name, _)| *name).collect();
+            ApiError::BadRequest(format!(
+                "Invalid metric '{}'. Valid metrics: {}",
+                params.metric,
+                valid.join(", ")
+            ))
+        })?;
+
+    let value = |stats: &TeamStats| match metric {
+        "off_rating" => stats.off_rating,
+        "def_rating" => stats.def_rating,
+        "net_rating" => stats.net_rating,
+        _ => stats.pace,
+    };
+
+    // Teams without a value for the metric can't be ranked
+    let stats: Vec<(TeamStats, f32)> = db::get_all_team_stats(&pool, &db::CURRENT_SEASON)
+        .await?
+        .into_iter()
+        .filter_map(|s| value(&s).map(|v| (s, v)))
+        .collect();
+    let ranks = rank_values(&stats, |(_, v)| *v, ascending);
+
+    let teams = db::get_all_teams(&pool)
+        .await?;
+
+    let mut rankings: Vec<TeamRanking> = stats
+        .into_iter()
+        .zip(ranks)
+        .map(|((stats, value), rank)| {
+            let team = teams.iter().find(|t| t.team_id == stats.team_id);
+            TeamRanking {
+                rank,
+                team_name: team.map(|t| t.full_name.clone()).unwrap_or_default(),
+                abbreviation: team.map(|t| t.abbreviation.clone()).unwrap_or_default(),
+                metric: metric.to_string(),
+                value,
+                stats,
+            }
+        })
+        .collect();
+    rankings.sort_by(|a, b| a.rank.cmp(&b.rank).then_with(|| a.team_name.cmp(&b.team_name)));
+
+    Ok(Json(rankings))
+}
+
+// Query parameters for standings
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StandingsQuery {
+    /// "east" or "west"; the whole league when omitted
+    #[serde(default)]
+    conference: Option<String>,
+}
+
+// GET /api/standings?conference=east - Teams by win percentage with games back
+#[utoipa::path(
+    get,
+    path = "/api/standings",
+    tag = "teams",
+    summary = "Teams by win percentage with games back",
+    params(StandingsQuery),
+    responses(
+        (status = 200, body = Vec<Standing>),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_standings(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<StandingsQuery>,
+) -> Result<Json<Vec<Standing>>, ApiError> {
+    let conference = parse_conference(params.conference.as_deref())?;
+
+    let teams = db::get_all_teams(&pool)
+        .await?;
+
+    let mut standings: Vec<Standing> = db::get_all_team_stats(&pool, &db::CURRENT_SEASON)
+        .await?
+        .into_iter()
+        .filter_map(|stats| {
+            let team = teams.iter().find(|t| t.team_id == stats.team_id)?;
+            let team_conference = db::team_conference(&team.abbreviation);
+            if conference.is_some() && team_conference != conference {
+                return None;
+            }
+
+            let wins = stats.wins.unwrap_or(0);
+            let losses = stats.losses.unwrap_or(0);
+            let games = wins + losses;
+            Some(Standing {
+                rank: 0,
+                team_id: team.team_id,
+                team_name: team.full_name.clone(),
+                abbreviation: team.abbreviation.clone(),
+                conference: team_conference.map(str::to_string),
+                wins,
+                losses,
+                win_pct: if games > 0 { wins as f32 / games as f32 } else { 0.0 },
+                games_back: 0.0,
+            })
+        })
+        .collect();
+
+    let ranks = rank_values(&standings, |s| s.win_pct, false);
+    for (standing, rank) in standings.iter_mut().zip(ranks) {
+        standing.rank = rank;
+    }
+    standings.sort_by(|a, b| a.rank.cmp(&b.rank).then_with(|| b.wins.cmp(&a.wins)));
+
+    // Games back from the leader: half the difference in wins plus half in losses
+    if let Some((leader_wins, leader_losses)) = standings.first().map(|s| (s.wins, s.losses)) {
+        for standing in standings.iter_mut() {
+            standing.games_back =
+                ((leader_wins - standing.wins) + (standing.losses - leader_losses)) as f32 / 2.0;
+        }
+    }
+
+    Ok(Json(standings))
+}
+
+// Query parameters for stats allowed by position
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AllowedQuery {
+    /// Stat from `db::ALLOWED_STAT_COLUMNS` (default: points)
+    #[serde(default = "default_allowed_stat")]
+    stat: String,
+    /// "G", "F" or "C"
+    position: String,
+    /// e.g. "2025-26" (default: `CURRENT_SEASON`)
+    season: Option<String>,
+}
+
+fn default_allowed_stat() -> String {
+    "points".to_string()
+}
+
+// GET /api/teams/:id/allowed?stat=points&position=G&season= - Defense vs position
+// Per-game average of the stat the team allows to that position, ranked across the league
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/allowed",
+    tag = "teams",
+    summary = "Defense vs position",
+    params(("id" = i64, Path, description = "Team id"), AllowedQuery),
+    responses(
+        (status = 200, body = PositionDefense),
+        (status = 400, description = "Invalid parameter", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_allowed_by_position(
+    State(pool): State<SqlitePool>,
+    Path(team_id): Path<i64>,
+    Query(params): Query<AllowedQuery>,
+) -> Result<Json<PositionDefense>, ApiError> {
+    if !db::ALLOWED_STAT_COLUMNS.iter().any(|(name, _)| *name == params.stat) {
+        let valid: Vec<&str> = db::ALLOWED_STAT_COLUMNS.iter().map(|(name, _)| *name).collect();
+        return Err(ApiError::BadRequest(format!(
+            "Invalid stat '{}'. Valid stats: {}",
+            params.stat,
+            valid.join(", ")
+        )));
+    }
+
+    let position = params.position.to_uppercase();
+    if !["G", "F", "C"].contains(&position.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid position '{}'. Use 'G', 'F' or 'C'",
+            params.position
+        )));
+    }
+
+    let team = db::get_team_by_id(&pool, team_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", team_id)))?;
+
+    let season = params.season.unwrap_or_else(|| db::CURRENT_SEASON.clone());
+    let teams = db::get_stat_allowed_by_position(&pool, &params.stat, &position, &season)
+        .await?;
+    let allowed = teams
+        .iter()
+        .find(|t| t.team_id == team_id)
+        .ok_or_else(|| ApiError::NotFound(format!(
+            "no {} allowed to {} for team {} in season {}",
+            params.stat, position, team_id, season
+        )))?;
+
+    let rank = rank_of(&teams, |t| t.allowed, true, |t| t.team_id == team_id).unwrap_or_default();
+    let league_avg = teams.iter().map(|t| t.allowed).sum::<f32>() / teams.len() as f32;
+
+    Ok(Json(PositionDefense {
+        team_id,
+        team_name: team.full_name,
+        stat: params.stat,
+        position,
+        allowed_per_game: allowed.allowed,
+        games: allowed.games,
+        rank,
+        teams_ranked: teams.len() as i32,
+        league_avg,
+    }))
+}
+
+/// Points allowed per 100 possessions at the team's pace; None without a usable pace
+fn per_100(team: &TeamPointsAllowed) -> Option<f32> {
+    team.pace.filter(|pace| *pace > 0.0).map(|pace| team.pts_allowed * 100.0 / pace)
+}
+
+// GET /api/teams/:id/scoring-defense?season= - Points allowed per game and per 100 possessions
+// Opponent points come from game logs; pace from team_pace normalizes them to 100 possessions
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/scoring-defense",
+    tag = "teams",
+    summary = "Points allowed per game and per 100 possessions",
+    params(("id" = i64, Path, description = "Team id"), TeamStatsQuery),
+    responses(
+        (status = 200, body = ScoringDefense),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_scoring_defense(
+    State(pool): State<SqlitePool>,
+    Path(team_id): Path<i64>,
+    Query(params): Query<TeamStatsQuery>,
+) -> Result<Json<ScoringDefense>, ApiError> {
+    let season = params.season.unwrap_or_else(|| db::CURRENT_SEASON.clone());
+
+    let team = db::get_team_by_id(&pool, team_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", team_id)))?;
+
+    let teams = db::get_team_points_allowed(&pool, &season).await?;
+    let allowed = teams
+        .iter()
+        .find(|t| t.team_id == team_id)
+        .ok_or_else(|| ApiError::NotFound(format!("no game logs against team {} in season {}", team_id, season)))?;
+
+    let is_team = |t: &&TeamPointsAllowed| t.team_id == team_id;
+    let pts_allowed_rank = rank_of(&teams, |t| t.pts_allowed, true, |t| t.team_id == team_id).unwrap_or_default();
+    let league_avg_pts_allowed = teams.iter().map(|t| t.pts_allowed).sum::<f32>() / teams.len() as f32;
+
+    // Pace-based ranks only among teams with a pace for the season
+    let with_pace: Vec<&TeamPointsAllowed> = teams.iter().filter(|t| per_100(t).is_some()).collect();
+    let pace_rank = rank_of(&with_pace, |t| t.pace.unwrap_or_default(), false, is_team);
+    let pts_allowed_per_100_rank = rank_of(&with_pace, |t| per_100(t).unwrap_or_default(), true, is_team);
+    let league_avg_pts_allowed_per_100 = (!with_pace.is_empty()).then(|| {
+        with_pace.iter().filter_map(|t| per_100(t)).sum::<f32>() / with_pace.len() as f32
+    });
+
+    Ok(Json(ScoringDefense {
+        team_id,
+        team_name: team.full_name,
+        season,
+        games: allowed.games,
+        pts_allowed: allowed.pts_allowed,
+        pts_allowed_rank,
+        pace: allowed.pace,
+        pace_rank,
+        pts_allowed_per_100: per_100(allowed),
+        pts_allowed_per_100_rank,
+        def_rating: allowed.def_rating,
+        teams_ranked: teams.len() as i32,
+        league_avg_pts_allowed,
+        league_avg_pts_allowed_per_100,
+    }))
+}
+
+/// Sanity bounds on projected pace (possessions) and offensive rating, so a bad
+/// team_pace row can't produce an absurd total
+const PACE_BOUNDS: (f32, f32) = (85.0, 115.0);
+const RATING_BOUNDS: (f32, f32) = (90.0, 135.0);
+
+// Query parameters for projected points
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectedPointsQuery {
+    opponent_id: i64,
+}
+
+// GET /api/teams/:id/projected-points?opponent_id=123 - Project a team's points vs an opponent
+//
+// Pace and efficiency are each blended multiplicatively against the league average:
+//   expected_pace       = team_pace * opp_pace / league_pace
+//   expected_off_rating = team_off_rating * opp_def_rating / league_rating
+//   projected_points    = expected_pace * expected_off_rating / 100
+// where league_rating is the league-average offensive rating. Expected pace is clamped
+// to PACE_BOUNDS and expected rating to RATING_BOUNDS, which bounds the projection to
+// roughly 76-155 points.
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/projected-points",
+    tag = "teams",
+    summary = "Project a team's points vs an opponent",
+    params(("id" = i64, Path, description = "Team id"), ProjectedPointsQuery),
+    responses(
+        (status = 200, body = ProjectedPoints),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_projected_points(
+    State(pool): State<SqlitePool>,
+    Path(team_id): Path<i64>,
+    Query(params): Query<ProjectedPointsQuery>,
+) -> Result<Json<ProjectedPoints>, ApiError> {
+    Ok(Json(project_team_points(&pool, team_id, params.opponent_id).await?))
+}
+
+/// Pace/rating projection of `team_id`'s points against `opponent_id` (see
+/// `get_team_projected_points`); 404 when either team or its current-season ratings are missing
+pub async fn project_team_points(
+    pool: &SqlitePool,
+    team_id: i64,
+    opponent_id: i64,
+) -> Result<ProjectedPoints, ApiError> {
+    let team = db::get_team_by_id(pool, team_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", team_id)))?;
+    let opponent = db::get_team_by_id(pool, opponent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("team {} not found", opponent_id)))?;
+
+    let no_ratings = |id: i64| ApiError::NotFound(format!("no pace/ratings for team {}", id));
+    let team_stats = db::get_team_stats(pool, team_id, &db::CURRENT_SEASON)
+        .await?
+        .ok_or_else(|| no_ratings(team_id))?;
+    let opp_stats = db::get_team_stats(pool, opponent_id, &db::CURRENT_SEASON)
+        .await?
+        .ok_or_else(|| no_ratings(opponent_id))?;
+    let league = db::get_league_team_averages(pool, &db::CURRENT_SEASON)
+        .await?;
+
+    let (Some(team_pace), Some(team_off_rating)) = (team_stats.pace, team_stats.off_rating) else {
+        return Err(no_ratings(team_id));
+    };
+    let (Some(opp_pace), Some(opp_def_rating)) = (opp_stats.pace, opp_stats.def_rating) else {
+        return Err(no_ratings(opponent_id));
+    };
+    // Both teams have ratings, so the league averages exist
+    let league_avg_pace = league.pace.unwrap_or(team_pace);
+    let league_avg_rating = league.off_rating.unwrap_or(team_off_rating);
+
+    let (expected_pace, expected_off_rating) = expected_pace_and_rating(
+        (team_pace, opp_pace, league_avg_pace),
+        (team_off_rating, opp_def_rating, league_avg_rating),
+    );
+
+    Ok(ProjectedPoints {
+        team_id,
+        team_name: team.full_name,
+        opponent_id,
+        opponent_name: opponent.full_name,
+        team_pace,
+        opp_pace,
+        league_avg_pace,
+        expected_pace,
+        team_off_rating,
+        opp_def_rating,
+        league_avg_rating,
+        expected_off_rating,
+        projected_points: expected_pace * expected_off_rating / 100.0,
+    })
+}
+
+/// Expected pace and offensive rating from (team, opponent, league average) paces and
+/// (team offense, opponent defense, league average) ratings, clamped to the sanity bounds
+fn expected_pace_and_rating(pace: (f32, f32, f32), rating: (f32, f32, f32)) -> (f32, f32) {
+    let (team_pace, opp_pace, league_avg_pace) = pace;
+    let (team_off_rating, opp_def_rating, league_avg_rating) = rating;
+    let expected_pace = (team_pace * opp_pace / league_avg_pace).clamp(PACE_BOUNDS.0, PACE_BOUNDS.1);
+    let expected_off_rating = (team_off_rating * opp_def_rating / league_avg_rating)
+        .clamp(RATING_BOUNDS.0, RATING_BOUNDS.1);
+    (expected_pace, expected_off_rating)
+}
 
-    Ok(Json(team))
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TeamStatsQuery {
+    pub season: Option<String>,  // e.g. "2025-26", defaults to db::CURRENT_SEASON
 }
 
-// GET /api/teams/:id/stats - Get team pace and ratings
+// GET /api/teams/:id/stats?season= - Get team pace and ratings
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/stats",
+    tag = "teams",
+    summary = "Get team pace and ratings",
+    params(("id" = i64, Path, description = "Team id"), TeamStatsQuery),
+    responses(
+        (status = 200, body = TeamStats),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_team_stats(
     State(pool): State<SqlitePool>,
     Path(team_id): Path<i64>,
-) -> Result<Json<TeamStats>, StatusCode> {
-    let stats = db::get_team_stats(&pool, team_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    Query(params): Query<TeamStatsQuery>,
+) -> Result<Json<TeamStats>, ApiError> {
+    let season = params.season.unwrap_or_else(|| db::CURRENT_SEASON.clone());
+    let stats = db::get_team_stats(&pool, team_id, &season)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("no stats for team {} in season {}", team_id, season)))?;
 
     Ok(Json(stats))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::*;
+    use axum::http::Uri;
+
+    fn query<T: serde::de::DeserializeOwned>(uri: &str) -> Query<T> {
+        Query::try_from_uri(&uri.parse::<Uri>().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn allowed_by_position_defaults_to_the_current_season() {
+        let pool = test_pool().await;
+        insert_team(&pool, 1, "HOM").await;
+        insert_team(&pool, 2, "AWY").await;
+        insert_player(&pool, 40, "Home Guard", 1, "G").await;
+        for (game_id, date, season, pts) in [("a1", "2026-01-01", db::CURRENT_SEASON.as_str(), 20), ("a2", "2025-03-01", "2024-25", 40)] {
+            insert_game(&pool, game_id, date, 1, 2).await;
+            insert_log(&pool, 40, 1, game_id, date, season, Line::new(30.0, pts, 4, 2)).await;
+        }
+
+        let Json(current) = get_team_allowed_by_position(State(pool.clone()), Path(2), query("/?position=g"))
+            .await
+            .unwrap();
+        assert_eq!(current.allowed_per_game, 20.0);
+        assert_eq!(current.position, "G");
+
+        let Json(previous) = get_team_allowed_by_position(State(pool.clone()), Path(2), query("/?position=G&season=2024-25"))
+            .await
+            .unwrap();
+        assert_eq!(previous.allowed_per_game, 40.0);
+
+        let none = get_team_allowed_by_position(State(pool), Path(2), query("/?position=G&season=2019-20")).await;
+        assert!(matches!(none, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn scoring_defense_ranks_per_100_only_among_teams_with_pace() {
+        let pool = test_pool().await;
+        for (id, abbr) in [(1, "AAA"), (2, "BBB"), (3, "CCC"), (4, "OPP")] {
+            insert_team(&pool, id, abbr).await;
+        }
+        insert_player(&pool, 40, "Visiting Scorer", 4, "G").await;
+        // Team 2 allows the most per game but plays fastest; team 3 has no pace row
+        for (game_id, date, defense, pts) in [("s1", "2026-01-01", 1, 100), ("s2", "2026-01-02", 2, 110), ("s3", "2026-01-03", 3, 90)] {
+            insert_game(&pool, game_id, date, defense, 4).await;
+            insert_log(&pool, 40, 4, game_id, date, SEASON, Line::new(36.0, pts, 5, 5)).await;
+        }
+        insert_team_pace(&pool, 1, 80.0, 112.0).await;
+        insert_team_pace(&pool, 2, 110.0, 108.0).await;
+
+        let Json(fast) = get_team_scoring_defense(State(pool.clone()), Path(2), query("/?season=2025-26"))
+            .await
+            .unwrap();
+        assert_eq!(fast.pts_allowed, 110.0);
+        assert_eq!(fast.pts_allowed_rank, 3);
+        assert_eq!(fast.pace_rank, Some(1));
+        assert_eq!(fast.pts_allowed_per_100, Some(100.0));
+        assert_eq!(fast.pts_allowed_per_100_rank, Some(1));
+        assert_eq!(fast.teams_ranked, 3);
+        assert_eq!(fast.league_avg_pts_allowed, 100.0);
+        // (125 + 100) / 2, without team 3
+        assert_eq!(fast.league_avg_pts_allowed_per_100, Some(112.5));
+
+        let Json(slow) = get_team_scoring_defense(State(pool.clone()), Path(1), query("/?season=2025-26"))
+            .await
+            .unwrap();
+        assert_eq!(slow.pts_allowed_rank, 2);
+        assert_eq!(slow.pace_rank, Some(2));
+        assert_eq!(slow.pts_allowed_per_100, Some(125.0));
+        assert_eq!(slow.pts_allowed_per_100_rank, Some(2));
+
+        let Json(no_pace) = get_team_scoring_defense(State(pool), Path(3), query("/?season=2025-26"))
+            .await
+            .unwrap();
+        assert_eq!(no_pace.pts_allowed_rank, 1);
+        assert_eq!(no_pace.pace, None);
+        assert_eq!(no_pace.pace_rank, None);
+        assert_eq!(no_pace.pts_allowed_per_100, None);
+        assert_eq!(no_pace.pts_allowed_per_100_rank, None);
+        assert_eq!(no_pace.def_rating, None);
+    }
+
+    #[test]
+    fn strong_offense_against_weak_defense_projects_more() {
+        // Average paces, league-average rating 115
+        let projected = |off_rating: f32, def_rating: f32| {
+            let (pace, rating) = expected_pace_and_rating((100.0, 100.0, 100.0), (off_rating, def_rating, 115.0));
+            pace * rating / 100.0
+        };
+
+        // League-average everything projects the league-average rating
+        assert_eq!(expected_pace_and_rating((100.0, 100.0, 100.0), (115.0, 115.0, 115.0)), (100.0, 115.0));
+        assert!(projected(120.0, 120.0) > projected(115.0, 115.0));
+        assert!(projected(120.0, 120.0) > projected(120.0, 108.0));
+        assert!(projected(108.0, 108.0) < projected(115.0, 115.0));
+    }
+
+    #[test]
+    fn expected_pace_and_rating_are_clamped() {
+        assert_eq!(expected_pace_and_rating((130.0, 130.0, 100.0), (115.0, 115.0, 115.0)).0, PACE_BOUNDS.1);
+        assert_eq!(expected_pace_and_rating((100.0, 100.0, 100.0), (60.0, 60.0, 115.0)).1, RATING_BOUNDS.0);
+    }
+}
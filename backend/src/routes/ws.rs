@@ -0,0 +1,158 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use crate::db;
+use crate::models::PropsSocketMessage;
+use crate::routes::props::{group_prop_lines, prop_game_started};
+use crate::util::today::Today;
+
+/// Most players one connection can follow, matching the batch props limit
+const MAX_SUBSCRIPTIONS: usize = 50;
+
+/// Client message: players to start or stop following. Either list may be omitted.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct PropsSubscription {
+    pub subscribe: Vec<i64>,
+    pub unsubscribe: Vec<i64>,
+}
+
+/// GET /api/ws/props - Live prop line updates over a WebSocket
+///
+/// Clients send `{"subscribe": [2544, 203999]}` (and optionally `"unsubscribe"`). Each
+/// newly subscribed player's current lines are pushed right away; after that, every
+/// `PROPS_WS_POLL_SECS` the latest `updated_at` of each followed player's upcoming props
+/// is checked and their lines are re-sent only when it changed.
+pub async fn props_socket(
+    State(pool): State<SqlitePool>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| stream_props(socket, pool))
+}
+
+async fn stream_props(mut socket: WebSocket, pool: SqlitePool) {
+    let poll_secs: u64 = std::env::var("PROPS_WS_POLL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10);
+    let mut ticker = tokio::time::interval(Duration::from_secs(poll_secs.max(1)));
+
+    let mut subscribed: HashSet<i64> = HashSet::new();
+    // Latest updated_at already sent per player (None: sent as having no props)
+    let mut last_sent: HashMap<i64, Option<String>> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let request: PropsSubscription = match serde_json::from_str(&text) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        let message = format!("Invalid subscription message: {}", e);
+                        if send(&mut socket, &PropsSocketMessage::Error { message }).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+
+                for id in &request.unsubscribe {
+                    subscribed.remove(id);
+                    last_sent.remove(id);
+                }
+                let added: Vec<i64> = request
+                    .subscribe
+                    .into_iter()
+                    .filter(|id| !subscribed.contains(id))
+                    .collect();
+                if subscribed.len() + added.len() > MAX_SUBSCRIPTIONS {
+                    let message = format!("At most {} players per connection", MAX_SUBSCRIPTIONS);
+                    if send(&mut socket, &PropsSocketMessage::Error { message }).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                subscribed.extend(&added);
+
+                if push_changes(&mut socket, &pool, &added, &mut last_sent).await.is_err() {
+                    break;
+                }
+            }
+            _ = ticker.tick() => {
+                let ids: Vec<i64> = subscribed.iter().copied().collect();
+                if push_changes(&mut socket, &pool, &ids, &mut last_sent).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Send current lines for each of `player_ids` whose latest update differs from what
+/// this connection last saw. Errs only when the socket is gone; database errors are
+/// logged and retried on the next tick.
+async fn push_changes(
+    socket: &mut WebSocket,
+    pool: &SqlitePool,
+    player_ids: &[i64],
+    last_sent: &mut HashMap<i64, Option<String>>,
+) -> Result<(), axum::Error> {
+    if player_ids.is_empty() {
+        return Ok(());
+    }
+
+    // Re-read each tick so a long-lived socket rolls over to the next day's props
+    let today = Today::now();
+    let latest = match db::get_latest_prop_updates(pool, player_ids, &today).await {
+        Ok(latest) => latest,
+        Err(e) => {
+            tracing::error!("Failed to check prop updates: {}", e);
+            return Ok(());
+        }
+    };
+    let changed: Vec<i64> = player_ids
+        .iter()
+        .copied()
+        .filter(|id| last_sent.get(id) != Some(&latest.get(id).cloned()))
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    let mut props_by_player = match db::get_batch_player_props(pool, &changed, None, &today).await {
+        Ok(props) => props,
+        Err(e) => {
+            tracing::error!("Failed to get props for live update: {}", e);
+            return Ok(());
+        }
+    };
+
+    for player_id in changed {
+        let props = props_by_player
+            .remove(&player_id)
+            .map(|props| group_prop_lines(props.iter().filter(|prop| !prop_game_started(prop, &today))))
+            .unwrap_or_default();
+        let updated_at = latest.get(&player_id).cloned();
+        last_sent.insert(player_id, updated_at.clone());
+        send(socket, &PropsSocketMessage::Props { player_id, updated_at, props }).await?;
+    }
+
+    Ok(())
+}
+
+async fn send(socket: &mut WebSocket, message: &PropsSocketMessage) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(message).unwrap_or_default();
+    socket.send(Message::Text(json.into())).await
+}
@@ -1,24 +1,139 @@
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
     response::Json,
 };
 use sqlx::sqlite::SqlitePool;
-use crate::models::{TeamDefensiveZones};
+use crate::models::{TeamDefensiveZones, ZoneLeagueAverage, ZoneRank, ZoneStrength};
+use crate::cache;
 use crate::db;
+use crate::error::{ApiError, ErrorResponse};
 
 // GET /api/teams/:id/defensive-zones - Get team's defensive zones
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/defensive-zones",
+    tag = "teams",
+    summary = "Get team's defensive zones",
+    params(("id" = i64, Path, description = "Team id")),
+    responses(
+        (status = 200, body = Vec<TeamDefensiveZones>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 pub async fn get_team_defensive_zones(
     State(pool): State<SqlitePool>,
     Path(team_id): Path<i64>,
-) -> Result<Json<Vec<TeamDefensiveZones>>, StatusCode> {
+) -> Result<Json<Vec<TeamDefensiveZones>>, ApiError> {
     let zones = db::get_defensive_zones(&pool, team_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if zones.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound(format!("no defensive zones for team {}", team_id)));
     }
 
     Ok(Json(zones))
 }
+
+// GET /api/teams/:id/defensive-zones/ranks - Team's league rank defending each shooting zone
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/defensive-zones/ranks",
+    tag = "teams",
+    summary = "Team's league rank defending each shooting zone",
+    params(("id" = i64, Path, description = "Team id")),
+    responses(
+        (status = 200, body = Vec<ZoneRank>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_defensive_zone_ranks(
+    State(pool): State<SqlitePool>,
+    Path(team_id): Path<i64>,
+) -> Result<Json<Vec<ZoneRank>>, ApiError> {
+    let zones = db::get_defensive_zones(&pool, team_id)
+        .await?;
+
+    if zones.is_empty() {
+        return Err(ApiError::NotFound(format!("no defensive zones for team {}", team_id)));
+    }
+
+    let ranks = db::compute_zone_ranks(&pool)
+        .await?;
+    let averages = cached_zone_league_averages(&pool)
+        .await?;
+
+    let zone_ranks = averages
+        .into_iter()
+        .map(|avg| ZoneRank {
+            opp_fg_pct: zones.iter().find(|z| z.zone_name == avg.zone_name).map(|z| z.opp_fg_pct),
+            rank: ranks.get(&(team_id, avg.zone_name.clone())).copied(),
+            teams_ranked: avg.teams,
+            league_avg_pct: avg.league_avg_pct,
+            is_three: avg.is_three,
+            zone_name: avg.zone_name,
+        })
+        .collect();
+
+    Ok(Json(zone_ranks))
+}
+
+// GET /api/teams/:id/zone-strengths - Get team's zones ordered from best to worst defended
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}/zone-strengths",
+    tag = "teams",
+    summary = "Get team's zones ordered from best to worst defended",
+    params(("id" = i64, Path, description = "Team id")),
+    responses(
+        (status = 200, body = Vec<ZoneStrength>),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_team_zone_strengths(
+    State(pool): State<SqlitePool>,
+    Path(team_id): Path<i64>,
+) -> Result<Json<Vec<ZoneStrength>>, ApiError> {
+    let strengths = db::get_team_zone_strengths(&pool, team_id)
+        .await?;
+
+    if strengths.is_empty() {
+        return Err(ApiError::NotFound(format!("no zone strengths for team {}", team_id)));
+    }
+
+    Ok(Json(strengths))
+}
+
+// GET /api/zones/league-averages - League-average opponent FG% per zone, with best/worst defenses
+// Served from cache until the zone defense data changes
+#[utoipa::path(
+    get,
+    path = "/api/zones/league-averages",
+    tag = "zones",
+    summary = "League-average opponent FG% per zone, with best/worst defenses",
+    responses(
+        (status = 200, body = Vec<ZoneLeagueAverage>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+pub async fn get_zone_league_averages(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<ZoneLeagueAverage>>, ApiError> {
+    Ok(Json(cached_zone_league_averages(&pool).await?))
+}
+
+/// Zone league averages, from cache while the zone defense data is unchanged
+async fn cached_zone_league_averages(pool: &SqlitePool) -> Result<Vec<ZoneLeagueAverage>, ApiError> {
+    let data_version = db::get_zone_data_version(pool).await?;
+
+    if let Some(cached) = cache::ZONE_LEAGUE_AVERAGES.get(&data_version) {
+        return Ok(cached);
+    }
+
+    let averages = db::get_zone_league_averages(pool).await?;
+    cache::ZONE_LEAGUE_AVERAGES.put(&data_version, averages.clone());
+
+    Ok(averages)
+}
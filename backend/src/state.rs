@@ -0,0 +1,25 @@
+use axum::extract::FromRef;
+use sqlx::sqlite::SqlitePool;
+use std::sync::Arc;
+use crate::cache::MatchupCache;
+
+/// Shared router state. Handlers extract just the part they need
+/// (`State<SqlitePool>`, `State<Arc<MatchupCache>>`) via `FromRef`. The pool is
+/// read-only unless `DB_READ_ONLY` is off; handlers never write.
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: SqlitePool,
+    pub matchup_cache: Arc<MatchupCache>,
+}
+
+impl FromRef<AppState> for SqlitePool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<MatchupCache> {
+    fn from_ref(state: &AppState) -> Self {
+        state.matchup_cache.clone()
+    }
+}
@@ -0,0 +1,25 @@
+//! `data_as_of` stamps for list responses, so clients can tell when they're looking at
+//! stale data because a nightly scrape failed.
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// Parse a stored `last_updated` ("2025-01-15 08:00:00", "2025-01-15T08:00:00.123",
+/// RFC 3339 or a bare date). SQLite's CURRENT_TIMESTAMP is UTC, so naive values are UTC.
+fn parse_last_updated(raw: &str) -> Option<NaiveDateTime> {
+    let raw = raw.trim();
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+        .or_else(|| chrono::DateTime::parse_from_rfc3339(raw).ok().map(|dt| dt.naive_utc()))
+        .or_else(|| NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+}
+
+/// The latest of `stamps` as an ISO 8601 UTC timestamp ("2025-01-15T08:00:00Z").
+/// Unparseable stamps are skipped; None if nothing parses.
+pub fn data_as_of<'a>(stamps: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    stamps
+        .into_iter()
+        .filter_map(parse_last_updated)
+        .max()
+        .map(|latest| latest.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
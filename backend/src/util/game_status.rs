@@ -0,0 +1,86 @@
+//! Coarse game state from the schedule's free-form `game_status` text. The scraper stores
+//! whatever the source shows ("Final", "Final/OT", "Q3 5:32", "Half", "7:30 pm ET", or the
+//! NBA's numeric 1/2/3), so every caller should categorize through `GameStatus::from_raw`.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GameStatus {
+    Scheduled,
+    Live,
+    Final,
+}
+
+impl GameStatus {
+    /// Category for a stored `game_status`. Anything not recognizably live or final
+    /// (tip-off times, "TBD", "PPD", empty) counts as scheduled.
+    pub fn from_raw(raw: &str) -> Self {
+        let status = raw.trim().to_lowercase();
+        let is_period = |word: &str| {
+            // "q3", "ot", "2ot"
+            word.strip_prefix('q').is_some_and(|n| n.parse::<u8>().is_ok())
+                || word.trim_start_matches(|c: char| c.is_ascii_digit()) == "ot"
+        };
+
+        if status == "3" || status.starts_with("final") || status.starts_with("f/") || status == "f" {
+            GameStatus::Final
+        } else if status == "2"
+            || ["qtr", "quarter", "half", "progress", "live", "end of", "overtime"]
+                .iter()
+                .any(|marker| status.contains(marker))
+            || status.split_whitespace().any(is_period)
+        {
+            GameStatus::Live
+        } else {
+            GameStatus::Scheduled
+        }
+    }
+
+    /// Parse a `status` filter value ("scheduled", "live" or "final", any case)
+    pub fn parse_filter(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "scheduled" => Some(GameStatus::Scheduled),
+            "live" => Some(GameStatus::Live),
+            "final" => Some(GameStatus::Final),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_statuses_map_to_categories() {
+        for (raw, expected) in [
+            ("Final", GameStatus::Final),
+            ("Final/OT", GameStatus::Final),
+            ("F/OT", GameStatus::Final),
+            ("3", GameStatus::Final),
+            ("Q3 5:32", GameStatus::Live),
+            ("Half", GameStatus::Live),
+            ("End of 3rd", GameStatus::Live),
+            ("2OT", GameStatus::Live),
+            ("2", GameStatus::Live),
+            ("7:30 pm ET", GameStatus::Scheduled),
+            ("PPD", GameStatus::Scheduled),
+            ("", GameStatus::Scheduled),
+            ("1", GameStatus::Scheduled),
+        ] {
+            assert_eq!(GameStatus::from_raw(raw), expected, "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn parse_filter_accepts_only_known_categories() {
+        assert_eq!(GameStatus::parse_filter(" Live "), Some(GameStatus::Live));
+        assert_eq!(GameStatus::parse_filter("FINAL"), Some(GameStatus::Final));
+        assert_eq!(GameStatus::parse_filter("scheduled"), Some(GameStatus::Scheduled));
+        for value in ["", "finished", "in_progress", "q3"] {
+            assert_eq!(GameStatus::parse_filter(value), None, "{:?}", value);
+        }
+    }
+}
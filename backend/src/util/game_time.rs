@@ -0,0 +1,124 @@
+//! Game start-time helpers shared by the schedule, screener and props routes.
+//! NBA game times are stored as ET wall-clock strings like "7:30 PM".
+
+use chrono::Timelike;
+use crate::util::today::Today;
+
+/// Parse game time string (e.g., "7:30 PM" or "7:30 pm ET") into hour and minute
+pub fn parse_game_time(time_str: &str) -> Option<(u32, u32)> {
+    // Remove timezone indicator if present
+    let clean_time = time_str
+        .trim()
+        .trim_end_matches("ET")
+        .trim_end_matches("EST")
+        .trim_end_matches("EDT")
+        .trim();
+
+    // Match pattern like "7:30 PM" or "10:00 AM"
+    let re = regex::Regex::new(r"(\d{1,2}):(\d{2})\s*(AM|PM|am|pm)").ok()?;
+    let caps = re.captures(clean_time)?;
+
+    let mut hours: u32 = caps.get(1)?.as_str().parse().ok()?;
+    let minutes: u32 = caps.get(2)?.as_str().parse().ok()?;
+    let am_pm = caps.get(3)?.as_str().to_uppercase();
+
+    // Convert to 24-hour format
+    if am_pm == "PM" && hours != 12 {
+        hours += 12;
+    } else if am_pm == "AM" && hours == 12 {
+        hours = 0;
+    }
+
+    Some((hours, minutes))
+}
+
+/// Check if a game has started based on its date and time, as of `today`
+/// Game times are in ET (Eastern Time), so the comparison uses today's ET time.
+/// A game counts as started from its tip-off minute onward. Placeholder times ("TBD",
+/// "Scheduled", "12:00 AM") and unparseable input are treated as not started.
+pub fn has_game_started(game_date: &str, game_time: &Option<String>, today: &Today) -> bool {
+    let now_et = today.now_et();
+
+    // Parse game date
+    let parsed_date = chrono::NaiveDate::parse_from_str(game_date, "%Y-%m-%d");
+    let game_date_parsed = match parsed_date {
+        Ok(d) => d,
+        Err(_) => return false, // Can't parse, assume not started
+    };
+
+    // Compare dates in ET
+    let today_et = now_et.date_naive();
+    if game_date_parsed > today_et {
+        return false; // Game is in the future
+    }
+
+    // If game is before today (in ET), it has started (and finished)
+    if game_date_parsed < today_et {
+        return true;
+    }
+
+    // Game is today (in ET) - check the time
+    let time_str = match game_time {
+        Some(t) => t,
+        None => return false, // No time info, assume not started
+    };
+
+    // Handle "TBD", "Scheduled", or "12:00 AM" (placeholder time) - assume not started
+    if time_str == "TBD" || time_str == "Scheduled" || time_str == "12:00 AM" {
+        return false;
+    }
+
+    let (game_hour, game_minute) = match parse_game_time(time_str) {
+        Some((h, m)) => (h, m),
+        None => return false, // Can't parse time, assume not started
+    };
+
+    // Compare current ET time with game time (both in ET now)
+    let current_hour_et = now_et.hour();
+    let current_minute_et = now_et.minute();
+
+    current_hour_et > game_hour || (current_hour_et == game_hour && current_minute_et >= game_minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> Option<String> {
+        Some(time.to_string())
+    }
+
+    #[test]
+    fn parse_game_time_handles_suffixes_and_noon_midnight() {
+        assert_eq!(parse_game_time("7:30 PM"), Some((19, 30)));
+        assert_eq!(parse_game_time("7:30 pm ET"), Some((19, 30)));
+        assert_eq!(parse_game_time("12:00 PM"), Some((12, 0)));
+        assert_eq!(parse_game_time("12:15 AM"), Some((0, 15)));
+        assert_eq!(parse_game_time("TBD"), None);
+    }
+
+    #[test]
+    fn game_starts_at_its_tip_off_minute() {
+        let game = at("7:30 PM");
+        assert!(!has_game_started("2026-01-15", &game, &Today::fixed("2026-01-15T19:29")));
+        assert!(has_game_started("2026-01-15", &game, &Today::fixed("2026-01-15T19:30")));
+        assert!(has_game_started("2026-01-15", &game, &Today::fixed("2026-01-15T22:00")));
+    }
+
+    #[test]
+    fn other_days_ignore_the_time() {
+        let today = Today::fixed("2026-01-15T12:00");
+        assert!(has_game_started("2026-01-14", &at("10:00 PM"), &today));
+        assert!(!has_game_started("2026-01-16", &at("12:30 AM"), &today));
+    }
+
+    #[test]
+    fn placeholder_times_never_start() {
+        let late = Today::fixed("2026-01-15T23:59");
+        for placeholder in ["12:00 AM", "TBD", "Scheduled", "halftime"] {
+            assert!(!has_game_started("2026-01-15", &at(placeholder), &late), "{}", placeholder);
+        }
+        assert!(!has_game_started("2026-01-15", &None, &late));
+        assert!(!has_game_started("not a date", &at("7:30 PM"), &late));
+    }
+}
@@ -0,0 +1,23 @@
+//! Player headshot and team logo URLs, so every client builds them the same way. The
+//! templates come from `PLAYER_HEADSHOT_URL_TEMPLATE` and `TEAM_LOGO_URL_TEMPLATE`, with
+//! `{player_id}` / `{team_id}` replaced by the id; both default to the NBA CDN.
+
+use std::sync::LazyLock;
+
+static PLAYER_HEADSHOT_URL_TEMPLATE: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("PLAYER_HEADSHOT_URL_TEMPLATE")
+        .unwrap_or_else(|_| "https://cdn.nba.com/headshots/nba/latest/1040x760/{player_id}.png".to_string())
+});
+
+static TEAM_LOGO_URL_TEMPLATE: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("TEAM_LOGO_URL_TEMPLATE")
+        .unwrap_or_else(|_| "https://cdn.nba.com/logos/nba/{team_id}/global/L/logo.svg".to_string())
+});
+
+pub fn headshot_url(player_id: i64) -> String {
+    PLAYER_HEADSHOT_URL_TEMPLATE.replace("{player_id}", &player_id.to_string())
+}
+
+pub fn logo_url(team_id: i64) -> String {
+    TEAM_LOGO_URL_TEMPLATE.replace("{team_id}", &team_id.to_string())
+}
@@ -0,0 +1,6 @@
+pub mod game_time;
+pub mod rank;
+pub mod freshness;
+pub mod image_urls;
+pub mod game_status;
+pub mod today;
@@ -0,0 +1,106 @@
+//! League ranking helpers shared by the play-type, zone and rebounding matchups.
+//! Ranks are competition ranks ("1224"): tied values share the best rank and the
+//! next distinct value skips ahead by the size of the tie.
+
+/// Competition ranks for `items` by `key`, parallel to `items`. With `ascending` the
+/// smallest value ranks 1 (PPP or opponent FG% allowed, where lower is better defense);
+/// otherwise the largest does. NaN keys rank behind every real value.
+pub fn rank_values<T>(items: &[T], key: impl Fn(&T) -> f32, ascending: bool) -> Vec<i32> {
+    let keys: Vec<f32> = items.iter().map(&key).collect();
+    let ranks_ahead = |a: f32, b: f32| match (a.is_nan(), b.is_nan()) {
+        (true, _) => false,
+        (false, true) => true,
+        (false, false) => if ascending { a < b } else { a > b },
+    };
+
+    keys.iter()
+        .map(|&k| keys.iter().filter(|&&other| ranks_ahead(other, k)).count() as i32 + 1)
+        .collect()
+}
+
+/// Competition rank of the first item matching `is_target`, or None if nothing matches
+pub fn rank_of<T>(
+    items: &[T],
+    key: impl Fn(&T) -> f32,
+    ascending: bool,
+    is_target: impl Fn(&T) -> bool,
+) -> Option<i32> {
+    let pos = items.iter().position(is_target)?;
+    Some(rank_values(items, key, ascending)[pos])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ties_share_a_rank_and_skip_the_next() {
+        assert_eq!(rank_values(&[10.0, 20.0, 20.0, 30.0], |&v| v, true), [1, 2, 2, 4]);
+        assert_eq!(rank_values(&[30.0, 20.0, 20.0, 10.0], |&v| v, false), [1, 2, 2, 4]);
+    }
+
+    #[test]
+    fn direction_flips_the_order() {
+        let values = [3.0, 1.0, 2.0];
+        assert_eq!(rank_values(&values, |&v| v, true), [3, 1, 2]);
+        assert_eq!(rank_values(&values, |&v| v, false), [1, 3, 2]);
+    }
+
+    #[test]
+    fn nan_ranks_last_either_way() {
+        let values = [f32::NAN, 5.0, 1.0, f32::NAN];
+        assert_eq!(rank_values(&values, |&v| v, true), [3, 2, 1, 3]);
+        assert_eq!(rank_values(&values, |&v| v, false), [3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn rank_of_finds_the_target() {
+        let teams = [(1, 110.0), (2, 105.0), (3, 105.0)];
+        assert_eq!(rank_of(&teams, |t| t.1, true, |t| t.0 == 3), Some(1));
+        assert_eq!(rank_of(&teams, |t| t.1, true, |t| t.0 == 1), Some(3));
+        assert_eq!(rank_of(&teams, |t| t.1, true, |t| t.0 == 4), None);
+    }
+
+    /// Seeded random vectors drawn from a few distinct values, so most have ties
+    fn random_vectors() -> Vec<Vec<f32>> {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(521);
+        (0..200)
+            .map(|_| {
+                let len = rng.random_range(1..30);
+                (0..len).map(|_| rng.random_range(0..8) as f32 / 2.0).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ranks_hold_over_random_vectors() {
+        for values in random_vectors() {
+            let n = values.len() as i32;
+            let asc = rank_values(&values, |&v| v, true);
+            let desc = rank_values(&values, |&v| v, false);
+            let tie_size = |v: f32| values.iter().filter(|&&o| o == v).count() as i32;
+
+            for (i, &v) in values.iter().enumerate() {
+                for (j, &w) in values.iter().enumerate() {
+                    if v == w {
+                        assert_eq!(asc[i], asc[j], "{:?}", values);
+                    }
+                }
+                assert_eq!(desc[i], n + 1 - asc[i] - (tie_size(v) - 1), "{:?}", values);
+            }
+
+            // Walking the distinct values in rank order, each tie group starts where the
+            // previous one's rank plus its size left off
+            let mut distinct = values.clone();
+            distinct.sort_by(f32::total_cmp);
+            distinct.dedup();
+            let mut expected = 1;
+            for v in distinct {
+                let i = values.iter().position(|&o| o == v).unwrap();
+                assert_eq!(asc[i], expected, "{:?}", values);
+                expected += tie_size(v);
+            }
+        }
+    }
+}
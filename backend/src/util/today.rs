@@ -0,0 +1,116 @@
+//! The "today" that schedule, props and game-started checks anchor to. It's the real clock
+//! unless overridden: `NBA_TODAY_OVERRIDE` fixes it for the whole process, and in DEV_MODE
+//! an `X-Debug-Date` request header fixes it for one request. Either takes "YYYY-MM-DD"
+//! (midnight ET, so none of that day's games have started) or "YYYY-MM-DDTHH:MM" (ET).
+//!
+//! Handlers take `Today` as an extractor and pass it down instead of reading the clock.
+
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{request::Parts, HeaderName},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime};
+use chrono_tz::{America::New_York, Tz};
+use std::convert::Infallible;
+use std::sync::LazyLock;
+
+pub const X_DEBUG_DATE: HeaderName = HeaderName::from_static("x-debug-date");
+
+/// Process-wide override from `NBA_TODAY_OVERRIDE`; unparseable values are ignored
+static TODAY_OVERRIDE: LazyLock<Option<Today>> = LazyLock::new(|| {
+    let raw = std::env::var("NBA_TODAY_OVERRIDE").ok()?;
+    let today = Today::parse(&raw);
+    match today {
+        Some(today) => tracing::warn!("NBA_TODAY_OVERRIDE: treating {} as today", today.ymd()),
+        None => tracing::warn!("Ignoring NBA_TODAY_OVERRIDE '{}'; use YYYY-MM-DD or YYYY-MM-DDTHH:MM", raw),
+    }
+    today
+});
+
+#[derive(Debug, Clone, Copy)]
+pub struct Today {
+    date: NaiveDate,
+    now_et: DateTime<Tz>,
+    /// Set from `X-Debug-Date` for this request only
+    debug: bool,
+}
+
+impl Today {
+    /// `NBA_TODAY_OVERRIDE` if set, otherwise the real clock (local date, current ET time)
+    pub fn now() -> Self {
+        TODAY_OVERRIDE.unwrap_or_else(|| Today {
+            date: chrono::Local::now().date_naive(),
+            now_et: chrono::Utc::now().with_timezone(&New_York),
+            debug: false,
+        })
+    }
+
+    /// A fixed "today" from "YYYY-MM-DD" or "YYYY-MM-DDTHH:MM" (ET)
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let at = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M")
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))?;
+        let now_et = at.and_local_timezone(New_York).earliest()?;
+        Some(Today { date: at.date(), now_et, debug: false })
+    }
+
+    /// A fixed today for tests, from "YYYY-MM-DD" or "YYYY-MM-DDTHH:MM" (ET)
+    #[cfg(test)]
+    pub fn fixed(raw: &str) -> Self {
+        Self::parse(raw).expect("YYYY-MM-DD or YYYY-MM-DDTHH:MM")
+    }
+
+    /// A fixed today for tests as if sent in `X-Debug-Date`
+    #[cfg(test)]
+    pub fn debug(raw: &str) -> Self {
+        Today { debug: true, ..Self::fixed(raw) }
+    }
+
+    /// Current time in ET, for comparing against tip-off times
+    pub fn now_et(&self) -> DateTime<Tz> {
+        self.now_et
+    }
+
+    /// Whether this request's today came from `X-Debug-Date`. Shared caches skip such
+    /// requests so debug results are never served to anyone else.
+    pub fn is_debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Today as "YYYY-MM-DD"
+    pub fn ymd(&self) -> String {
+        self.date.format("%Y-%m-%d").to_string()
+    }
+
+    /// The date `days` after today as "YYYY-MM-DD"
+    pub fn plus_days(&self, days: i64) -> String {
+        (self.date + Duration::days(days)).format("%Y-%m-%d").to_string()
+    }
+}
+
+/// DEV_MODE middleware fixing today for one request from `X-Debug-Date`. A malformed
+/// header is ignored.
+pub async fn debug_date(mut req: Request, next: Next) -> Response {
+    let today = req
+        .headers()
+        .get(&X_DEBUG_DATE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Today::parse)
+        .map(|today| Today { debug: true, ..today });
+    if let Some(today) = today {
+        req.extensions_mut().insert(today);
+    }
+    next.run(req).await
+}
+
+/// `X-Debug-Date`'s today when `debug_date` set one, otherwise `Today::now()`
+impl<S: Send + Sync> FromRequestParts<S> for Today {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<Today>().copied().unwrap_or_else(Today::now))
+    }
+}
@@ -79,9 +79,13 @@ export function AssistZonesCourt({
             </div>
             <div className="flex justify-between items-center text-xs">
               <span className="text-muted-foreground">Opp DEF Rank:</span>
-              <span className="font-mono font-semibold" style={{ color: zone.oppDefRank >= 21 ? 'var(--success)' : zone.oppDefRank >= 11 ? 'var(--accent)' : 'var(--destructive)' }}>
-                #{zone.oppDefRank} of 30
-              </span>
+              {zone.oppDefRank != null ? (
+                <span className="font-mono font-semibold" style={{ color: zone.oppDefRank >= 21 ? 'var(--success)' : zone.oppDefRank >= 11 ? 'var(--accent)' : 'var(--destructive)' }}>
+                  #{zone.oppDefRank} of 30
+                </span>
+              ) : (
+                <span className="font-mono text-muted-foreground">N/A</span>
+              )}
             </div>
             <div className="flex justify-between items-center text-xs">
               <span className="text-muted-foreground">Opp DEF FG%:</span>
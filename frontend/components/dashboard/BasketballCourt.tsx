@@ -111,7 +111,8 @@ function formatRank(rank: number): string {
 /**
  * Get defense quality label
  */
-function getDefenseLabel(rank: number): { label: string; color: string } {
+function getDefenseLabel(rank: number | null): { label: string; color: string } {
+  if (rank == null) return { label: 'No data', color: 'var(--muted-foreground)' };
   if (rank <= 10) return { label: 'Strong', color: 'var(--destructive)' };
   if (rank <= 20) return { label: 'Average', color: 'var(--accent)' };
   return { label: 'Weak', color: 'var(--success)' };
@@ -185,7 +186,7 @@ export function BasketballCourt({ zoneData, totalFga, className = '' }: Basketba
               <div className="flex justify-between items-center text-xs">
                 <span className="text-muted-foreground">Rank:</span>
                 <span className="font-mono" style={{ color: defenseInfo.color }}>
-                  {formatRank(zone.oppRank)} ({defenseInfo.label})
+                  {zone.oppRank != null ? `${formatRank(zone.oppRank)} (${defenseInfo.label})` : defenseInfo.label}
                 </span>
               </div>
             </div>
@@ -96,14 +96,14 @@ function FutureGameTooltipContent({ data, statCategory }: FutureGameTooltipProps
     );
   }
 
-  // Rebound-related stats (ranks are stored in dsz/dpt fields)
+  // Rebound-related stats
   if (statCategory === 'Rebounds') {
     return (
       <div className="space-y-1 border-t border-border pt-2 mt-2">
         <div className="text-xs font-semibold text-muted-foreground mb-1">Defensive Context</div>
-        <RankBadge rank={ctx?.dszRank} label="D-rank vs Total Reb" />
-        <RankBadge rank={ctx?.dsz2Rank} label="D-rank vs OREB" />
-        <RankBadge rank={ctx?.dptRank} label="D-rank vs DREB" />
+        <RankBadge rank={ctx?.totalRebRank} label="D-rank vs Total Reb" />
+        <RankBadge rank={ctx?.orebRank} label="D-rank vs OREB" />
+        <RankBadge rank={ctx?.drebRank} label="D-rank vs DREB" />
         <StatDisplay value={ctx?.pace} label="Pace" />
       </div>
     );
@@ -39,6 +39,11 @@ function formatOdds(odds: number | null): string {
   return odds > 0 ? `+${odds}` : `${odds}`;
 }
 
+function formatFairProb(prob: number | null): string {
+  if (prob === null) return '-';
+  return `${prob.toFixed(1)}%`;
+}
+
 function formatMatchup(home: string, away: string): string {
   const short = (name: string) => {
     const abbrevs: Record<string, string> = {
@@ -105,6 +110,7 @@ function ExpandedDetail({ books, direction }: { books: ApiSharpBookLine[]; direc
                 <th className="text-left pb-1.5 font-medium">Book</th>
                 <th className="text-right pb-1.5 font-medium">Line</th>
                 <th className="text-right pb-1.5 font-medium">{isOver ? 'Over' : 'Under'}</th>
+                <th className="text-right pb-1.5 font-medium">Fair</th>
                 <th className="text-right pb-1.5 font-medium text-muted-foreground/50">{isOver ? 'Under' : 'Over'}</th>
               </tr>
             </thead>
@@ -116,6 +122,9 @@ function ExpandedDetail({ books, direction }: { books: ApiSharpBookLine[]; direc
                   <td className="py-1.5 text-right font-mono tabular-nums">
                     {formatOdds(isOver ? book.overOdds : book.underOdds)}
                   </td>
+                  <td className="py-1.5 text-right font-mono tabular-nums">
+                    {formatFairProb(isOver ? book.overFairProb : book.underFairProb)}
+                  </td>
                   <td className="py-1.5 text-right font-mono tabular-nums text-muted-foreground/40">
                     {formatOdds(isOver ? book.underOdds : book.overOdds)}
                   </td>
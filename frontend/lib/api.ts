@@ -33,13 +33,23 @@ export interface ApiPlayer {
   first_half_points: number | null;
   games_played: number;
   last_updated: string;
+  // Shooting efficiency (fractions); absent on recent-averages responses
+  fg_pct?: number | null;
+  ts_pct?: number | null;
+  efg_pct?: number | null;
+  // Server-built image URL; absent on recent-averages responses
+  headshot_url?: string;
 }
 
+// Schedule game_status normalized by the backend
+export type ApiGameStatus = 'scheduled' | 'live' | 'final';
+
 export interface ApiScheduleGame {
   gameId: string;
   gameDate: string;
   gameTime: string;
   gameStatus: string;
+  statusCategory: ApiGameStatus;
   homeTeam: {
     id: number;
     name: string;
@@ -57,6 +67,7 @@ export interface ApiScheduleGame {
 export interface ApiScheduleResponse {
   games: ApiScheduleGame[];
   count: number;
+  dataAsOf: string | null;
 }
 
 // Roster types for tomorrow's games endpoint
@@ -93,6 +104,7 @@ export interface ApiGameWithRosters {
 export interface ApiRosterResponse {
   games: ApiGameWithRosters[];
   count: number;
+  dataAsOf: string | null;
 }
 
 export interface DnpPlayer {
@@ -137,6 +149,8 @@ export interface ApiPropLine {
   underOdds: number | null;
   opponent: string | null;
   scheduledAt: string | null;
+  seasonAvg: number | null;
+  lineVsAvgPct: number | null;
 }
 
 // Player props response
@@ -144,9 +158,19 @@ export interface ApiPlayerPropsResponse {
   playerName: string;
   opponentId: number;
   opponentName: string;
+  injuryStatus: string | null;
+  injuryDescription: string | null;
   props: ApiPropLine[];
 }
 
+// Player with props on the upcoming slate
+export interface ApiPropPlayer {
+  playerId: number;
+  playerName: string;
+  team: string | null;
+  propCount: number;
+}
+
 // Play type matchup
 export interface ApiPlayTypeMatchup {
   playType: string;
@@ -194,7 +218,7 @@ export interface ApiShootingZoneMatchup {
   playerFgPct: number;       // Player's FG% (as percentage, e.g., 38.5)
   playerVolumePct: number;   // % of player's total FGA from this zone
   oppFgPct: number;          // Opponent allows (as percentage)
-  oppRank: number;           // Opponent rank 1-30 (1 = best defense)
+  oppRank: number | null;    // Opponent rank 1-30 (1 = best defense), null without data
   leagueAvgPct: number;      // League average FG% for this zone
   advantage: number;         // League-adjusted advantage
   isThree: boolean;          // Is this a 3-point zone
@@ -240,23 +264,41 @@ export async function fetchPlayerById(playerId: number): Promise<ApiPlayer> {
   return response.json();
 }
 
+export interface ApiPlayerBatch {
+  players: ApiPlayer[];   // In the requested order
+  missing: number[];      // Requested ids with no stats
+}
+
+/**
+ * Fetch several players in one request (at most 100 ids)
+ */
+export async function fetchPlayersByIds(playerIds: number[]): Promise<ApiPlayerBatch> {
+  const response = await fetch(`${API_BASE_URL}/api/players/batch?ids=${playerIds.join(',')}`);
+  if (!response.ok) {
+    throw new Error(`Failed to fetch players: ${response.statusText}`);
+  }
+
+  return response.json();
+}
+
 /**
- * Search for a player by name
+ * Search for players by name (exact, or partial with fuzzy)
  */
-export async function searchPlayer(name: string): Promise<ApiPlayer> {
-  const response = await fetch(`${API_BASE_URL}/api/players/search?name=${encodeURIComponent(name)}`);
+export async function searchPlayers(name: string, fuzzy = false): Promise<ApiPlayer[]> {
+  const response = await fetch(`${API_BASE_URL}/api/players/search?name=${encodeURIComponent(name)}&fuzzy=${fuzzy}`);
   if (!response.ok) {
-    throw new Error(`Failed to search player: ${response.statusText}`);
+    throw new Error(`Failed to search players: ${response.statusText}`);
   }
 
   return response.json();
 }
 
 /**
- * Fetch today's schedule
+ * Fetch today's schedule, optionally only scheduled, live or final games
  */
-export async function fetchTodaysSchedule(): Promise<ApiScheduleResponse> {
-  const response = await fetch(`${API_BASE_URL}/api/schedule/today`);
+export async function fetchTodaysSchedule(status?: ApiGameStatus): Promise<ApiScheduleResponse> {
+  const query = status ? `?status=${status}` : '';
+  const response = await fetch(`${API_BASE_URL}/api/schedule/today${query}`);
   if (!response.ok) {
     throw new Error(`Failed to fetch schedule: ${response.statusText}`);
   }
@@ -325,6 +367,39 @@ export async function fetchPlayerGameLogs(
   return response.json();
 }
 
+// Active run of consecutive played games at or above a threshold
+export interface ApiStreak {
+  stat: string;
+  threshold: number;
+  games: number;
+  label: string;
+}
+
+export interface ApiGameLogsWithStreaks {
+  logs: ApiGameLog[];
+  streaks: ApiStreak[];
+}
+
+/**
+ * Fetch game logs for a player along with their active streaks
+ * @param playerId - Player ID to fetch logs for
+ * @param limit - Number of games to fetch
+ */
+export async function fetchPlayerGameLogsWithStreaks(
+  playerId: number,
+  limit?: number
+): Promise<ApiGameLogsWithStreaks> {
+  const params = new URLSearchParams({ include_streaks: 'true' });
+  if (limit) params.append('limit', limit.toString());
+
+  const response = await fetch(`${API_BASE_URL}/api/players/${playerId}/game-logs?${params.toString()}`);
+  if (!response.ok) {
+    throw new Error(`Failed to fetch game logs: ${response.statusText}`);
+  }
+
+  return response.json();
+}
+
 /**
  * Fetch underdog props for a player
  */
@@ -337,6 +412,18 @@ export async function fetchPlayerProps(playerId: number): Promise<ApiPlayerProps
   return response.json();
 }
 
+/**
+ * Fetch every player with props for today's or tomorrow's games
+ */
+export async function fetchPropPlayers(): Promise<ApiPropPlayer[]> {
+  const response = await fetch(`${API_BASE_URL}/api/props/players`);
+  if (!response.ok) {
+    throw new Error(`Failed to fetch prop players: ${response.statusText}`);
+  }
+
+  return response.json();
+}
+
 /**
  * Fetch play type matchup analysis for a player vs opponent
  */
@@ -400,7 +487,7 @@ export interface ApiAssistZoneMatchup {
   zoneName: string;
   playerAssists: number;
   playerAstPct: number;
-  oppDefRank: number;
+  oppDefRank: number | null;
   oppDefFgPct: number;
   hasData: boolean;
 }
@@ -442,6 +529,8 @@ export interface ApiSharpBookLine {
   line: number;
   overOdds: number | null;
   underOdds: number | null;
+  overFairProb: number | null;
+  underFairProb: number | null;
 }
 
 export interface ApiTopPick {
@@ -486,6 +575,38 @@ export async function fetchTopPicks(
   return response.json();
 }
 
+export interface ApiGameTopPicks {
+  homeTeam: string;
+  awayTeam: string;
+  gameDate: string;
+  gameTime: string | null;
+  picks: ApiTopPick[];
+}
+
+export interface ApiTopPicksByGameResponse {
+  games: ApiGameTopPicks[];
+  totalQualifying: number;
+  lastUpdated: string | null;
+}
+
+/**
+ * Fetch top +EV picks nested by game (one entry per matchup, best game first)
+ */
+export async function fetchTopPicksByGame(
+  gameDate?: string,
+): Promise<ApiTopPicksByGameResponse> {
+  const params = new URLSearchParams({ group_by: 'game' });
+  if (gameDate) params.append('game_date', gameDate);
+  const url = `${API_BASE_URL}/api/screener/top-picks?${params.toString()}`;
+
+  const response = await fetch(url);
+  if (!response.ok) {
+    throw new Error(`Failed to fetch top picks: ${response.statusText}`);
+  }
+
+  return response.json();
+}
+
 // Upcoming matchup context for tooltip
 export interface ApiUpcomingMatchupContext {
   opponentName: string;
@@ -512,6 +633,9 @@ export interface ApiUpcomingMatchupContext {
   reboundsAllowed: number | null;
   orebAllowed: number | null;
   drebAllowed: number | null;
+  totalRebRank: number | null;
+  orebRank: number | null;
+  drebRank: number | null;
 }
 
 /**
@@ -536,3 +660,30 @@ export async function fetchUpcomingMatchupContext(
   return response.json();
 }
 
+export interface ApiUpcomingMatchupContextAll {
+  opponentName: string;
+  points: ApiUpcomingMatchupContext;
+  assists: ApiUpcomingMatchupContext;
+  rebounds: ApiUpcomingMatchupContext;
+}
+
+/**
+ * Fetch points, assists and rebounds upcoming matchup context in one request
+ * @param playerId - Player ID
+ * @param opponentId - Opponent team ID
+ */
+export async function fetchUpcomingMatchupContextAll(
+  playerId: number,
+  opponentId: number
+): Promise<ApiUpcomingMatchupContextAll> {
+  const response = await fetch(
+    `${API_BASE_URL}/api/players/${playerId}/upcoming-matchup/all?opponent_id=${opponentId}`
+  );
+
+  if (!response.ok) {
+    throw new Error(`Failed to fetch upcoming matchup context: ${response.statusText}`);
+  }
+
+  return response.json();
+}
+